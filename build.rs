@@ -0,0 +1,18 @@
+// The `blas` feature needs a library providing the CBLAS and LAPACKE symbols. OpenBLAS, which
+// bundles both, is linked by default. Set LINALG_BLAS_LIB to a comma-separated list of libraries to
+// use others, e.g. "mkl_rt" or "cblas,lapacke,blas,lapack", and LINALG_BLAS_DIR to a directory to
+// search for them. The LAPACK integer type must be 32-bit (LP64), not ILP64.
+fn main() {
+    println!("cargo:rerun-if-env-changed=LINALG_BLAS_LIB");
+    println!("cargo:rerun-if-env-changed=LINALG_BLAS_DIR");
+    if std::env::var_os("CARGO_FEATURE_BLAS").is_none() {
+        return;
+    }
+    if let Some(dir) = std::env::var_os("LINALG_BLAS_DIR") {
+        println!("cargo:rustc-link-search=native={}", dir.to_string_lossy());
+    }
+    let libs = std::env::var("LINALG_BLAS_LIB").unwrap_or_else(|_| "openblas".to_string());
+    for lib in libs.split(',').map(str::trim).filter(|lib| !lib.is_empty()) {
+        println!("cargo:rustc-link-lib={lib}");
+    }
+}
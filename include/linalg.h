@@ -0,0 +1,108 @@
+/*
+ * C interface to the linalg crate, generated by cbindgen from src/capi.rs. Do not edit.
+ *
+ * Matrices are opaque row-major handles released with the matching _free. Functions that create
+ * a matrix return a status and write the new handle through `out` only on success; on failure
+ * linalg_last_error() describes what went wrong. Link against liblinalg.so or liblinalg.a.
+ */
+
+#ifndef LINALG_H
+#define LINALG_H
+
+#include <stddef.h>
+
+typedef enum LinalgStatus {
+  LINALG_STATUS_OK = 0,
+  LINALG_STATUS_NULL_POINTER,
+  LINALG_STATUS_TOO_LARGE,
+  LINALG_STATUS_DIMENSION_MISMATCH,
+  LINALG_STATUS_NOT_SQUARE,
+  LINALG_STATUS_SINGULAR,
+  LINALG_STATUS_NOT_POSITIVE_DEFINITE,
+  LINALG_STATUS_NOT_CONVERGED,
+  LINALG_STATUS_OTHER,
+} LinalgStatus;
+
+typedef struct LinalgMatrixF32 LinalgMatrixF32;
+
+typedef struct LinalgMatrixF64 LinalgMatrixF64;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+const char *linalg_last_error(void);
+
+const char *linalg_status_message(int status);
+
+enum LinalgStatus linalg_f64_new(const double *data,
+                                 size_t rows,
+                                 size_t cols,
+                                 struct LinalgMatrixF64 **out);
+
+void linalg_f64_free(struct LinalgMatrixF64 *m);
+
+size_t linalg_f64_rows(const struct LinalgMatrixF64 *m);
+
+size_t linalg_f64_cols(const struct LinalgMatrixF64 *m);
+
+const double *linalg_f64_data(const struct LinalgMatrixF64 *m);
+
+enum LinalgStatus linalg_f64_add(const struct LinalgMatrixF64 *a,
+                                 const struct LinalgMatrixF64 *b,
+                                 struct LinalgMatrixF64 **out);
+
+enum LinalgStatus linalg_f64_sub(const struct LinalgMatrixF64 *a,
+                                 const struct LinalgMatrixF64 *b,
+                                 struct LinalgMatrixF64 **out);
+
+enum LinalgStatus linalg_f64_mul(const struct LinalgMatrixF64 *a,
+                                 const struct LinalgMatrixF64 *b,
+                                 struct LinalgMatrixF64 **out);
+
+enum LinalgStatus linalg_f64_transpose(const struct LinalgMatrixF64 *a,
+                                       struct LinalgMatrixF64 **out);
+
+enum LinalgStatus linalg_f64_solve(const struct LinalgMatrixF64 *a,
+                                   const struct LinalgMatrixF64 *b,
+                                   struct LinalgMatrixF64 **out,
+                                   double *rcond);
+
+enum LinalgStatus linalg_f32_new(const float *data,
+                                 size_t rows,
+                                 size_t cols,
+                                 struct LinalgMatrixF32 **out);
+
+void linalg_f32_free(struct LinalgMatrixF32 *m);
+
+size_t linalg_f32_rows(const struct LinalgMatrixF32 *m);
+
+size_t linalg_f32_cols(const struct LinalgMatrixF32 *m);
+
+const float *linalg_f32_data(const struct LinalgMatrixF32 *m);
+
+enum LinalgStatus linalg_f32_add(const struct LinalgMatrixF32 *a,
+                                 const struct LinalgMatrixF32 *b,
+                                 struct LinalgMatrixF32 **out);
+
+enum LinalgStatus linalg_f32_sub(const struct LinalgMatrixF32 *a,
+                                 const struct LinalgMatrixF32 *b,
+                                 struct LinalgMatrixF32 **out);
+
+enum LinalgStatus linalg_f32_mul(const struct LinalgMatrixF32 *a,
+                                 const struct LinalgMatrixF32 *b,
+                                 struct LinalgMatrixF32 **out);
+
+enum LinalgStatus linalg_f32_transpose(const struct LinalgMatrixF32 *a,
+                                       struct LinalgMatrixF32 **out);
+
+enum LinalgStatus linalg_f32_solve(const struct LinalgMatrixF32 *a,
+                                   const struct LinalgMatrixF32 *b,
+                                   struct LinalgMatrixF32 **out,
+                                   float *rcond);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* LINALG_H */
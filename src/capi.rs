@@ -0,0 +1,508 @@
+// Every entry point has the same safety contract, stated once below
+#![allow(clippy::missing_safety_doc)]
+
+use crate::{error::LinalgError, matrix::mat::Matrix};
+use num::complex::ComplexFloat;
+use std::{
+    cell::RefCell,
+    ffi::{c_char, c_int, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    slice,
+};
+
+// A C interface to `Matrix<f64>` and `Matrix<f32>`, declared in include/linalg.h, which cbindgen
+// generates from this file (tests/capi.rs fails when it is out of date). Matrices are opaque
+// handles owned by the caller, who releases them with the matching `_free`. Entry points that
+// produce a matrix return a status and write a new handle through `out` only on success; on
+// failure `linalg_last_error` describes what went wrong. A panic inside the library never unwinds
+// into C, it is reported as `LINALG_STATUS_OTHER`. Handles and buffers must be valid for the
+// duration of the call, and a handle may be used from several threads as long as none frees it.
+
+pub struct LinalgMatrixF64(Matrix<f64>);
+pub struct LinalgMatrixF32(Matrix<f32>);
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinalgStatus {
+    Ok = 0,
+    NullPointer,
+    // rows * cols overflows
+    TooLarge,
+    DimensionMismatch,
+    NotSquare,
+    Singular,
+    NotPositiveDefinite,
+    NotConverged,
+    // Errors from parts of the library this interface does not reach yet
+    Other,
+}
+
+impl From<&LinalgError> for LinalgStatus {
+    fn from(err: &LinalgError) -> Self {
+        match err {
+            LinalgError::DimensionMismatch { .. } => LinalgStatus::DimensionMismatch,
+            LinalgError::NotSquare { .. } => LinalgStatus::NotSquare,
+            LinalgError::Singular { .. } => LinalgStatus::Singular,
+            LinalgError::NotPositiveDefinite { .. } => LinalgStatus::NotPositiveDefinite,
+            LinalgError::NotConverged { .. } => LinalgStatus::NotConverged,
+            LinalgError::RaggedInput { .. }
+            | LinalgError::BlockShape { .. }
+            | LinalgError::UnknownBlockSize { .. }
+            | LinalgError::InvalidPermutation { .. }
+            | LinalgError::NoStabilizingSolution
+            | LinalgError::Io { .. }
+            | LinalgError::InvalidFormat { .. }
+            | LinalgError::Parse { .. }
+            | LinalgError::ChecksumMismatch { .. }
+            | LinalgError::UnsupportedVersion { .. }
+            | LinalgError::TypeMismatch { .. }
+            | LinalgError::NotContiguous { .. } => LinalgStatus::Other,
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+fn fail(status: LinalgStatus, message: String) -> LinalgStatus {
+    // Display messages never contain NUL
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with_borrow_mut(|last| *last = message);
+    status
+}
+
+fn null(name: &str) -> LinalgStatus {
+    fail(LinalgStatus::NullPointer, format!("{name} is null"))
+}
+
+// Message for the last failure on the calling thread, empty if there was none. Valid until the
+// next failure on the same thread.
+#[no_mangle]
+pub extern "C" fn linalg_last_error() -> *const c_char {
+    LAST_ERROR.with_borrow(|last| last.as_ptr())
+}
+
+// Fixed description of a `LinalgStatus` value, never null. The status is taken as a plain int,
+// since C code can pass any value, and values that are no status get a generic message.
+#[no_mangle]
+pub extern "C" fn linalg_status_message(status: c_int) -> *const c_char {
+    const STATUSES: [LinalgStatus; 9] = [
+        LinalgStatus::Ok,
+        LinalgStatus::NullPointer,
+        LinalgStatus::TooLarge,
+        LinalgStatus::DimensionMismatch,
+        LinalgStatus::NotSquare,
+        LinalgStatus::Singular,
+        LinalgStatus::NotPositiveDefinite,
+        LinalgStatus::NotConverged,
+        LinalgStatus::Other,
+    ];
+    let message: &CStr = match STATUSES.into_iter().find(|&s| s as c_int == status) {
+        Some(LinalgStatus::Ok) => c"Success",
+        Some(LinalgStatus::NullPointer) => c"Null pointer argument",
+        Some(LinalgStatus::TooLarge) => c"Matrix is too large",
+        Some(LinalgStatus::DimensionMismatch) => c"Matrix dimensions do not match",
+        Some(LinalgStatus::NotSquare) => c"Matrix must be square",
+        Some(LinalgStatus::Singular) => c"Matrix is singular",
+        Some(LinalgStatus::NotPositiveDefinite) => c"Matrix is not positive definite",
+        Some(LinalgStatus::NotConverged) => c"Failed to converge",
+        Some(LinalgStatus::Other) => c"Error",
+        None => c"Unknown status",
+    };
+    message.as_ptr()
+}
+
+// Runs an entry point, turning a panic into `Other` with the panic message as the last error
+fn guarded(f: impl FnOnce() -> LinalgStatus) -> LinalgStatus {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        fail(LinalgStatus::Other, format!("Internal error: {message}"))
+    })
+}
+
+trait Handle: Sized {
+    type Elem: ComplexFloat<Real = Self::Elem> + Default + 'static;
+
+    fn new(m: Matrix<Self::Elem>) -> Self;
+    fn matrix(&self) -> &Matrix<Self::Elem>;
+}
+
+macro_rules! impl_handle {
+    ($handle:ident, $t:ty) => {
+        impl Handle for $handle {
+            type Elem = $t;
+
+            fn new(m: Matrix<$t>) -> Self {
+                $handle(m)
+            }
+
+            fn matrix(&self) -> &Matrix<$t> {
+                &self.0
+            }
+        }
+    };
+}
+
+impl_handle!(LinalgMatrixF64, f64);
+impl_handle!(LinalgMatrixF32, f32);
+
+unsafe fn finish<H: Handle>(
+    result: Result<Matrix<H::Elem>, LinalgError>,
+    out: *mut *mut H,
+) -> LinalgStatus {
+    match result {
+        Ok(m) => {
+            *out = Box::into_raw(Box::new(H::new(m)));
+            LinalgStatus::Ok
+        }
+        Err(err) => fail(LinalgStatus::from(&err), err.to_string()),
+    }
+}
+
+unsafe fn create<H: Handle>(
+    data: *const H::Elem,
+    rows: usize,
+    cols: usize,
+    out: *mut *mut H,
+) -> LinalgStatus {
+    if out.is_null() {
+        return null("out");
+    }
+    let Some(len) = rows.checked_mul(cols) else {
+        return fail(
+            LinalgStatus::TooLarge,
+            format!("A {rows}x{cols} matrix is too large"),
+        );
+    };
+    let data = match len {
+        0 => &[],
+        _ if data.is_null() => return null("data"),
+        // SAFETY: the caller provides `rows * cols` elements at `data`
+        _ => slice::from_raw_parts(data, len),
+    };
+    guarded(|| {
+        let m = Matrix::from_row_iter(rows, cols, data.iter().copied());
+        // SAFETY: `out` is non-null and the caller's to write
+        unsafe { finish(Ok(m), out) }
+    })
+}
+
+unsafe fn unary<H: Handle>(
+    a: *const H,
+    out: *mut *mut H,
+    op: fn(&Matrix<H::Elem>) -> Matrix<H::Elem>,
+) -> LinalgStatus {
+    let Some(a) = a.as_ref() else {
+        return null("a");
+    };
+    if out.is_null() {
+        return null("out");
+    }
+    guarded(|| {
+        let m = op(a.matrix());
+        // SAFETY: `out` is non-null and the caller's to write
+        unsafe { finish(Ok(m), out) }
+    })
+}
+
+unsafe fn binary<H: Handle>(
+    a: *const H,
+    b: *const H,
+    out: *mut *mut H,
+    op: impl FnOnce(&Matrix<H::Elem>, &Matrix<H::Elem>) -> Result<Matrix<H::Elem>, LinalgError>,
+) -> LinalgStatus {
+    let Some(a) = a.as_ref() else {
+        return null("a");
+    };
+    let Some(b) = b.as_ref() else {
+        return null("b");
+    };
+    if out.is_null() {
+        return null("out");
+    }
+    guarded(|| {
+        let result = op(a.matrix(), b.matrix());
+        // SAFETY: `out` is non-null and the caller's to write
+        unsafe { finish(result, out) }
+    })
+}
+
+unsafe fn solve<H: Handle>(
+    a: *const H,
+    b: *const H,
+    out: *mut *mut H,
+    rcond: *mut H::Elem,
+) -> LinalgStatus {
+    binary(a, b, out, |a, b| {
+        let solution = a.solve(b)?;
+        if !rcond.is_null() {
+            // SAFETY: a non-null `rcond` points to an element the caller owns
+            unsafe { *rcond = solution.rcond };
+        }
+        Ok(solution.x)
+    })
+}
+
+fn same_shape<T: ComplexFloat>(a: &Matrix<T>, b: &Matrix<T>) -> Result<(), LinalgError> {
+    if (a.rows, a.cols) == (b.rows, b.cols) {
+        Ok(())
+    } else {
+        Err(LinalgError::DimensionMismatch {
+            expected: (a.rows, a.cols),
+            found: (b.rows, b.cols),
+        })
+    }
+}
+
+fn chained<T: ComplexFloat>(a: &Matrix<T>, b: &Matrix<T>) -> Result<(), LinalgError> {
+    if a.cols == b.rows {
+        Ok(())
+    } else {
+        Err(LinalgError::DimensionMismatch {
+            expected: (a.cols, b.cols),
+            found: (b.rows, b.cols),
+        })
+    }
+}
+
+// Copies `rows * cols` row-major elements from `data`, which the caller keeps. `data` may be null
+// when the matrix is empty.
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f64_new(
+    data: *const f64,
+    rows: usize,
+    cols: usize,
+    out: *mut *mut LinalgMatrixF64,
+) -> LinalgStatus {
+    create(data, rows, cols, out)
+}
+
+// Null is ignored
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f64_free(m: *mut LinalgMatrixF64) {
+    if !m.is_null() {
+        drop(Box::from_raw(m));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f64_rows(m: *const LinalgMatrixF64) -> usize {
+    m.as_ref().map_or(0, |m| m.0.rows)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f64_cols(m: *const LinalgMatrixF64) -> usize {
+    m.as_ref().map_or(0, |m| m.0.cols)
+}
+
+// The row-major elements, valid until the handle is freed
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f64_data(m: *const LinalgMatrixF64) -> *const f64 {
+    m.as_ref().map_or(std::ptr::null(), |m| m.0.data.as_ptr())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f64_add(
+    a: *const LinalgMatrixF64,
+    b: *const LinalgMatrixF64,
+    out: *mut *mut LinalgMatrixF64,
+) -> LinalgStatus {
+    binary(a, b, out, |a, b| same_shape(a, b).map(|()| a + b))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f64_sub(
+    a: *const LinalgMatrixF64,
+    b: *const LinalgMatrixF64,
+    out: *mut *mut LinalgMatrixF64,
+) -> LinalgStatus {
+    binary(a, b, out, |a, b| same_shape(a, b).map(|()| a - b))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f64_mul(
+    a: *const LinalgMatrixF64,
+    b: *const LinalgMatrixF64,
+    out: *mut *mut LinalgMatrixF64,
+) -> LinalgStatus {
+    binary(a, b, out, |a, b| chained(a, b).map(|()| a * b))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f64_transpose(
+    a: *const LinalgMatrixF64,
+    out: *mut *mut LinalgMatrixF64,
+) -> LinalgStatus {
+    unary(a, out, Matrix::transpose)
+}
+
+// Solves a x = b for square a through LU. A non-null `rcond` receives the reciprocal condition
+// number estimate; x is unreliable when it is below machine epsilon.
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f64_solve(
+    a: *const LinalgMatrixF64,
+    b: *const LinalgMatrixF64,
+    out: *mut *mut LinalgMatrixF64,
+    rcond: *mut f64,
+) -> LinalgStatus {
+    solve(a, b, out, rcond)
+}
+
+// The same for f32
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f32_new(
+    data: *const f32,
+    rows: usize,
+    cols: usize,
+    out: *mut *mut LinalgMatrixF32,
+) -> LinalgStatus {
+    create(data, rows, cols, out)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f32_free(m: *mut LinalgMatrixF32) {
+    if !m.is_null() {
+        drop(Box::from_raw(m));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f32_rows(m: *const LinalgMatrixF32) -> usize {
+    m.as_ref().map_or(0, |m| m.0.rows)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f32_cols(m: *const LinalgMatrixF32) -> usize {
+    m.as_ref().map_or(0, |m| m.0.cols)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f32_data(m: *const LinalgMatrixF32) -> *const f32 {
+    m.as_ref().map_or(std::ptr::null(), |m| m.0.data.as_ptr())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f32_add(
+    a: *const LinalgMatrixF32,
+    b: *const LinalgMatrixF32,
+    out: *mut *mut LinalgMatrixF32,
+) -> LinalgStatus {
+    binary(a, b, out, |a, b| same_shape(a, b).map(|()| a + b))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f32_sub(
+    a: *const LinalgMatrixF32,
+    b: *const LinalgMatrixF32,
+    out: *mut *mut LinalgMatrixF32,
+) -> LinalgStatus {
+    binary(a, b, out, |a, b| same_shape(a, b).map(|()| a - b))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f32_mul(
+    a: *const LinalgMatrixF32,
+    b: *const LinalgMatrixF32,
+    out: *mut *mut LinalgMatrixF32,
+) -> LinalgStatus {
+    binary(a, b, out, |a, b| chained(a, b).map(|()| a * b))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f32_transpose(
+    a: *const LinalgMatrixF32,
+    out: *mut *mut LinalgMatrixF32,
+) -> LinalgStatus {
+    unary(a, out, Matrix::transpose)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn linalg_f32_solve(
+    a: *const LinalgMatrixF32,
+    b: *const LinalgMatrixF32,
+    out: *mut *mut LinalgMatrixF32,
+    rcond: *mut f32,
+) -> LinalgStatus {
+    solve(a, b, out, rcond)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    #[test]
+    fn test_round_trip_and_errors() {
+        unsafe {
+            let mut a = ptr::null_mut();
+            let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+            assert_eq!(
+                linalg_f64_new(data.as_ptr(), 2, 3, &mut a),
+                LinalgStatus::Ok
+            );
+            assert_eq!((linalg_f64_rows(a), linalg_f64_cols(a)), (2, 3));
+            assert_eq!(slice::from_raw_parts(linalg_f64_data(a), 6), data);
+
+            let mut out = ptr::null_mut();
+            assert_eq!(
+                linalg_f64_mul(a, a, &mut out),
+                LinalgStatus::DimensionMismatch
+            );
+            assert!(out.is_null());
+            assert_eq!(
+                CStr::from_ptr(linalg_last_error()).to_str().unwrap(),
+                "Expected a 3x3 matrix, got 2x3"
+            );
+            assert_eq!(
+                linalg_f64_solve(a, a, &mut out, ptr::null_mut()),
+                LinalgStatus::NotSquare
+            );
+            assert_eq!(
+                linalg_f64_add(a, ptr::null(), &mut out),
+                LinalgStatus::NullPointer
+            );
+            assert_eq!(
+                linalg_f64_new(ptr::null(), usize::MAX, 2, &mut out),
+                LinalgStatus::TooLarge
+            );
+            linalg_f64_free(a);
+            linalg_f64_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_status_from_error() {
+        assert_eq!(
+            LinalgStatus::from(&LinalgError::Singular { pivot: 1 }),
+            LinalgStatus::Singular
+        );
+        assert_eq!(
+            LinalgStatus::from(&LinalgError::NoStabilizingSolution),
+            LinalgStatus::Other
+        );
+        let message = |status| unsafe { CStr::from_ptr(linalg_status_message(status)) };
+        assert_eq!(
+            message(LinalgStatus::Singular as c_int).to_str().unwrap(),
+            "Matrix is singular"
+        );
+        assert_eq!(message(-1).to_str().unwrap(), "Unknown status");
+        assert_eq!(message(9).to_str().unwrap(), "Unknown status");
+    }
+
+    #[test]
+    fn test_panics_become_errors() {
+        let status = guarded(|| panic!("kernel invariant broken"));
+        assert_eq!(status, LinalgStatus::Other);
+        let message = unsafe { CStr::from_ptr(linalg_last_error()) };
+        assert_eq!(
+            message.to_str().unwrap(),
+            "Internal error: kernel invariant broken"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use crate::matrix::mat::Axis;
+use std::{fmt, io};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinalgError {
+    NotSquare {
+        rows: usize,
+        cols: usize,
+    },
+    DimensionMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    // Row or column `index` of the input has a different length than the first one
+    RaggedInput {
+        index: usize,
+        expected: usize,
+        found: usize,
+    },
+    // Block `block` of a block matrix does not match the height of its block row or the width of
+    // its block column
+    BlockShape {
+        block: (usize, usize),
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    // Every block in this block row (`Axis::Rows`) or block column (`Axis::Cols`) is zeros or
+    // identity, so nothing fixes its size
+    UnknownBlockSize {
+        axis: Axis,
+        index: usize,
+    },
+    // Entry `index` is out of range or repeats an earlier one
+    InvalidPermutation {
+        index: usize,
+    },
+    // An iterative algorithm gave up after this many iterations
+    NotConverged {
+        iterations: usize,
+    },
+    // The Hamiltonian or symplectic matrix of a Riccati equation does not split into equally
+    // sized stable and unstable parts
+    NoStabilizingSolution,
+    // An exactly zero pivot was met at this step of the elimination
+    Singular {
+        pivot: usize,
+    },
+    // The leading minor of this order is not positive
+    NotPositiveDefinite {
+        order: usize,
+    },
+    // Reading or writing a file or stream failed, `io::Error` is neither `Clone` nor `Eq` so only
+    // its kind and message are kept
+    Io {
+        kind: io::ErrorKind,
+        message: String,
+    },
+    // The input does not follow the file format being read
+    InvalidFormat {
+        message: String,
+    },
+    // Text input could not be parsed, `line` counts from 1
+    Parse {
+        line: usize,
+        message: String,
+    },
+    // The stored checksum does not match the one computed from the data, which was corrupted
+    ChecksumMismatch {
+        expected: u64,
+        found: u64,
+    },
+    // The file was written in a format version this library cannot read
+    UnsupportedVersion {
+        found: u32,
+        supported: u32,
+    },
+    // The file stores elements of type `found`, which cannot be read as `expected`
+    TypeMismatch {
+        expected: String,
+        found: String,
+    },
+    // Borrowed foreign data cannot be viewed without copying because its rows are not
+    // contiguous and back to back, `strides` are the (row, column) steps in elements
+    NotContiguous {
+        strides: (isize, isize),
+    },
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinalgError::NotSquare { rows, cols } => {
+                write!(f, "Matrix must be square, got {rows}x{cols}")
+            }
+            LinalgError::DimensionMismatch { expected, found } => write!(
+                f,
+                "Expected a {}x{} matrix, got {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            LinalgError::RaggedInput {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Input {index} has {found} elements, expected {expected} like the first one"
+            ),
+            LinalgError::BlockShape {
+                block,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Block ({}, {}) is {}x{}, expected {}x{}",
+                block.0, block.1, found.0, found.1, expected.0, expected.1
+            ),
+            LinalgError::UnknownBlockSize { axis, index } => {
+                let line = match axis {
+                    Axis::Rows => "row",
+                    Axis::Cols => "column",
+                };
+                write!(
+                    f,
+                    "Block {line} {index} has no matrix or view to take its size from"
+                )
+            }
+            LinalgError::InvalidPermutation { index } => write!(
+                f,
+                "Not a permutation, entry {index} is out of range or repeated"
+            ),
+            LinalgError::NotConverged { iterations } => {
+                write!(f, "Failed to converge after {iterations} iterations")
+            }
+            LinalgError::NoStabilizingSolution => {
+                write!(f, "Riccati equation has no stabilizing solution")
+            }
+            LinalgError::Singular { pivot } => {
+                write!(f, "Matrix is singular, zero pivot at step {pivot}")
+            }
+            LinalgError::NotPositiveDefinite { order } => write!(
+                f,
+                "Matrix is not positive definite, leading minor of order {order} is not positive"
+            ),
+            LinalgError::Io { message, .. } => write!(f, "I/O error: {message}"),
+            LinalgError::InvalidFormat { message } => write!(f, "Invalid file: {message}"),
+            LinalgError::Parse { line, message } => write!(f, "Line {line}: {message}"),
+            LinalgError::ChecksumMismatch { expected, found } => write!(
+                f,
+                "Checksum mismatch, stored {expected:#018x} but the data gives {found:#018x}"
+            ),
+            LinalgError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "Format version {found} is not supported, this library reads up to version {supported}"
+            ),
+            LinalgError::TypeMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Expected elements of type {expected}, the file has {found}"
+                )
+            }
+            LinalgError::NotContiguous { strides } => write!(
+                f,
+                "Data with strides ({}, {}) is not contiguous in row-major order",
+                strides.0, strides.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LinalgError {}
+
+impl From<io::Error> for LinalgError {
+    fn from(err: io::Error) -> Self {
+        LinalgError::Io {
+            kind: err.kind(),
+            message: err.to_string(),
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use super::{Component, Element, ElementKind, Notation};
+use crate::{error::LinalgError, matrix::mat::Matrix};
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+#[derive(Debug, Clone)]
+pub struct CsvReadOptions<T> {
+    pub delimiter: char,
+    // Lines skipped before any data, such as a header row
+    pub skip_lines: usize,
+    // Lines starting with this character are ignored
+    pub comment: Option<char>,
+    // Field values that mean "missing", besides an empty field
+    pub missing_markers: Vec<String>,
+    // Stands in for missing values, with `None` a missing value is an error
+    pub fill: Option<T>,
+}
+
+impl<T> Default for CsvReadOptions<T> {
+    fn default() -> Self {
+        CsvReadOptions {
+            delimiter: ',',
+            skip_lines: 0,
+            comment: None,
+            missing_markers: vec!["NA".to_string()],
+            fill: None,
+        }
+    }
+}
+
+impl<T> CsvReadOptions<T> {
+    pub fn tsv() -> Self {
+        CsvReadOptions {
+            delimiter: '\t',
+            ..Self::default()
+        }
+    }
+}
+
+// How complex numbers are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComplexStyle {
+    // 1+2i
+    Algebraic,
+    // (1,2)
+    Pair,
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvWriteOptions {
+    pub delimiter: char,
+    // Column names written as the first line, nothing is written when empty
+    pub header: Vec<String>,
+    pub notation: Notation,
+    pub complex_style: ComplexStyle,
+}
+
+impl Default for CsvWriteOptions {
+    fn default() -> Self {
+        CsvWriteOptions {
+            delimiter: ',',
+            header: Vec::new(),
+            notation: Notation::Shortest,
+            complex_style: ComplexStyle::Algebraic,
+        }
+    }
+}
+
+impl CsvWriteOptions {
+    pub fn tsv() -> Self {
+        CsvWriteOptions {
+            delimiter: '\t',
+            ..Self::default()
+        }
+    }
+}
+
+impl<T: Element> Matrix<T> {
+    // Reads one matrix row per line, streaming straight into the matrix storage. Fields may be
+    // double-quoted, and complex values may be written as `1+2i`, `1-2j` or `(1,2)`. Rows of
+    // different lengths are a `RaggedInput` error, unparsable fields a `Parse` error.
+    pub fn from_csv_reader<R: Read>(
+        reader: R,
+        options: &CsvReadOptions<T>,
+    ) -> Result<Self, LinalgError> {
+        let mut data = AVec::new(CACHELINE_ALIGN);
+        let mut rows = 0;
+        let mut cols = None;
+        let mut fields = Vec::new();
+
+        for (index, line) in BufReader::new(reader)
+            .lines()
+            .enumerate()
+            .skip(options.skip_lines)
+        {
+            let line = line?;
+            let number = index + 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || options.comment.is_some_and(|c| trimmed.starts_with(c)) {
+                continue;
+            }
+
+            // Split the whole line, trimming would drop empty first and last fields of a TSV
+            split_fields(&line, options.delimiter, &mut fields);
+            let expected = *cols.get_or_insert(fields.len());
+            if fields.len() != expected {
+                return Err(LinalgError::RaggedInput {
+                    index: rows,
+                    expected,
+                    found: fields.len(),
+                });
+            }
+
+            data.reserve(expected);
+            for (j, field) in fields.iter().enumerate() {
+                let value =
+                    if field.is_empty() || options.missing_markers.iter().any(|m| m == field) {
+                        options.fill.ok_or_else(|| LinalgError::Parse {
+                            line: number,
+                            message: format!("missing value in column {}", j + 1),
+                        })?
+                    } else {
+                        parse_value(field).ok_or_else(|| LinalgError::Parse {
+                            line: number,
+                            message: format!("invalid number '{field}'"),
+                        })?
+                    };
+                data.push(value);
+            }
+            rows += 1;
+        }
+
+        Ok(Matrix {
+            rows,
+            cols: cols.unwrap_or(0),
+            data,
+        })
+    }
+
+    pub fn to_csv_writer<W: Write>(
+        &self,
+        writer: W,
+        options: &CsvWriteOptions,
+    ) -> Result<(), LinalgError> {
+        let mut writer = BufWriter::new(writer);
+        let delimiter = options.delimiter.to_string();
+        if !options.header.is_empty() {
+            let names: Vec<String> = options
+                .header
+                .iter()
+                .map(|name| quoted(name.clone(), options.delimiter))
+                .collect();
+            writeln!(writer, "{}", names.join(&delimiter))?;
+        }
+
+        let mut line = String::new();
+        let mut field = String::new();
+        for row in self.data.chunks(self.cols.max(1)) {
+            line.clear();
+            for (j, &x) in row.iter().enumerate() {
+                if j > 0 {
+                    line.push(options.delimiter);
+                }
+                field.clear();
+                write_value(&mut field, x, options);
+                line.push_str(&quoted(std::mem::take(&mut field), options.delimiter));
+            }
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+// Splits on the delimiter except inside double quotes or parentheses, so `(1,2)` stays whole.
+// Surrounding whitespace and quotes are removed.
+fn split_fields(line: &str, delimiter: char, fields: &mut Vec<String>) {
+    fields.clear();
+    let mut field = String::new();
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            '(' if !in_quotes => {
+                depth += 1;
+                field.push(c);
+            }
+            ')' if !in_quotes => {
+                depth = depth.saturating_sub(1);
+                field.push(c);
+            }
+            c if c == delimiter && !in_quotes && depth == 0 => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+}
+
+fn parse_value<T: Element>(text: &str) -> Option<T> {
+    let zero = T::Component::default();
+    if T::KIND != ElementKind::Complex {
+        return T::from_parts(text.parse().ok()?, zero);
+    }
+    let (re, im) = parse_complex::<T::Component>(text)?;
+    T::from_parts(re, im)
+}
+
+// `(re,im)`, or `re+imi` where any part may be left out and `j` may stand in for `i`
+fn parse_complex<C: Component>(text: &str) -> Option<(C, C)> {
+    if let Some(pair) = text.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        let (re, im) = pair.split_once(',')?;
+        return Some((re.trim().parse().ok()?, im.trim().parse().ok()?));
+    }
+
+    let zero = C::default();
+    let text: String = text.split_whitespace().collect();
+    let Some(body) = text.strip_suffix(['i', 'j']) else {
+        return Some((text.parse().ok()?, zero));
+    };
+    // The imaginary part starts at the last sign that is not the leading one or an exponent's
+    let split = body
+        .char_indices()
+        .rev()
+        .find(|&(k, c)| (c == '+' || c == '-') && k > 0 && !body[..k].ends_with(['e', 'E']))
+        .map_or(0, |(k, _)| k);
+    let (re, im) = body.split_at(split);
+    let re = if re.is_empty() {
+        zero
+    } else {
+        re.parse().ok()?
+    };
+    let im = match im {
+        "" | "+" => C::one(),
+        "-" => "-1".parse().ok()?,
+        im => im.parse().ok()?,
+    };
+    Some((re, im))
+}
+
+fn write_value<T: Element>(out: &mut String, x: T, options: &CsvWriteOptions) {
+    if T::KIND != ElementKind::Complex {
+        x.re().write_text(options.notation, out);
+        return;
+    }
+    match options.complex_style {
+        ComplexStyle::Algebraic => {
+            x.re().write_text(options.notation, out);
+            let start = out.len();
+            x.im().write_text(options.notation, out);
+            if !out[start..].starts_with('-') {
+                out.insert(start, '+');
+            }
+            out.push('i');
+        }
+        ComplexStyle::Pair => {
+            out.push('(');
+            x.re().write_text(options.notation, out);
+            out.push(',');
+            x.im().write_text(options.notation, out);
+            out.push(')');
+        }
+    }
+}
+
+// Quotes a field that contains the delimiter or a double quote
+fn quoted(field: String, delimiter: char) -> String {
+    if field.contains(['"', delimiter]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+
+    fn read<T: Element>(text: &str, options: &CsvReadOptions<T>) -> Result<Matrix<T>, LinalgError> {
+        Matrix::from_csv_reader(text.as_bytes(), options)
+    }
+
+    #[test]
+    fn test_read_with_options() {
+        let text = "x,y,z\n# calibration run\n1, 2, 3\n\n4,NA,6\n";
+        let options = CsvReadOptions {
+            skip_lines: 1,
+            comment: Some('#'),
+            fill: Some(f64::NAN),
+            ..CsvReadOptions::default()
+        };
+        let m = read(text, &options).unwrap();
+        assert_eq!((m.rows(), m.cols()), (2, 3));
+        assert_eq!(m[(1, 2)], 6.0);
+        assert!(m[(1, 1)].is_nan());
+
+        let strict = CsvReadOptions::<f64> {
+            skip_lines: 1,
+            comment: Some('#'),
+            ..CsvReadOptions::default()
+        };
+        assert_eq!(
+            read(text, &strict).unwrap_err(),
+            LinalgError::Parse {
+                line: 5,
+                message: "missing value in column 2".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_tsv_missing_values() {
+        let options = CsvReadOptions {
+            fill: Some(f64::NAN),
+            ..CsvReadOptions::tsv()
+        };
+        let m = read("1\t2\n\t3\n4\t\n", &options).unwrap();
+        assert_eq!((m.rows(), m.cols()), (3, 2));
+        assert!(m[(1, 0)].is_nan() && m[(2, 1)].is_nan());
+        assert_eq!((m[(1, 1)], m[(2, 0)]), (3.0, 4.0));
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        assert_eq!(
+            read::<i32>("1\t2\n3\n", &CsvReadOptions::tsv()).unwrap_err(),
+            LinalgError::RaggedInput {
+                index: 1,
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            read::<i32>("1,2\n3,x\n", &CsvReadOptions::default()).unwrap_err(),
+            LinalgError::Parse {
+                line: 2,
+                message: "invalid number 'x'".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_complex_styles() {
+        let text = "1+2i,(3,-4),-5.5j\n\"(0.5, 1e-3)\",7,-i\n1e-3-2E+2i,i,2 - 3i\n";
+        let m = read::<c64>(text, &CsvReadOptions::default()).unwrap();
+        assert_eq!(
+            m,
+            Matrix::new([
+                [c64::new(1.0, 2.0), c64::new(3.0, -4.0), c64::new(0.0, -5.5)],
+                [c64::new(0.5, 1e-3), c64::new(7.0, 0.0), c64::new(0.0, -1.0)],
+                [
+                    c64::new(1e-3, -200.0),
+                    c64::new(0.0, 1.0),
+                    c64::new(2.0, -3.0)
+                ],
+            ])
+        );
+        assert!(read::<f64>("1+2i\n", &CsvReadOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_write_and_read_back() {
+        let m = Matrix::new([[c64::new(1.5, -2.0), c64::new(0.0, 1.0)]]);
+        let mut out = Vec::new();
+        m.to_csv_writer(&mut out, &CsvWriteOptions::default())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out.clone()).unwrap(),
+            "1.5-2.0i,0.0+1.0i\n"
+        );
+        assert_eq!(
+            read(
+                std::str::from_utf8(&out).unwrap(),
+                &CsvReadOptions::default()
+            )
+            .unwrap(),
+            m
+        );
+
+        let options = CsvWriteOptions {
+            header: vec!["a".to_string(), "b".to_string()],
+            complex_style: ComplexStyle::Pair,
+            notation: Notation::Fixed(2),
+            ..CsvWriteOptions::default()
+        };
+        let mut out = Vec::new();
+        m.to_csv_writer(&mut out, &options).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a,b\n\"(1.50,-2.00)\",\"(0.00,1.00)\"\n"
+        );
+
+        let r = Matrix::new([[1.0, 2.5e-10], [1e20, -3.0]]);
+        let mut out = Vec::new();
+        r.to_csv_writer(&mut out, &CsvWriteOptions::tsv()).unwrap();
+        assert_eq!(
+            String::from_utf8(out.clone()).unwrap(),
+            "1.0\t2.5e-10\n1e20\t-3.0\n"
+        );
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(read(&text, &CsvReadOptions::tsv()).unwrap(), r);
+    }
+}
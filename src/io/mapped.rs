@@ -0,0 +1,298 @@
+use super::{
+    cast_slice, cast_slice_mut, invalid,
+    native::{self, Checksum, StorageOrder},
+    Element,
+};
+use crate::{
+    error::LinalgError,
+    matrix::mat_view::{MatrixView, MatrixViewMut},
+};
+use memmap2::{Mmap, MmapMut};
+use std::{fs::File, marker::PhantomData, path::Path};
+
+enum Map {
+    ReadOnly(Mmap),
+    Writable(MmapMut),
+}
+
+impl Map {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Map::ReadOnly(map) => map,
+            Map::Writable(map) => map,
+        }
+    }
+}
+
+// A matrix kept in a file and mapped into memory, for data larger than RAM. Pages are read when
+// touched and written back by the OS, so only the parts in use take memory. The file holds
+// row-major little-endian elements, either raw or after a native-format header; pair it with
+// `out_of_core` to multiply or transpose without loading it.
+pub struct MappedMatrix<T: Element> {
+    map: Map,
+    offset: usize,
+    rows: usize,
+    cols: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T: Element> MappedMatrix<T> {
+    // A file of exactly `rows * cols` elements and nothing else
+    pub fn open_raw<P: AsRef<Path>>(
+        path: P,
+        rows: usize,
+        cols: usize,
+    ) -> Result<Self, LinalgError> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only. As with any file mapping, the file must not be truncated
+        // or modified by another process while it is mapped.
+        let map = Map::ReadOnly(unsafe { Mmap::map(&file)? });
+        Self::raw(map, rows, cols)
+    }
+
+    // As `open_raw`, writes through `view_mut` go to the file
+    pub fn open_raw_mut<P: AsRef<Path>>(
+        path: P,
+        rows: usize,
+        cols: usize,
+    ) -> Result<Self, LinalgError> {
+        let file = File::options().read(true).write(true).open(path)?;
+        // SAFETY: as above, the file must not be truncated or modified elsewhere while mapped
+        let map = Map::Writable(unsafe { MmapMut::map_mut(&file)? });
+        Self::raw(map, rows, cols)
+    }
+
+    // Creates, or truncates, a zero-filled raw file
+    pub fn create_raw<P: AsRef<Path>>(
+        path: P,
+        rows: usize,
+        cols: usize,
+    ) -> Result<Self, LinalgError> {
+        let file = create(path, payload_size::<T>(rows, cols, 0)?)?;
+        // SAFETY: as above
+        let map = Map::Writable(unsafe { MmapMut::map_mut(&file)? });
+        Self::raw(map, rows, cols)
+    }
+
+    // A native-format file. The checksum, if any, is not verified, as that would read the whole
+    // file; use `Matrix::read_native` or `NativeView` for that.
+    pub fn open_native<P: AsRef<Path>>(path: P) -> Result<Self, LinalgError> {
+        let file = File::open(path)?;
+        // SAFETY: as above
+        let map = Map::ReadOnly(unsafe { Mmap::map(&file)? });
+        Self::native(map)
+    }
+
+    // As `open_native`, for files without a checksum, which writes would invalidate
+    pub fn open_native_mut<P: AsRef<Path>>(path: P) -> Result<Self, LinalgError> {
+        let file = File::options().read(true).write(true).open(path)?;
+        // SAFETY: as above
+        let map = Map::Writable(unsafe { MmapMut::map_mut(&file)? });
+        Self::native(map)
+    }
+
+    // Creates, or truncates, a zero-filled native-format file without a checksum
+    pub fn create_native<P: AsRef<Path>>(
+        path: P,
+        rows: usize,
+        cols: usize,
+    ) -> Result<Self, LinalgError> {
+        let file = create(path, payload_size::<T>(rows, cols, native::HEADER_SIZE)?)?;
+        // SAFETY: as above
+        let mut map = unsafe { MmapMut::map_mut(&file)? };
+        map[..native::HEADER_SIZE].copy_from_slice(&native::mappable_header::<T>(rows, cols));
+        Self::native(Map::Writable(map))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn view(&self) -> MatrixView<'_, T> {
+        let bytes = self.map.bytes();
+        let len = self.rows * self.cols * T::SIZE;
+        // Checked when the file was opened
+        let data = cast_slice(&bytes[self.offset..self.offset + len]).unwrap();
+        MatrixView::new(self.rows, self.cols, data)
+    }
+
+    // `None` when the file was opened read-only
+    pub fn view_mut(&mut self) -> Option<MatrixViewMut<'_, T>> {
+        let Map::Writable(map) = &mut self.map else {
+            return None;
+        };
+        let len = self.rows * self.cols * T::SIZE;
+        let data = cast_slice_mut(&mut map[self.offset..self.offset + len]).unwrap();
+        Some(MatrixViewMut::new(self.rows, self.cols, data))
+    }
+
+    // Writes changed pages back to the file and waits for the writes to finish
+    pub fn flush(&self) -> Result<(), LinalgError> {
+        if let Map::Writable(map) = &self.map {
+            map.flush()?;
+        }
+        Ok(())
+    }
+
+    fn raw(map: Map, rows: usize, cols: usize) -> Result<Self, LinalgError> {
+        let size = payload_size::<T>(rows, cols, 0)?;
+        let len = map.bytes().len();
+        if len != size {
+            return Err(invalid(format!(
+                "file holds {len} bytes, a {rows}x{cols} matrix needs {size}"
+            )));
+        }
+        Self::new(map, 0, rows, cols)
+    }
+
+    fn native(map: Map) -> Result<Self, LinalgError> {
+        let bytes = map.bytes();
+        let (rows, cols, order, checksum) = native::parse_header::<T>(bytes)?;
+        if order != StorageOrder::RowMajor {
+            return Err(invalid(
+                "column-major files cannot be mapped as a row-major view",
+            ));
+        }
+        if checksum != Checksum::None && matches!(map, Map::Writable(_)) {
+            return Err(invalid(
+                "file has a checksum, which writes through the mapping would invalidate",
+            ));
+        }
+        if bytes.len() < payload_size::<T>(rows, cols, native::HEADER_SIZE)? {
+            return Err(invalid("truncated payload"));
+        }
+        Self::new(map, native::HEADER_SIZE, rows, cols)
+    }
+
+    fn new(map: Map, offset: usize, rows: usize, cols: usize) -> Result<Self, LinalgError> {
+        let mapped = MappedMatrix {
+            map,
+            offset,
+            rows,
+            cols,
+            marker: PhantomData,
+        };
+        let len = rows * cols * T::SIZE;
+        let bytes = mapped.map.bytes();
+        // Maps are page aligned and both offsets are multiples of every element size, so this
+        // only fails on big-endian platforms
+        if cast_slice::<T>(&bytes[offset..offset + len]).is_none() {
+            return Err(invalid("mapping requires a little-endian platform"));
+        }
+        Ok(mapped)
+    }
+}
+
+fn payload_size<T: Element>(rows: usize, cols: usize, offset: usize) -> Result<usize, LinalgError> {
+    rows.checked_mul(cols)
+        .and_then(|n| n.checked_mul(T::SIZE))
+        .and_then(|n| n.checked_add(offset))
+        .ok_or_else(|| invalid("matrix is too large for this platform"))
+}
+
+fn create<P: AsRef<Path>>(path: P, size: usize) -> Result<File, LinalgError> {
+    let file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    file.set_len(size as u64)?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::{mat::Matrix, out_of_core};
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("linalg-{}-mapped-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn test_native_round_trip() {
+        let m = Matrix::from_fn(6, 5, |i, j| (i * 5 + j) as f64 - 3.5);
+        let path = temp_path("native");
+        {
+            let mut mapped = MappedMatrix::<f64>::create_native(&path, 6, 5).unwrap();
+            mapped.view_mut().unwrap().copy_from(&m.view(0..6, 0..5));
+            mapped.flush().unwrap();
+        }
+        assert_eq!(
+            Matrix::<f64>::read_native(File::open(&path).unwrap()).unwrap(),
+            m
+        );
+
+        let mut mapped = MappedMatrix::<f64>::open_native(&path).unwrap();
+        assert_eq!((mapped.rows(), mapped.cols()), (6, 5));
+        assert_eq!(mapped.view(), m.view(0..6, 0..5));
+        assert!(mapped.view_mut().is_none());
+        assert!(matches!(
+            MappedMatrix::<f32>::open_native(&path),
+            Err(LinalgError::TypeMismatch { .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        m.write_native(File::create(&path).unwrap(), Checksum::Crc32)
+            .unwrap();
+        assert!(MappedMatrix::<f64>::open_native(&path).is_ok());
+        assert!(MappedMatrix::<f64>::open_native_mut(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_raw_files() {
+        let path = temp_path("raw");
+        let data: Vec<u8> = (0..12i32).flat_map(|x| x.to_le_bytes()).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        assert!(MappedMatrix::<i32>::open_raw(&path, 4, 4).is_err());
+        let mut mapped = MappedMatrix::<i32>::open_raw_mut(&path, 3, 4).unwrap();
+        assert_eq!(mapped.view()[(2, 1)], 9);
+        mapped.view_mut().unwrap()[(0, 0)] = -1;
+        mapped.flush().unwrap();
+        drop(mapped);
+        assert_eq!(&std::fs::read(&path).unwrap()[..4], (-1i32).to_le_bytes());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_out_of_core_on_mapped_files() {
+        let a = Matrix::from_fn(40, 24, |i, j| ((i * 7 + j * 3) % 11) as f32 - 5.0);
+        let b = Matrix::from_fn(24, 33, |i, j| ((i + 2 * j) % 5) as f32 * 0.5);
+        let paths = [temp_path("a"), temp_path("b"), temp_path("c")];
+
+        let mut mapped_a = MappedMatrix::<f32>::create_raw(&paths[0], 40, 24).unwrap();
+        mapped_a
+            .view_mut()
+            .unwrap()
+            .copy_from(&a.view(0..40, 0..24));
+        let mut mapped_b = MappedMatrix::<f32>::create_raw(&paths[1], 33, 24).unwrap();
+        out_of_core::transpose(
+            &b.view(0..24, 0..33),
+            &mut mapped_b.view_mut().unwrap(),
+            4096,
+        );
+        assert_eq!(mapped_b.view().to_matrix(), b.transpose());
+
+        let mut mapped_c = MappedMatrix::<f32>::create_native(&paths[2], 40, 33).unwrap();
+        out_of_core::multiply(
+            &mapped_a.view(),
+            &b.view(0..24, 0..33),
+            &mut mapped_c.view_mut().unwrap(),
+            4096,
+        );
+        assert_eq!(mapped_c.view().to_matrix(), &a * &b);
+
+        drop((mapped_a, mapped_b, mapped_c));
+        for path in paths {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,567 @@
+use super::{dense_zeros, Component, Element, ElementKind, Notation};
+use crate::{error::LinalgError, matrix::mat::Matrix};
+use aligned_vec::avec;
+use num_traits::One;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::str::SplitWhitespace;
+
+const MAX_RESERVED_ENTRIES: usize = 1 << 20; // Header counts are not trusted for preallocation
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketLayout {
+    // Every entry, column by column
+    Array,
+    // Only the nonzero entries, one (row, column, value) triplet per line
+    Coordinate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Real,
+    Complex,
+    Integer,
+    Pattern,
+}
+
+impl Field {
+    fn name(self) -> &'static str {
+        match self {
+            Field::Real => "real",
+            Field::Complex => "complex",
+            Field::Integer => "integer",
+            Field::Pattern => "pattern",
+        }
+    }
+
+    // The field a matrix of `T` is written with
+    fn of<T: Element>() -> Self {
+        match T::KIND {
+            ElementKind::Float => Field::Real,
+            ElementKind::Complex => Field::Complex,
+            ElementKind::Int | ElementKind::UInt => Field::Integer,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symmetry {
+    General,
+    Symmetric,
+    SkewSymmetric,
+    Hermitian,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    layout: MarketLayout,
+    field: Field,
+    symmetry: Symmetry,
+    rows: usize,
+    cols: usize,
+    // Number of stored entries of a coordinate file
+    entries: usize,
+}
+
+// The entries of a Matrix Market file with the implied half of a symmetric matrix filled in, the
+// form a sparse matrix is built from. Indices count from 0 and repeated entries are kept as they
+// appear, to be summed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triplets<T> {
+    pub rows: usize,
+    pub cols: usize,
+    pub entries: Vec<(usize, usize, T)>,
+}
+
+impl<T: Element> Matrix<T> {
+    // Reads either layout into a dense matrix, summing repeated coordinate entries. Real,
+    // integer and pattern fields can be read as any float or complex type, integer and pattern
+    // fields also as integer types.
+    pub fn read_market<R: Read>(reader: R) -> Result<Self, LinalgError> {
+        let mut parser = Parser::new(reader);
+        let header = parser.header::<T>()?;
+        if header.layout == MarketLayout::Array {
+            return parser.array(header);
+        }
+
+        let triplets = parser.coordinate(header)?;
+        let mut data = dense_zeros(header.rows, header.cols)?;
+        for (i, j, value) in triplets.entries {
+            let entry = &mut data[i * header.cols + j];
+            *entry = *entry + value;
+        }
+        Ok(Matrix {
+            rows: header.rows,
+            cols: header.cols,
+            data,
+        })
+    }
+
+    // Writes a general (non-symmetric) file, `Coordinate` keeps only the nonzero entries
+    pub fn write_market<W: Write>(
+        &self,
+        writer: W,
+        layout: MarketLayout,
+    ) -> Result<(), LinalgError> {
+        let mut writer = BufWriter::new(writer);
+        let layout_name = match layout {
+            MarketLayout::Array => "array",
+            MarketLayout::Coordinate => "coordinate",
+        };
+        writeln!(
+            writer,
+            "%%MatrixMarket matrix {layout_name} {} general",
+            Field::of::<T>().name()
+        )?;
+
+        let mut line = String::new();
+        match layout {
+            MarketLayout::Array => {
+                writeln!(writer, "{} {}", self.rows, self.cols)?;
+                for j in 0..self.cols {
+                    for i in 0..self.rows {
+                        line.clear();
+                        write_value(&mut line, self.data[i * self.cols + j]);
+                        line.push('\n');
+                        writer.write_all(line.as_bytes())?;
+                    }
+                }
+            }
+            MarketLayout::Coordinate => {
+                let zero = T::default();
+                let nonzeros = self.data.iter().filter(|&&x| x != zero).count();
+                writeln!(writer, "{} {} {nonzeros}", self.rows, self.cols)?;
+                for (k, &x) in self.data.iter().enumerate() {
+                    if x == zero {
+                        continue;
+                    }
+                    line.clear();
+                    write!(line, "{} {} ", k / self.cols + 1, k % self.cols + 1).unwrap();
+                    write_value(&mut line, x);
+                    line.push('\n');
+                    writer.write_all(line.as_bytes())?;
+                }
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+// Reads the entries of a file without building a dense matrix. An array file yields its
+// nonzero entries.
+pub fn read_triplets<T: Element, R: Read>(reader: R) -> Result<Triplets<T>, LinalgError> {
+    let mut parser = Parser::new(reader);
+    let header = parser.header::<T>()?;
+    if header.layout == MarketLayout::Coordinate {
+        return parser.coordinate(header);
+    }
+
+    let m = parser.array::<T>(header)?;
+    let zero = T::default();
+    let entries = m
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(_, &x)| x != zero)
+        .map(|(k, &x)| (k / m.cols, k % m.cols, x))
+        .collect();
+    Ok(Triplets {
+        rows: m.rows,
+        cols: m.cols,
+        entries,
+    })
+}
+
+fn write_value<T: Element>(out: &mut String, x: T) {
+    x.re().write_text(Notation::Shortest, out);
+    if T::KIND == ElementKind::Complex {
+        out.push(' ');
+        x.im().write_text(Notation::Shortest, out);
+    }
+}
+
+struct Parser<R> {
+    lines: std::io::Lines<BufReader<R>>,
+    // Number of the line read last
+    line: usize,
+}
+
+impl<R: Read> Parser<R> {
+    fn new(reader: R) -> Self {
+        Parser {
+            lines: BufReader::new(reader).lines(),
+            line: 0,
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> LinalgError {
+        LinalgError::Parse {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn next_line(&mut self) -> Result<Option<String>, LinalgError> {
+        let line = self.lines.next().transpose()?;
+        if line.is_some() {
+            self.line += 1;
+        }
+        Ok(line)
+    }
+
+    // The next line that is neither blank nor a % comment
+    fn next_data(&mut self) -> Result<Option<String>, LinalgError> {
+        while let Some(line) = self.next_line()? {
+            let trimmed = line.trim_start();
+            if !trimmed.is_empty() && !trimmed.starts_with('%') {
+                return Ok(Some(line));
+            }
+        }
+        Ok(None)
+    }
+
+    fn expect_data(&mut self) -> Result<String, LinalgError> {
+        self.next_data()?
+            .ok_or_else(|| self.error("file ended before all entries were read"))
+    }
+
+    fn header<T: Element>(&mut self) -> Result<Header, LinalgError> {
+        let banner = self.next_line()?.unwrap_or_default().to_ascii_lowercase();
+        let words: Vec<&str> = banner.split_whitespace().collect();
+        let ["%%matrixmarket", "matrix", layout, field, symmetry] = words[..] else {
+            return Err(self.error("expected '%%MatrixMarket matrix <layout> <field> <symmetry>'"));
+        };
+
+        let layout = match layout {
+            "array" => MarketLayout::Array,
+            "coordinate" => MarketLayout::Coordinate,
+            _ => return Err(self.error(format!("unknown layout '{layout}'"))),
+        };
+        let field = match field {
+            "real" | "double" => Field::Real,
+            "complex" => Field::Complex,
+            "integer" => Field::Integer,
+            "pattern" => Field::Pattern,
+            _ => return Err(self.error(format!("unknown field '{field}'"))),
+        };
+        let symmetry = match symmetry {
+            "general" => Symmetry::General,
+            "symmetric" => Symmetry::Symmetric,
+            "skew-symmetric" => Symmetry::SkewSymmetric,
+            "hermitian" => Symmetry::Hermitian,
+            _ => return Err(self.error(format!("unknown symmetry '{symmetry}'"))),
+        };
+        if layout == MarketLayout::Array && field == Field::Pattern {
+            return Err(self.error("the pattern field needs the coordinate layout"));
+        }
+        if symmetry == Symmetry::Hermitian && field != Field::Complex {
+            return Err(self.error("hermitian symmetry needs the complex field"));
+        }
+
+        // Complex values only fit complex types, and integer types take only whole numbers
+        let fits = match (T::KIND, field) {
+            (ElementKind::Complex, _) => true,
+            (_, Field::Complex) => false,
+            (ElementKind::Int | ElementKind::UInt, Field::Real) => false,
+            _ => true,
+        };
+        if !fits {
+            return Err(LinalgError::TypeMismatch {
+                expected: Field::of::<T>().name().to_string(),
+                found: field.name().to_string(),
+            });
+        }
+
+        let line = self
+            .next_data()?
+            .ok_or_else(|| self.error("missing size line"))?;
+        let sizes = line
+            .split_whitespace()
+            .map(|s| s.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| self.error(format!("invalid size line '{}'", line.trim())))?;
+        let (rows, cols, entries) = match (layout, &sizes[..]) {
+            (MarketLayout::Array, &[rows, cols]) => (rows, cols, 0),
+            (MarketLayout::Coordinate, &[rows, cols, entries]) => (rows, cols, entries),
+            (MarketLayout::Array, _) => return Err(self.error("expected 'rows cols'")),
+            (MarketLayout::Coordinate, _) => return Err(self.error("expected 'rows cols entries'")),
+        };
+        if symmetry != Symmetry::General && rows != cols {
+            return Err(self.error(format!("a {rows}x{cols} matrix cannot be symmetric")));
+        }
+        if rows.checked_mul(cols).is_none() {
+            return Err(self.error("matrix is too large"));
+        }
+
+        Ok(Header {
+            layout,
+            field,
+            symmetry,
+            rows,
+            cols,
+            entries,
+        })
+    }
+
+    fn component<C: Component>(&self, tokens: &mut SplitWhitespace) -> Result<C, LinalgError> {
+        let token = tokens.next().ok_or_else(|| self.error("missing value"))?;
+        token
+            .parse()
+            .map_err(|_| self.error(format!("invalid number '{token}'")))
+    }
+
+    fn value<T: Element>(
+        &self,
+        tokens: &mut SplitWhitespace,
+        field: Field,
+    ) -> Result<T, LinalgError> {
+        let zero = T::Component::default();
+        let (re, im) = match field {
+            Field::Pattern => (T::Component::one(), zero),
+            Field::Complex => (self.component(tokens)?, self.component(tokens)?),
+            Field::Real | Field::Integer => (self.component(tokens)?, zero),
+        };
+        if tokens.next().is_some() {
+            return Err(self.error("unexpected text after the entry"));
+        }
+        T::from_parts(re, im).ok_or_else(|| self.error("complex value in a real matrix"))
+    }
+
+    fn index(&self, tokens: &mut SplitWhitespace, bound: usize) -> Result<usize, LinalgError> {
+        let token = tokens.next().ok_or_else(|| self.error("missing index"))?;
+        let index: usize = token
+            .parse()
+            .map_err(|_| self.error(format!("invalid index '{token}'")))?;
+        if index == 0 || index > bound {
+            return Err(self.error(format!("index {index} is out of range 1..={bound}")));
+        }
+        Ok(index - 1)
+    }
+
+    // The entry at the transposed position of a symmetric file
+    fn mirrored<T: Element>(&self, symmetry: Symmetry, value: T) -> Result<T, LinalgError> {
+        match symmetry {
+            Symmetry::General | Symmetry::Symmetric => Ok(value),
+            Symmetry::Hermitian => Ok(value.conj()),
+            Symmetry::SkewSymmetric => value
+                .checked_neg()
+                .ok_or_else(|| self.error("skew-symmetric entry cannot be negated")),
+        }
+    }
+
+    fn end_of_file(&mut self) -> Result<(), LinalgError> {
+        match self.next_data()? {
+            Some(_) => Err(self.error("more entries than the size line declares")),
+            None => Ok(()),
+        }
+    }
+
+    // Column by column, symmetric files store only the lower triangle and skew-symmetric ones
+    // only the part below the diagonal
+    fn array<T: Element>(&mut self, header: Header) -> Result<Matrix<T>, LinalgError> {
+        let Header {
+            rows,
+            cols,
+            symmetry,
+            ..
+        } = header;
+        // Values are collected as they are read, so the dense matrix is only allocated once the
+        // file has proven to hold about as many entries as it declares
+        let mut values = Vec::with_capacity((rows * cols).min(MAX_RESERVED_ENTRIES));
+        for j in 0..cols {
+            for i in first_row(symmetry, j)..rows {
+                let line = self.expect_data()?;
+                let value = self.value(&mut line.split_whitespace(), header.field)?;
+                values.push(value);
+                if i != j && symmetry != Symmetry::General {
+                    values.push(self.mirrored(symmetry, value)?);
+                }
+            }
+        }
+        self.end_of_file()?;
+
+        let mut data = avec![T::default(); rows * cols];
+        let mut values = values.into_iter();
+        for j in 0..cols {
+            for i in first_row(symmetry, j)..rows {
+                data[i * cols + j] = values.next().unwrap();
+                if i != j && symmetry != Symmetry::General {
+                    data[j * cols + i] = values.next().unwrap();
+                }
+            }
+        }
+        Ok(Matrix { rows, cols, data })
+    }
+
+    fn coordinate<T: Element>(&mut self, header: Header) -> Result<Triplets<T>, LinalgError> {
+        let mut entries = Vec::with_capacity(header.entries.min(MAX_RESERVED_ENTRIES));
+        for _ in 0..header.entries {
+            let line = self.expect_data()?;
+            let mut tokens = line.split_whitespace();
+            let i = self.index(&mut tokens, header.rows)?;
+            let j = self.index(&mut tokens, header.cols)?;
+            let value = self.value(&mut tokens, header.field)?;
+            entries.push((i, j, value));
+            if i != j && header.symmetry != Symmetry::General {
+                entries.push((j, i, self.mirrored(header.symmetry, value)?));
+            }
+        }
+        self.end_of_file()?;
+        Ok(Triplets {
+            rows: header.rows,
+            cols: header.cols,
+            entries,
+        })
+    }
+}
+
+// First stored row of column `j` in an array file
+fn first_row(symmetry: Symmetry, j: usize) -> usize {
+    match symmetry {
+        Symmetry::General => 0,
+        Symmetry::SkewSymmetric => j + 1,
+        Symmetry::Symmetric | Symmetry::Hermitian => j,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+
+    fn parse_error(text: &str) -> (usize, String) {
+        match Matrix::<f64>::read_market(text.as_bytes()).unwrap_err() {
+            LinalgError::Parse { line, message } => (line, message),
+            err => panic!("unexpected error {err:?}"),
+        }
+    }
+
+    #[test]
+    fn test_array_round_trip() {
+        let m = Matrix::new([[1.0, 2.5], [-3.0, 1e-300]]);
+        let mut out = Vec::new();
+        m.write_market(&mut out, MarketLayout::Array).unwrap();
+        assert_eq!(
+            String::from_utf8(out.clone()).unwrap(),
+            "%%MatrixMarket matrix array real general\n2 2\n1.0\n-3.0\n2.5\n1e-300\n"
+        );
+        assert_eq!(Matrix::<f64>::read_market(&out[..]).unwrap(), m);
+    }
+
+    #[test]
+    fn test_coordinate_round_trip() {
+        let m = Matrix::new([
+            [c64::new(0.0, 0.0), c64::new(1.0, -1.0)],
+            [c64::new(2.0, 0.0), c64::new(0.0, 0.0)],
+        ]);
+        let mut out = Vec::new();
+        m.write_market(&mut out, MarketLayout::Coordinate).unwrap();
+        assert_eq!(
+            String::from_utf8(out.clone()).unwrap(),
+            "%%MatrixMarket matrix coordinate complex general\n2 2 2\n1 2 1.0 -1.0\n2 1 2.0 0.0\n"
+        );
+        assert_eq!(Matrix::<c64>::read_market(&out[..]).unwrap(), m);
+
+        let u = Matrix::new([[0u32, 7], [0, 0]]);
+        let mut out = Vec::new();
+        u.write_market(&mut out, MarketLayout::Coordinate).unwrap();
+        assert!(out.starts_with(b"%%MatrixMarket matrix coordinate integer general\n"));
+        assert_eq!(Matrix::<u32>::read_market(&out[..]).unwrap(), u);
+    }
+
+    #[test]
+    fn test_symmetric_coordinate_with_comments() {
+        let text = "%%MatrixMarket matrix coordinate real symmetric\n\
+                    % a comment\n\
+                    \n\
+                    3 3 4\n\
+                    1 1 4.0\n\
+                    2 1 -1.0\n\
+                    3 2 -1.0\n\
+                    3 2 -0.5\n";
+        assert_eq!(
+            Matrix::<f64>::read_market(text.as_bytes()).unwrap(),
+            Matrix::new([[4.0, -1.0, 0.0], [-1.0, 0.0, -1.5], [0.0, -1.5, 0.0]])
+        );
+
+        let triplets = read_triplets::<f64, _>(text.as_bytes()).unwrap();
+        assert_eq!(triplets.entries.len(), 7);
+        assert_eq!(triplets.entries[1..3], [(1, 0, -1.0), (0, 1, -1.0)]);
+    }
+
+    #[test]
+    fn test_hermitian_skew_and_pattern() {
+        let text = "%%MatrixMarket matrix coordinate complex hermitian\n2 2 2\n1 1 2 0\n2 1 1 3\n";
+        assert_eq!(
+            Matrix::<c64>::read_market(text.as_bytes()).unwrap(),
+            Matrix::new([
+                [c64::new(2.0, 0.0), c64::new(1.0, -3.0)],
+                [c64::new(1.0, 3.0), c64::new(0.0, 0.0)]
+            ])
+        );
+
+        // Strictly lower triangle, column by column
+        let text = "%%MatrixMarket matrix array integer skew-symmetric\n3 3\n1\n2\n3\n";
+        assert_eq!(
+            Matrix::<i32>::read_market(text.as_bytes()).unwrap(),
+            Matrix::new([[0, -1, -2], [1, 0, -3], [2, 3, 0]])
+        );
+        assert!(Matrix::<u32>::read_market(text.as_bytes()).is_err());
+
+        let text = "%%MatrixMarket matrix coordinate pattern general\n2 3 2\n1 3\n2 1\n";
+        assert_eq!(
+            Matrix::<f32>::read_market(text.as_bytes()).unwrap(),
+            Matrix::new([[0.0, 0.0, 1.0], [1.0, 0.0, 0.0]])
+        );
+    }
+
+    #[test]
+    fn test_errors_have_line_numbers() {
+        assert_eq!(parse_error("%%MatrixMarket tensor\n").0, 1);
+
+        let (line, message) = parse_error(
+            "%%MatrixMarket matrix coordinate real general\n% comment\n2 2 2\n1 1 1.0\n3 1 1.0\n",
+        );
+        assert_eq!(line, 5);
+        assert_eq!(message, "index 3 is out of range 1..=2");
+
+        let (line, message) =
+            parse_error("%%MatrixMarket matrix array real general\n2 1\n1.0\nabc\n");
+        assert_eq!((line, message.as_str()), (4, "invalid number 'abc'"));
+
+        let (line, _) = parse_error("%%MatrixMarket matrix array real general\n2 1\n1.0\n");
+        assert_eq!(line, 3);
+
+        let (line, _) = parse_error("%%MatrixMarket matrix array real general\n1 1\n1.0\n2.0\n");
+        assert_eq!(line, 4);
+
+        assert_eq!(
+            Matrix::<f64>::read_market(
+                "%%MatrixMarket matrix array complex general\n1 1\n1 2\n".as_bytes()
+            )
+            .unwrap_err(),
+            LinalgError::TypeMismatch {
+                expected: "real".to_string(),
+                found: "complex".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_huge_size_line_does_not_allocate() {
+        // An array file only allocates once its values are there
+        let (line, _) =
+            parse_error("%%MatrixMarket matrix array real general\n3000000000 3000000000\n1.0\n");
+        assert_eq!(line, 3);
+
+        let text = "%%MatrixMarket matrix coordinate real general\n3000000000 3000000000 0\n";
+        assert!(matches!(
+            Matrix::<f64>::read_market(text.as_bytes()),
+            Err(LinalgError::InvalidFormat { .. })
+        ));
+        // The sparse form has no dense storage to allocate
+        let triplets = read_triplets::<f64, _>(text.as_bytes()).unwrap();
+        assert_eq!((triplets.rows, triplets.entries.len()), (3000000000, 0));
+    }
+}
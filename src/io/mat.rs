@@ -0,0 +1,900 @@
+use super::{dense_zeros, from_column_major, invalid, market::Triplets, Element, ElementKind};
+use crate::{error::LinalgError, matrix::mat::Matrix};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+use num_traits::{NumCast, One};
+use std::io::{Read, Write};
+
+// Level 5 MAT-files, as MATLAB writes them with `save -v7` or older. A 128-byte header of
+// descriptive text, subsystem offset, version and endian indicator is followed by data elements,
+// each an 8-byte (type, size) tag and its data padded to 8 bytes. Tags of at most 4 bytes of data
+// may use the small format, with the size in the upper half of the type word and the data in the
+// second word. A compressed element holds another element as a zlib stream. Version 7.3 files are
+// HDF5 containers and not supported.
+const HEADER_SIZE: usize = 128;
+const TEXT_SIZE: usize = 116;
+const VERSION: u16 = 0x0100;
+
+const MI_INT8: u32 = 1;
+const MI_UINT8: u32 = 2;
+const MI_INT16: u32 = 3;
+const MI_UINT16: u32 = 4;
+const MI_INT32: u32 = 5;
+const MI_UINT32: u32 = 6;
+const MI_SINGLE: u32 = 7;
+const MI_DOUBLE: u32 = 9;
+const MI_INT64: u32 = 12;
+const MI_UINT64: u32 = 13;
+const MI_MATRIX: u32 = 14;
+const MI_COMPRESSED: u32 = 15;
+
+const FLAG_COMPLEX: u32 = 0x0800;
+const FLAG_LOGICAL: u32 = 0x0200;
+
+// Array classes, the low byte of the array flags
+const MX_SPARSE: u32 = 5;
+const MX_DOUBLE: u32 = 6;
+const MX_SINGLE: u32 = 7;
+const MX_INT32: u32 = 12;
+const MX_UINT32: u32 = 13;
+const MX_INT64: u32 = 14;
+const MX_UINT64: u32 = 15;
+
+// The numeric type of a variable's values. Sparse arrays hold doubles, or logicals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Double,
+    Single,
+    Int(u32),
+    UInt(u32),
+    Logical,
+}
+
+impl Class {
+    fn from_code(code: u32) -> Option<Self> {
+        Some(match code {
+            MX_DOUBLE => Class::Double,
+            MX_SINGLE => Class::Single,
+            8 => Class::Int(8),
+            9 => Class::UInt(8),
+            10 => Class::Int(16),
+            11 => Class::UInt(16),
+            MX_INT32 => Class::Int(32),
+            MX_UINT32 => Class::UInt(32),
+            MX_INT64 => Class::Int(64),
+            MX_UINT64 => Class::UInt(64),
+            _ => return None,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Class::Double => "double",
+            Class::Single => "single",
+            Class::Int(8) => "int8",
+            Class::Int(16) => "int16",
+            Class::Int(32) => "int32",
+            Class::Int(_) => "int64",
+            Class::UInt(8) => "uint8",
+            Class::UInt(16) => "uint16",
+            Class::UInt(32) => "uint32",
+            Class::UInt(_) => "uint64",
+            Class::Logical => "logical",
+        }
+    }
+
+    // Whether every value of the class converts to `T` exactly
+    fn fits<T: Element>(self) -> bool {
+        let bits = 8 * T::SIZE as u32
+            / if T::KIND == ElementKind::Complex {
+                2
+            } else {
+                1
+            };
+        let class = if self == Class::Logical {
+            Class::UInt(8)
+        } else {
+            self
+        };
+        match (T::KIND, class) {
+            (ElementKind::Float | ElementKind::Complex, Class::Double) => bits == 64,
+            (ElementKind::Float | ElementKind::Complex, Class::Single) => true,
+            // The integer has to fit in the mantissa
+            (ElementKind::Float | ElementKind::Complex, Class::Int(b) | Class::UInt(b)) => {
+                b < if bits == 64 { 53 } else { 24 }
+            }
+            (ElementKind::Int, Class::Int(b)) => b <= bits,
+            (ElementKind::Int, Class::UInt(b)) => b < bits,
+            (ElementKind::UInt, Class::UInt(b)) => b <= bits,
+            _ => false,
+        }
+    }
+}
+
+fn class_of<T: Element>() -> (u32, Class) {
+    match (T::KIND, T::SIZE) {
+        (ElementKind::Float, 4) | (ElementKind::Complex, 8) => (MX_SINGLE, Class::Single),
+        (ElementKind::Float | ElementKind::Complex, _) => (MX_DOUBLE, Class::Double),
+        (ElementKind::Int, 4) => (MX_INT32, Class::Int(32)),
+        (ElementKind::Int, _) => (MX_INT64, Class::Int(64)),
+        (ElementKind::UInt, 4) => (MX_UINT32, Class::UInt(32)),
+        (ElementKind::UInt, _) => (MX_UINT64, Class::UInt(64)),
+    }
+}
+
+fn type_name<T: Element>() -> String {
+    let name = class_of::<T>().1.name();
+    if T::KIND == ElementKind::Complex {
+        format!("complex {name}")
+    } else {
+        name.to_string()
+    }
+}
+
+// Values as stored, widened without loss. MATLAB saves doubles as smaller integer types when
+// they all fit, so the storage type says little about the class.
+#[derive(Debug, Clone)]
+enum Values {
+    Float(Vec<f64>),
+    Int(Vec<i64>),
+    UInt(Vec<u64>),
+}
+
+impl Values {
+    fn decode(ty: u32, bytes: &[u8], big_endian: bool) -> Result<Self, LinalgError> {
+        macro_rules! decode {
+            ($t:ty, $variant:ident, $wide:ty) => {{
+                const SIZE: usize = std::mem::size_of::<$t>();
+                if !bytes.len().is_multiple_of(SIZE) {
+                    return Err(invalid("numeric data ends inside a value"));
+                }
+                Values::$variant(
+                    bytes
+                        .chunks_exact(SIZE)
+                        .map(|b| {
+                            let b = b.try_into().unwrap();
+                            (if big_endian {
+                                <$t>::from_be_bytes(b)
+                            } else {
+                                <$t>::from_le_bytes(b)
+                            }) as $wide
+                        })
+                        .collect(),
+                )
+            }};
+        }
+        Ok(match ty {
+            MI_INT8 => decode!(i8, Int, i64),
+            MI_UINT8 => decode!(u8, UInt, u64),
+            MI_INT16 => decode!(i16, Int, i64),
+            MI_UINT16 => decode!(u16, UInt, u64),
+            MI_INT32 => decode!(i32, Int, i64),
+            MI_UINT32 => decode!(u32, UInt, u64),
+            MI_INT64 => decode!(i64, Int, i64),
+            MI_UINT64 => decode!(u64, UInt, u64),
+            MI_SINGLE => decode!(f32, Float, f64),
+            MI_DOUBLE => decode!(f64, Float, f64),
+            other => return Err(invalid(format!("data type {other} is not numeric"))),
+        })
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Values::Float(v) => v.len(),
+            Values::Int(v) => v.len(),
+            Values::UInt(v) => v.len(),
+        }
+    }
+
+    fn get<C: NumCast>(&self, k: usize) -> Result<C, LinalgError> {
+        match self {
+            Values::Float(v) => C::from(v[k]),
+            Values::Int(v) => C::from(v[k]),
+            Values::UInt(v) => C::from(v[k]),
+        }
+        .ok_or_else(|| invalid("value is out of range for its class"))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Data {
+    Dense {
+        re: Values,
+        im: Option<Values>,
+    },
+    // Compressed sparse columns, the rows of column j are `row_indices[col_starts[j]..col_starts[j + 1]]`
+    Sparse {
+        row_indices: Vec<usize>,
+        col_starts: Vec<usize>,
+        re: Values,
+        im: Option<Values>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Variable {
+    name: String,
+    class: Class,
+    complex: bool,
+    dims: Vec<usize>,
+    data: Data,
+}
+
+impl Variable {
+    fn shape(&self) -> Result<(usize, usize), LinalgError> {
+        match self.dims[..] {
+            [rows, cols] => Ok((rows, cols)),
+            _ => Err(invalid(format!(
+                "'{}' has {} dimensions, only matrices are supported",
+                self.name,
+                self.dims.len()
+            ))),
+        }
+    }
+
+    fn check<T: Element>(&self) -> Result<(), LinalgError> {
+        if self.class.fits::<T>() && (!self.complex || T::KIND == ElementKind::Complex) {
+            return Ok(());
+        }
+        let found = if self.complex {
+            format!("complex {}", self.class.name())
+        } else {
+            self.class.name().to_string()
+        };
+        Err(LinalgError::TypeMismatch {
+            expected: type_name::<T>(),
+            found,
+        })
+    }
+
+    // Value `k` of the real and imaginary parts, logical sparse arrays may leave the values out
+    fn value<T: Element>(re: &Values, im: Option<&Values>, k: usize) -> Result<T, LinalgError> {
+        let re = if re.len() == 0 {
+            T::Component::one()
+        } else {
+            re.get(k)?
+        };
+        let im = match im {
+            Some(im) => im.get(k)?,
+            None => T::Component::default(),
+        };
+        Ok(T::from_parts(re, im).unwrap())
+    }
+}
+
+// All numeric and sparse variables of a MAT-file, other classes (cells, structs, chars and
+// objects) are skipped
+#[derive(Debug, Clone)]
+pub struct MatFile {
+    variables: Vec<Variable>,
+}
+
+impl MatFile {
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, LinalgError> {
+        let mut header = [0u8; HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        let big_endian = match &header[126..] {
+            b"IM" => false,
+            b"MI" => true,
+            _ => return Err(invalid("not a MAT-file")),
+        };
+        let version = if big_endian {
+            u16::from_be_bytes([header[124], header[125]])
+        } else {
+            u16::from_le_bytes([header[124], header[125]])
+        };
+        if version != VERSION {
+            return Err(LinalgError::UnsupportedVersion {
+                found: version.into(),
+                supported: VERSION.into(),
+            });
+        }
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let mut elements = Elements::new(&bytes, big_endian);
+        let mut variables = Vec::new();
+        while let Some((ty, data)) = elements.next()? {
+            let variable = match ty {
+                MI_MATRIX => parse_matrix(data, big_endian)?,
+                MI_COMPRESSED => {
+                    let mut inflated = Vec::new();
+                    ZlibDecoder::new(data)
+                        .read_to_end(&mut inflated)
+                        .map_err(|err| invalid(format!("bad compressed element: {err}")))?;
+                    match Elements::new(&inflated, big_endian).next()? {
+                        Some((MI_MATRIX, data)) => parse_matrix(data, big_endian)?,
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            variables.extend(variable);
+        }
+        Ok(MatFile { variables })
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.variables.iter().map(|v| v.name.as_str())
+    }
+
+    pub fn is_sparse(&self, name: &str) -> Option<bool> {
+        self.find(name)
+            .ok()
+            .map(|v| matches!(v.data, Data::Sparse { .. }))
+    }
+
+    // The variable as a dense matrix. The class has to convert to `T` without loss, so doubles
+    // need `f64` but `int16` reads into `i32` or `f32` as well.
+    pub fn get<T: Element>(&self, name: &str) -> Result<Matrix<T>, LinalgError> {
+        let variable = self.find(name)?;
+        variable.check::<T>()?;
+        let (rows, cols) = variable.shape()?;
+        match &variable.data {
+            Data::Dense { re, im } => {
+                let len = rows
+                    .checked_mul(cols)
+                    .ok_or_else(|| invalid(format!("'{name}' is too large")))?;
+                if re.len() != len || im.as_ref().is_some_and(|im| im.len() != len) {
+                    return Err(invalid(format!(
+                        "'{name}' has {} values for {rows}x{cols} elements",
+                        re.len()
+                    )));
+                }
+                let data = (0..len)
+                    .map(|k| Variable::value(re, im.as_ref(), k))
+                    .collect::<Result<Vec<T>, _>>()?;
+                Ok(from_column_major(rows, cols, &data))
+            }
+            Data::Sparse { .. } => {
+                let triplets = self.triplets::<T>(name)?;
+                let mut m = Matrix {
+                    rows,
+                    cols,
+                    data: dense_zeros(rows, cols)?,
+                };
+                for (i, j, x) in triplets.entries {
+                    m[(i, j)] = m[(i, j)] + x;
+                }
+                Ok(m)
+            }
+        }
+    }
+
+    // The stored entries of a sparse variable, or the nonzero entries of a dense one, in column
+    // order
+    pub fn triplets<T: Element>(&self, name: &str) -> Result<Triplets<T>, LinalgError> {
+        let variable = self.find(name)?;
+        let Data::Sparse {
+            row_indices,
+            col_starts,
+            re,
+            im,
+        } = &variable.data
+        else {
+            let m = self.get::<T>(name)?;
+            let entries = (0..m.cols)
+                .flat_map(|j| (0..m.rows).map(move |i| (i, j)))
+                .filter(|&(i, j)| m[(i, j)] != T::default())
+                .map(|(i, j)| (i, j, m[(i, j)]))
+                .collect();
+            return Ok(Triplets {
+                rows: m.rows,
+                cols: m.cols,
+                entries,
+            });
+        };
+        variable.check::<T>()?;
+        let (rows, cols) = variable.shape()?;
+
+        let corrupt = || invalid(format!("'{name}' has inconsistent sparse indices"));
+        if col_starts.len() != cols + 1 || col_starts.windows(2).any(|w| w[0] > w[1]) {
+            return Err(corrupt());
+        }
+        let nnz = col_starts[cols];
+        let values_short = |v: &Values| v.len() != 0 && v.len() < nnz;
+        if row_indices.len() < nnz || values_short(re) || im.as_ref().is_some_and(values_short) {
+            return Err(corrupt());
+        }
+        let mut entries = Vec::with_capacity(nnz);
+        for j in 0..cols {
+            let range = col_starts[j]..col_starts[j + 1];
+            for (k, &i) in range.clone().zip(&row_indices[range]) {
+                if i >= rows {
+                    return Err(corrupt());
+                }
+                entries.push((i, j, Variable::value(re, im.as_ref(), k)?));
+            }
+        }
+        Ok(Triplets {
+            rows,
+            cols,
+            entries,
+        })
+    }
+
+    fn find(&self, name: &str) -> Result<&Variable, LinalgError> {
+        self.variables
+            .iter()
+            .find(|v| v.name == name)
+            .ok_or_else(|| invalid(format!("no numeric variable named '{name}'")))
+    }
+}
+
+// Walks the data elements of a byte buffer
+struct Elements<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl<'a> Elements<'a> {
+    fn new(bytes: &'a [u8], big_endian: bool) -> Self {
+        Elements {
+            bytes,
+            pos: 0,
+            big_endian,
+        }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], LinalgError> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| invalid("data element is truncated"))?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, LinalgError> {
+        let bytes = self.take(4)?.try_into().unwrap();
+        Ok(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    // The type and data of the next element, `None` at the end of the buffer
+    fn next(&mut self) -> Result<Option<(u32, &'a [u8])>, LinalgError> {
+        if self.pos == self.bytes.len() {
+            return Ok(None);
+        }
+        let word = self.u32()?;
+        let (ty, size) = (word & 0xffff, (word >> 16) as usize);
+        if size != 0 {
+            if size > 4 {
+                return Err(invalid("small data element holds more than 4 bytes"));
+            }
+            return Ok(Some((ty, &self.take(4)?[..size])));
+        }
+        let size = self.u32()? as usize;
+        let data = self.take(size)?;
+        // Compressed elements are not padded, and the last element of a file may not be either
+        if ty != MI_COMPRESSED {
+            let padding = size.next_multiple_of(8) - size;
+            self.pos = (self.pos + padding).min(self.bytes.len());
+        }
+        Ok(Some((ty, data)))
+    }
+
+    fn expect(&mut self, what: &str) -> Result<(u32, &'a [u8]), LinalgError> {
+        self.next()?
+            .ok_or_else(|| invalid(format!("array is missing its {what}")))
+    }
+
+    fn indices(&mut self, what: &str) -> Result<Vec<usize>, LinalgError> {
+        let (ty, data) = self.expect(what)?;
+        let values: Result<Vec<usize>, _> = match Values::decode(ty, data, self.big_endian)? {
+            Values::Int(v) => v.into_iter().map(usize::try_from).collect(),
+            Values::UInt(v) => v.into_iter().map(usize::try_from).collect(),
+            Values::Float(_) => return Err(invalid(format!("{what} are not integers"))),
+        };
+        values.map_err(|_| invalid(format!("{what} are out of range")))
+    }
+}
+
+// `None` for arrays of a class that does not map to a matrix
+fn parse_matrix(bytes: &[u8], big_endian: bool) -> Result<Option<Variable>, LinalgError> {
+    let mut elements = Elements::new(bytes, big_endian);
+    // An empty matrix element is how MATLAB writes an empty cell
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    let (ty, flags) = elements.expect("flags")?;
+    if ty != MI_UINT32 || flags.len() != 8 {
+        return Err(invalid("array flags are malformed"));
+    }
+    let flags = Elements::new(flags, big_endian).u32()?;
+    let code = flags & 0xff;
+    let complex = flags & FLAG_COMPLEX != 0;
+    let logical = flags & FLAG_LOGICAL != 0;
+
+    let dims = elements.indices("dimensions")?;
+    let (_, name) = elements.expect("name")?;
+    let name = String::from_utf8_lossy(name).into_owned();
+
+    let parts = |elements: &mut Elements| -> Result<(Values, Option<Values>), LinalgError> {
+        let re = match elements.next()? {
+            Some((ty, data)) => Values::decode(ty, data, big_endian)?,
+            None => Values::Float(Vec::new()),
+        };
+        let im = if complex {
+            let (ty, data) = elements.expect("imaginary part")?;
+            Some(Values::decode(ty, data, big_endian)?)
+        } else {
+            None
+        };
+        Ok((re, im))
+    };
+
+    let (class, data) = if code == MX_SPARSE {
+        let row_indices = elements.indices("row indices")?;
+        let col_starts = elements.indices("column starts")?;
+        let (re, im) = parts(&mut elements)?;
+        let class = if logical {
+            Class::Logical
+        } else {
+            Class::Double
+        };
+        let data = Data::Sparse {
+            row_indices,
+            col_starts,
+            re,
+            im,
+        };
+        (class, data)
+    } else if let Some(class) = Class::from_code(code) {
+        let (re, im) = parts(&mut elements)?;
+        let class = if logical { Class::Logical } else { class };
+        (class, Data::Dense { re, im })
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(Variable {
+        name,
+        class,
+        complex,
+        dims,
+        data,
+    }))
+}
+
+// Writes a little-endian Level 5 MAT-file, readable by MATLAB `load` and `scipy.io.loadmat`
+pub struct MatWriter<W: Write> {
+    writer: W,
+    compressed: bool,
+}
+
+impl<W: Write> MatWriter<W> {
+    pub fn new(writer: W) -> Result<Self, LinalgError> {
+        Self::start(writer, false)
+    }
+
+    // Each variable in its own zlib-compressed element, like MATLAB's default `save`
+    pub fn compressed(writer: W) -> Result<Self, LinalgError> {
+        Self::start(writer, true)
+    }
+
+    fn start(mut writer: W, compressed: bool) -> Result<Self, LinalgError> {
+        let mut header = [0u8; HEADER_SIZE];
+        let text = b"MATLAB 5.0 MAT-file, written by linalg";
+        header[..TEXT_SIZE].fill(b' ');
+        header[..text.len()].copy_from_slice(text);
+        header[124..126].copy_from_slice(&VERSION.to_le_bytes());
+        header[126..].copy_from_slice(b"IM");
+        writer.write_all(&header)?;
+        Ok(MatWriter { writer, compressed })
+    }
+
+    pub fn add<T: Element>(&mut self, name: &str, matrix: &Matrix<T>) -> Result<(), LinalgError> {
+        let column_major = (0..matrix.cols).flat_map(|j| (0..matrix.rows).map(move |i| (i, j)));
+        let values: Vec<T> = column_major.map(|(i, j)| matrix[(i, j)]).collect();
+        let (code, _) = class_of::<T>();
+        let mut body = array_header::<T>(name, code, matrix.rows, matrix.cols, 0)?;
+        push_values(&mut body, &values);
+        self.write_matrix(body)
+    }
+
+    // Writes a sparse array, which MATLAB only has for doubles. Duplicate entries are summed on
+    // load, as `sparse(i, j, v)` does.
+    pub fn add_sparse<T: Element>(
+        &mut self,
+        name: &str,
+        triplets: &Triplets<T>,
+    ) -> Result<(), LinalgError> {
+        if class_of::<T>().1 != Class::Double {
+            return Err(LinalgError::TypeMismatch {
+                expected: "double".to_string(),
+                found: type_name::<T>(),
+            });
+        }
+        let mut entries = triplets.entries.clone();
+        entries.sort_by_key(|&(i, j, _)| (j, i));
+        if let Some(&(i, j, _)) = entries
+            .iter()
+            .find(|&&(i, j, _)| i >= triplets.rows || j >= triplets.cols)
+        {
+            return Err(LinalgError::DimensionMismatch {
+                expected: (triplets.rows, triplets.cols),
+                found: (i + 1, j + 1),
+            });
+        }
+
+        let mut col_starts = vec![0i32; triplets.cols + 1];
+        for &(_, j, _) in &entries {
+            col_starts[j + 1] += 1;
+        }
+        for j in 0..triplets.cols {
+            col_starts[j + 1] += col_starts[j];
+        }
+        let nnz = entries.len();
+        let mut body = array_header::<T>(name, MX_SPARSE, triplets.rows, triplets.cols, nnz)?;
+        let row_indices: Vec<i32> = entries.iter().map(|&(i, _, _)| i as i32).collect();
+        push_element(&mut body, MI_INT32, &int32_bytes(&row_indices));
+        push_element(&mut body, MI_INT32, &int32_bytes(&col_starts));
+        let values: Vec<T> = entries.iter().map(|&(_, _, x)| x).collect();
+        push_values(&mut body, &values);
+        self.write_matrix(body)
+    }
+
+    pub fn finish(mut self) -> Result<W, LinalgError> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_matrix(&mut self, body: Vec<u8>) -> Result<(), LinalgError> {
+        let mut element = Vec::with_capacity(body.len() + 8);
+        push_tag(&mut element, MI_MATRIX, body.len())?;
+        element.extend_from_slice(&body);
+        if self.compressed {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&element)?;
+            let deflated = encoder.finish()?;
+            let mut tag = Vec::with_capacity(8);
+            push_tag(&mut tag, MI_COMPRESSED, deflated.len())?;
+            self.writer.write_all(&tag)?;
+            self.writer.write_all(&deflated)?;
+        } else {
+            self.writer.write_all(&element)?;
+        }
+        Ok(())
+    }
+}
+
+// Flags, dimensions and name of an array element
+fn array_header<T: Element>(
+    name: &str,
+    code: u32,
+    rows: usize,
+    cols: usize,
+    nnz: usize,
+) -> Result<Vec<u8>, LinalgError> {
+    let valid_name = name.len() <= 63
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_name {
+        return Err(invalid(format!(
+            "'{name}' is not a valid MATLAB variable name"
+        )));
+    }
+    let too_large = || invalid("matrix is too large for a MAT-file");
+    let rows = i32::try_from(rows).map_err(|_| too_large())?;
+    let cols = i32::try_from(cols).map_err(|_| too_large())?;
+    let nnz = u32::try_from(nnz).map_err(|_| too_large())?;
+
+    let mut flags = code;
+    if T::KIND == ElementKind::Complex {
+        flags |= FLAG_COMPLEX;
+    }
+    let mut body = Vec::new();
+    let flag_bytes: Vec<u8> = [flags, nnz].iter().flat_map(|x| x.to_le_bytes()).collect();
+    push_element(&mut body, MI_UINT32, &flag_bytes);
+    push_element(&mut body, MI_INT32, &int32_bytes(&[rows, cols]));
+    push_element(&mut body, MI_INT8, name.as_bytes());
+    Ok(body)
+}
+
+// The real part, then the imaginary part for complex types
+fn push_values<T: Element>(body: &mut Vec<u8>, values: &[T]) {
+    let ty = match (T::KIND, T::SIZE) {
+        (ElementKind::Float, 4) | (ElementKind::Complex, 8) => MI_SINGLE,
+        (ElementKind::Float | ElementKind::Complex, _) => MI_DOUBLE,
+        (ElementKind::Int, 4) => MI_INT32,
+        (ElementKind::Int, _) => MI_INT64,
+        (ElementKind::UInt, 4) => MI_UINT32,
+        (ElementKind::UInt, _) => MI_UINT64,
+    };
+    let mut bytes = vec![0u8; values.len() * T::SIZE];
+    for (x, out) in values.iter().zip(bytes.chunks_exact_mut(T::SIZE)) {
+        x.write_le_bytes(out);
+    }
+    if T::KIND == ElementKind::Complex {
+        let half = T::SIZE / 2;
+        let part = |offset: usize| -> Vec<u8> {
+            bytes
+                .chunks_exact(T::SIZE)
+                .flat_map(|b| &b[offset..offset + half])
+                .copied()
+                .collect()
+        };
+        push_element(body, ty, &part(0));
+        push_element(body, ty, &part(half));
+    } else {
+        push_element(body, ty, &bytes);
+    }
+}
+
+fn push_tag(out: &mut Vec<u8>, ty: u32, size: usize) -> Result<(), LinalgError> {
+    let size = u32::try_from(size).map_err(|_| invalid("matrix is too large for a MAT-file"))?;
+    out.extend_from_slice(&ty.to_le_bytes());
+    out.extend_from_slice(&size.to_le_bytes());
+    Ok(())
+}
+
+// Sizes inside an array are bounded by the array's own, already checked, size
+fn push_element(out: &mut Vec<u8>, ty: u32, data: &[u8]) {
+    out.extend_from_slice(&ty.to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    out.resize(out.len().next_multiple_of(8), 0);
+}
+
+fn int32_bytes(values: &[i32]) -> Vec<u8> {
+    values.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::{Complex32 as c32, Complex64 as c64};
+
+    const LITTLE_ENDIAN: &[u8] = include_bytes!("../../tests/fixtures/matlab_le.mat");
+    const BIG_ENDIAN: &[u8] = include_bytes!("../../tests/fixtures/matlab_be.mat");
+
+    #[test]
+    fn test_read_fixtures() {
+        let file = MatFile::read(LITTLE_ENDIAN).unwrap();
+        assert_eq!(file.names().collect::<Vec<_>>(), ["a", "b", "z", "k", "s"]);
+        assert_eq!(
+            file.get::<f64>("a").unwrap(),
+            Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+        );
+        assert_eq!(file.get::<f64>("b").unwrap(), Matrix::new([[0.5, -1.25]]));
+        assert_eq!(
+            file.get::<c32>("z").unwrap(),
+            Matrix::new([[c32::new(1.0, 2.0)], [c32::new(0.0, -3.0)]])
+        );
+        assert_eq!(file.get::<i32>("k").unwrap(), Matrix::new([[-2, 300]]));
+        assert_eq!(file.get::<f32>("k").unwrap(), Matrix::new([[-2.0, 300.0]]));
+
+        assert_eq!(file.is_sparse("s"), Some(true));
+        assert_eq!(
+            file.triplets::<f64>("s").unwrap().entries,
+            [(0, 0, 1.5), (2, 0, 2.0), (1, 2, -4.0)]
+        );
+        assert_eq!(
+            file.get::<f64>("s").unwrap(),
+            Matrix::new([[1.5, 0.0, 0.0], [0.0, 0.0, -4.0], [2.0, 0.0, 0.0]])
+        );
+
+        let file = MatFile::read(BIG_ENDIAN).unwrap();
+        assert_eq!(
+            file.get::<f64>("m").unwrap(),
+            Matrix::new([[1.5, 2.0], [-3.0, 4.0]])
+        );
+    }
+
+    #[test]
+    fn test_lossy_conversions_are_rejected() {
+        let file = MatFile::read(LITTLE_ENDIAN).unwrap();
+        assert_eq!(
+            file.get::<f32>("a").unwrap_err(),
+            LinalgError::TypeMismatch {
+                expected: "single".to_string(),
+                found: "double".to_string(),
+            }
+        );
+        assert!(matches!(
+            file.get::<f32>("z"),
+            Err(LinalgError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            file.get::<u32>("k"),
+            Err(LinalgError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            file.get::<f64>("c"),
+            Err(LinalgError::InvalidFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let a = Matrix::from_fn(3, 4, |i, j| i as f64 - 0.25 * j as f64);
+        let z = Matrix::new([[c64::new(1.0, -1.0), c64::new(0.5, 2.0)]]);
+        let n = Matrix::new([[u64::MAX], [7]]);
+        let s = Triplets {
+            rows: 2,
+            cols: 3,
+            entries: vec![(1, 2, 3.0), (0, 0, -1.0), (1, 0, 2.5)],
+        };
+        for compressed in [false, true] {
+            let mut writer = if compressed {
+                MatWriter::compressed(Vec::new()).unwrap()
+            } else {
+                MatWriter::new(Vec::new()).unwrap()
+            };
+            writer.add("a", &a).unwrap();
+            writer.add("z_1", &z).unwrap();
+            writer.add("n", &n).unwrap();
+            writer.add_sparse("s", &s).unwrap();
+            let bytes = writer.finish().unwrap();
+
+            let file = MatFile::read(&bytes[..]).unwrap();
+            assert_eq!(file.get::<f64>("a").unwrap(), a);
+            assert_eq!(file.get::<c64>("z_1").unwrap(), z);
+            assert_eq!(file.get::<u64>("n").unwrap(), n);
+            assert_eq!(
+                file.triplets::<f64>("s").unwrap().entries,
+                [(0, 0, -1.0), (1, 0, 2.5), (1, 2, 3.0)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut writer = MatWriter::new(Vec::new()).unwrap();
+        let m = Matrix::new([[1.0]]);
+        assert!(writer.add("1st", &m).is_err());
+        assert!(writer.add("has space", &m).is_err());
+        let s = Triplets {
+            rows: 1,
+            cols: 1,
+            entries: vec![(0, 0, 1.0f32)],
+        };
+        assert!(matches!(
+            writer.add_sparse("s", &s),
+            Err(LinalgError::TypeMismatch { .. })
+        ));
+
+        let mut v73 = LITTLE_ENDIAN[..HEADER_SIZE].to_vec();
+        v73[124..126].copy_from_slice(&0x0200u16.to_le_bytes());
+        assert_eq!(
+            MatFile::read(&v73[..]).unwrap_err(),
+            LinalgError::UnsupportedVersion {
+                found: 0x0200,
+                supported: 0x0100,
+            }
+        );
+        let truncated = &LITTLE_ENDIAN[..LITTLE_ENDIAN.len() - 20];
+        assert!(MatFile::read(truncated).is_err());
+        assert!(MatFile::read(&b"not a mat file"[..]).is_err());
+    }
+
+    #[test]
+    fn test_huge_sparse_variable() {
+        // A single stored entry, but far too many rows to densify
+        let file = MatFile {
+            variables: vec![Variable {
+                name: "s".to_string(),
+                class: Class::Double,
+                complex: false,
+                dims: vec![1 << 62, 1],
+                data: Data::Sparse {
+                    row_indices: vec![0],
+                    col_starts: vec![0, 1],
+                    re: Values::Float(vec![1.0]),
+                    im: None,
+                },
+            }],
+        };
+        assert_eq!(file.triplets::<f64>("s").unwrap().entries, [(0, 0, 1.0)]);
+        assert!(matches!(
+            file.get::<f64>("s"),
+            Err(LinalgError::InvalidFormat { .. })
+        ));
+    }
+}
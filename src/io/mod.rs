@@ -0,0 +1,314 @@
+pub mod csv;
+pub mod mapped;
+pub mod market;
+#[cfg(feature = "mat")]
+pub mod mat;
+pub mod native;
+pub mod npy;
+
+use crate::{error::LinalgError, matrix::mat::Matrix};
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use num::complex::Complex;
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+const CHUNK_ELEMENTS: usize = 64 * 1024; // Elements converted per read or write call
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementKind {
+    Float,
+    Complex,
+    Int,
+    UInt,
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+// How text formats write floating point numbers, integers are always written in full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    // The shortest text that parses back to the same value
+    Shortest,
+    // This many digits after the decimal point
+    Fixed(usize),
+    // An exponent, with this many digits after the decimal point of the mantissa
+    Scientific(usize),
+}
+
+// One real number of a text format: a real element, or either half of a complex one
+pub trait Component:
+    Copy + PartialEq + Default + num_traits::One + num_traits::NumCast + FromStr + private::Sealed
+{
+    fn write_text(self, notation: Notation, out: &mut String);
+}
+
+// Scalar types the file formats can store. All of them are plain old data, any bit pattern of
+// the right size is a valid value, which is what makes the zero-copy views sound.
+pub trait Element:
+    Copy
+    + Default
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Send
+    + Sync
+    + 'static
+    + private::Sealed
+{
+    const KIND: ElementKind;
+    // Size in bytes, both parts together for complex types
+    const SIZE: usize;
+
+    type Component: Component;
+
+    fn from_le_bytes(bytes: &[u8]) -> Self;
+    fn from_be_bytes(bytes: &[u8]) -> Self;
+    fn write_le_bytes(self, out: &mut [u8]);
+
+    // `None` for a real type given a nonzero imaginary part
+    fn from_parts(re: Self::Component, im: Self::Component) -> Option<Self>;
+    fn re(self) -> Self::Component;
+    fn im(self) -> Self::Component;
+    fn conj(self) -> Self;
+    // `None` when the negation does not fit, as for any nonzero unsigned value
+    fn checked_neg(self) -> Option<Self>;
+
+    fn from_bytes(bytes: &[u8], big_endian: bool) -> Self {
+        if big_endian {
+            Self::from_be_bytes(bytes)
+        } else {
+            Self::from_le_bytes(bytes)
+        }
+    }
+}
+
+macro_rules! impl_element {
+    ($kind:ident, |$x:ident| $neg:expr; $($t:ty),*) => {
+        $(
+            impl private::Sealed for $t {}
+
+            impl Element for $t {
+                const KIND: ElementKind = ElementKind::$kind;
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                type Component = $t;
+
+                fn from_le_bytes(bytes: &[u8]) -> Self {
+                    <$t>::from_le_bytes(bytes.try_into().unwrap())
+                }
+
+                fn from_be_bytes(bytes: &[u8]) -> Self {
+                    <$t>::from_be_bytes(bytes.try_into().unwrap())
+                }
+
+                fn write_le_bytes(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn from_parts(re: $t, im: $t) -> Option<Self> {
+                    (im == <$t>::default()).then_some(re)
+                }
+
+                fn re(self) -> $t {
+                    self
+                }
+
+                fn im(self) -> $t {
+                    <$t>::default()
+                }
+
+                fn conj(self) -> Self {
+                    self
+                }
+
+                fn checked_neg(self) -> Option<Self> {
+                    let $x = self;
+                    $neg
+                }
+            }
+        )*
+    };
+}
+
+impl_element!(Float, |x| Some(-x); f32, f64);
+impl_element!(Int, |x| x.checked_neg(); i32, i64);
+impl_element!(UInt, |x| x.checked_neg(); u32, u64);
+
+macro_rules! impl_component {
+    (float: $($t:ty),*) => {
+        $(
+            impl Component for $t {
+                fn write_text(self, notation: Notation, out: &mut String) {
+                    match notation {
+                        // Debug switches to an exponent for very large and small magnitudes,
+                        // where Display would write out every digit
+                        Notation::Shortest => write!(out, "{self:?}"),
+                        Notation::Fixed(digits) => write!(out, "{self:.digits$}"),
+                        Notation::Scientific(digits) => write!(out, "{self:.digits$e}"),
+                    }
+                    .unwrap();
+                }
+            }
+        )*
+    };
+    (int: $($t:ty),*) => {
+        $(
+            impl Component for $t {
+                fn write_text(self, _: Notation, out: &mut String) {
+                    write!(out, "{self}").unwrap();
+                }
+            }
+        )*
+    };
+}
+
+impl_component!(float: f32, f64);
+impl_component!(int: i32, i64, u32, u64);
+
+// Real part first, then imaginary, each in the byte order of the real type
+impl<R> private::Sealed for Complex<R> where R: Element {}
+
+impl<R> Element for Complex<R>
+where
+    R: Element<Component = R> + Component + num_traits::Float,
+{
+    const KIND: ElementKind = ElementKind::Complex;
+    const SIZE: usize = 2 * R::SIZE;
+
+    type Component = R;
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        let (re, im) = bytes.split_at(R::SIZE);
+        Complex::new(R::from_le_bytes(re), R::from_le_bytes(im))
+    }
+
+    fn from_be_bytes(bytes: &[u8]) -> Self {
+        let (re, im) = bytes.split_at(R::SIZE);
+        Complex::new(R::from_be_bytes(re), R::from_be_bytes(im))
+    }
+
+    fn write_le_bytes(self, out: &mut [u8]) {
+        let (re, im) = out.split_at_mut(R::SIZE);
+        self.re.write_le_bytes(re);
+        self.im.write_le_bytes(im);
+    }
+
+    fn from_parts(re: R, im: R) -> Option<Self> {
+        Some(Complex::new(re, im))
+    }
+
+    fn re(self) -> R {
+        self.re
+    }
+
+    fn im(self) -> R {
+        self.im
+    }
+
+    fn conj(self) -> Self {
+        Complex::conj(&self)
+    }
+
+    fn checked_neg(self) -> Option<Self> {
+        Some(-self)
+    }
+}
+
+// Reinterprets little-endian bytes as elements without copying, `None` when the platform is big
+// endian or the bytes are not suitably aligned
+pub(crate) fn cast_slice<T: Element>(bytes: &[u8]) -> Option<&[T]> {
+    if cfg!(target_endian = "big") || !bytes.len().is_multiple_of(T::SIZE) {
+        return None;
+    }
+    // SAFETY: every `Element` is plain old data of size `T::SIZE`, and `align_to` only hands
+    // back a middle part that is correctly aligned for `T`
+    let (head, elements, tail) = unsafe { bytes.align_to::<T>() };
+    (head.is_empty() && tail.is_empty()).then_some(elements)
+}
+
+pub(crate) fn cast_slice_mut<T: Element>(bytes: &mut [u8]) -> Option<&mut [T]> {
+    if cfg!(target_endian = "big") || !bytes.len().is_multiple_of(T::SIZE) {
+        return None;
+    }
+    // SAFETY: as for `cast_slice`
+    let (head, elements, tail) = unsafe { bytes.align_to_mut::<T>() };
+    (head.is_empty() && tail.is_empty()).then_some(elements)
+}
+
+// Reads `len` elements in chunks, so a huge (or corrupt) length never allocates up front more
+// than the stream actually holds
+pub(crate) fn read_elements<T: Element, R: Read>(
+    reader: &mut R,
+    len: usize,
+    big_endian: bool,
+) -> Result<AVec<T>, LinalgError> {
+    let mut data = AVec::with_capacity(CACHELINE_ALIGN, len.min(CHUNK_ELEMENTS));
+    let mut buf = vec![0u8; len.min(CHUNK_ELEMENTS) * T::SIZE];
+    let mut remaining = len;
+    while remaining > 0 {
+        let n = remaining.min(CHUNK_ELEMENTS);
+        let bytes = &mut buf[..n * T::SIZE];
+        reader.read_exact(bytes)?;
+        data.reserve(n);
+        for chunk in bytes.chunks_exact(T::SIZE) {
+            data.push(T::from_bytes(chunk, big_endian));
+        }
+        remaining -= n;
+    }
+    Ok(data)
+}
+
+// Writes elements in little-endian order through a bounded buffer
+pub(crate) fn write_elements<'a, T: Element, W: Write>(
+    writer: &mut W,
+    elements: impl IntoIterator<Item = &'a T>,
+) -> Result<(), LinalgError> {
+    let mut buf = vec![0u8; CHUNK_ELEMENTS * T::SIZE];
+    let mut filled = 0;
+    for &x in elements {
+        x.write_le_bytes(&mut buf[filled..filled + T::SIZE]);
+        filled += T::SIZE;
+        if filled == buf.len() {
+            writer.write_all(&buf)?;
+            filled = 0;
+        }
+    }
+    writer.write_all(&buf[..filled])?;
+    Ok(())
+}
+
+// Rearranges column-major elements, as Fortran and MATLAB store them, into a row-major matrix
+pub(crate) fn from_column_major<T: Element>(rows: usize, cols: usize, data: &[T]) -> Matrix<T> {
+    Matrix {
+        rows,
+        cols,
+        data: AVec::from_iter(
+            CACHELINE_ALIGN,
+            (0..rows).flat_map(|i| (0..cols).map(move |j| data[j * rows + i])),
+        ),
+    }
+}
+
+// Zeroed row-major storage for a shape taken from a file. Sparse formats declare sizes that no
+// payload backs, so running out of memory is an error rather than an abort.
+pub(crate) fn dense_zeros<T: Element>(rows: usize, cols: usize) -> Result<AVec<T>, LinalgError> {
+    let too_large = || LinalgError::InvalidFormat {
+        message: format!("a dense {rows}x{cols} matrix does not fit in memory"),
+    };
+    let len = rows.checked_mul(cols).ok_or_else(too_large)?;
+    let mut data = AVec::new(CACHELINE_ALIGN);
+    data.try_reserve_exact(len).map_err(|_| too_large())?;
+    data.resize(len, T::default());
+    Ok(data)
+}
+
+pub(crate) fn invalid(message: impl Into<String>) -> LinalgError {
+    LinalgError::InvalidFormat {
+        message: message.into(),
+    }
+}
@@ -0,0 +1,551 @@
+use super::{
+    cast_slice, from_column_major, invalid, read_elements, write_elements, Element, ElementKind,
+};
+use crate::{
+    error::LinalgError,
+    matrix::{mat::Matrix, mat_view::MatrixView},
+};
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+use xxhash_rust::xxh64::Xxh64;
+
+// Layout of the 64-byte header, all integers little-endian:
+//   0  magic "LINALGMX"
+//   8  format version, u32
+//  12  element type tag, u8
+//  13  storage order, u8 (0 row-major, 1 column-major)
+//  14  checksum kind, u8 (0 none, 1 CRC32, 2 XXH64)
+//  15  zero
+//  16  rows, u64
+//  24  cols, u64
+//  32  zero up to 64, so the payload starts cache-line aligned
+// The payload follows, then the checksum of the payload as a u64 unless the kind is none.
+const MAGIC: &[u8; 8] = b"LINALGMX";
+const VERSION: u32 = 1;
+pub(super) const HEADER_SIZE: usize = 64;
+const TRAILER_SIZE: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checksum {
+    None,
+    Crc32,
+    XxHash64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageOrder {
+    RowMajor,
+    ColumnMajor,
+}
+
+impl<T: Element> Matrix<T> {
+    pub fn write_native<W: Write>(&self, writer: W, checksum: Checksum) -> Result<(), LinalgError> {
+        let mut writer = NativeWriter::new(
+            writer,
+            self.rows,
+            self.cols,
+            StorageOrder::RowMajor,
+            checksum,
+        )?;
+        writer.write(&self.data)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    // Streams the payload through the checksum, a mismatch is a `ChecksumMismatch` error
+    pub fn read_native<R: Read>(mut reader: R) -> Result<Self, LinalgError> {
+        let mut bytes = [0u8; HEADER_SIZE];
+        reader.read_exact(&mut bytes)?;
+        let header = Header::decode(&bytes)?;
+        header.check::<T>()?;
+
+        let mut hashing = HashingReader {
+            reader: &mut reader,
+            hasher: Hasher::new(header.checksum),
+        };
+        let data = read_elements(&mut hashing, header.rows * header.cols, false)?;
+        let found = hashing.hasher.finish();
+        if header.checksum != Checksum::None {
+            let mut stored = [0u8; TRAILER_SIZE];
+            reader.read_exact(&mut stored)?;
+            verify(u64::from_le_bytes(stored), found)?;
+        }
+        Ok(header.into_matrix(data))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    tag: u8,
+    order: StorageOrder,
+    checksum: Checksum,
+    rows: usize,
+    cols: usize,
+}
+
+impl Header {
+    fn encode(&self) -> [u8; HEADER_SIZE] {
+        let mut bytes = [0u8; HEADER_SIZE];
+        bytes[..8].copy_from_slice(MAGIC);
+        bytes[8..12].copy_from_slice(&VERSION.to_le_bytes());
+        bytes[12] = self.tag;
+        bytes[13] = match self.order {
+            StorageOrder::RowMajor => 0,
+            StorageOrder::ColumnMajor => 1,
+        };
+        bytes[14] = match self.checksum {
+            Checksum::None => 0,
+            Checksum::Crc32 => 1,
+            Checksum::XxHash64 => 2,
+        };
+        bytes[16..24].copy_from_slice(&(self.rows as u64).to_le_bytes());
+        bytes[24..32].copy_from_slice(&(self.cols as u64).to_le_bytes());
+        bytes
+    }
+
+    fn decode(bytes: &[u8; HEADER_SIZE]) -> Result<Self, LinalgError> {
+        if &bytes[..8] != MAGIC {
+            return Err(invalid("not a linalg matrix file"));
+        }
+        let version = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        if version != VERSION {
+            return Err(LinalgError::UnsupportedVersion {
+                found: version,
+                supported: VERSION,
+            });
+        }
+        let tag = bytes[12];
+        if type_name(tag).is_none() {
+            return Err(invalid(format!("unknown element type tag {tag}")));
+        }
+        let order = match bytes[13] {
+            0 => StorageOrder::RowMajor,
+            1 => StorageOrder::ColumnMajor,
+            other => return Err(invalid(format!("unknown storage order {other}"))),
+        };
+        let checksum = match bytes[14] {
+            0 => Checksum::None,
+            1 => Checksum::Crc32,
+            2 => Checksum::XxHash64,
+            other => return Err(invalid(format!("unknown checksum kind {other}"))),
+        };
+
+        let dim = |range: std::ops::Range<usize>| {
+            usize::try_from(u64::from_le_bytes(bytes[range].try_into().unwrap()))
+                .map_err(|_| invalid("matrix is too large for this platform"))
+        };
+        let (rows, cols) = (dim(16..24)?, dim(24..32)?);
+        // 16 bytes is the largest element size
+        if rows
+            .checked_mul(cols)
+            .and_then(|n| n.checked_mul(16))
+            .is_none()
+        {
+            return Err(invalid("matrix is too large for this platform"));
+        }
+
+        Ok(Header {
+            tag,
+            order,
+            checksum,
+            rows,
+            cols,
+        })
+    }
+
+    fn check<T: Element>(&self) -> Result<(), LinalgError> {
+        if self.tag == tag::<T>() {
+            Ok(())
+        } else {
+            Err(LinalgError::TypeMismatch {
+                expected: type_name(tag::<T>()).unwrap().to_string(),
+                found: type_name(self.tag).unwrap().to_string(),
+            })
+        }
+    }
+
+    fn payload_size<T: Element>(&self) -> usize {
+        self.rows * self.cols * T::SIZE
+    }
+
+    fn into_matrix<T: Element>(self, data: AVec<T>) -> Matrix<T> {
+        match self.order {
+            StorageOrder::RowMajor => Matrix {
+                rows: self.rows,
+                cols: self.cols,
+                data,
+            },
+            StorageOrder::ColumnMajor => from_column_major(self.rows, self.cols, &data),
+        }
+    }
+}
+
+// Header of a row-major file without a checksum, the layout that can be mapped and written in
+// place
+pub(super) fn mappable_header<T: Element>(rows: usize, cols: usize) -> [u8; HEADER_SIZE] {
+    Header {
+        tag: tag::<T>(),
+        order: StorageOrder::RowMajor,
+        checksum: Checksum::None,
+        rows,
+        cols,
+    }
+    .encode()
+}
+
+// Shape, order and checksum kind of a file holding `T`
+pub(super) fn parse_header<T: Element>(
+    bytes: &[u8],
+) -> Result<(usize, usize, StorageOrder, Checksum), LinalgError> {
+    let bytes = bytes
+        .first_chunk::<HEADER_SIZE>()
+        .ok_or_else(|| invalid("truncated header"))?;
+    let header = Header::decode(bytes)?;
+    header.check::<T>()?;
+    Ok((header.rows, header.cols, header.order, header.checksum))
+}
+
+fn tag<T: Element>() -> u8 {
+    match (T::KIND, T::SIZE) {
+        (ElementKind::Float, 4) => 1,
+        (ElementKind::Float, _) => 2,
+        (ElementKind::Complex, 8) => 3,
+        (ElementKind::Complex, _) => 4,
+        (ElementKind::Int, 4) => 5,
+        (ElementKind::Int, _) => 6,
+        (ElementKind::UInt, 4) => 7,
+        (ElementKind::UInt, _) => 8,
+    }
+}
+
+fn type_name(tag: u8) -> Option<&'static str> {
+    let names = ["f32", "f64", "c32", "c64", "i32", "i64", "u32", "u64"];
+    names.get(usize::from(tag).checked_sub(1)?).copied()
+}
+
+fn verify(stored: u64, computed: u64) -> Result<(), LinalgError> {
+    if stored == computed {
+        Ok(())
+    } else {
+        Err(LinalgError::ChecksumMismatch {
+            expected: stored,
+            found: computed,
+        })
+    }
+}
+
+enum Hasher {
+    None,
+    Crc32(crc32fast::Hasher),
+    XxHash64(Box<Xxh64>),
+}
+
+impl Hasher {
+    fn new(checksum: Checksum) -> Self {
+        match checksum {
+            Checksum::None => Hasher::None,
+            Checksum::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+            Checksum::XxHash64 => Hasher::XxHash64(Box::new(Xxh64::new(0))),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Hasher::None => {}
+            Hasher::Crc32(h) => h.update(bytes),
+            Hasher::XxHash64(h) => h.update(bytes),
+        }
+    }
+
+    fn finish(self) -> u64 {
+        match self {
+            Hasher::None => 0,
+            Hasher::Crc32(h) => u64::from(h.finalize()),
+            Hasher::XxHash64(h) => h.digest(),
+        }
+    }
+}
+
+struct HashingReader<R> {
+    reader: R,
+    hasher: Hasher,
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.reader.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+struct HashingWriter<W> {
+    writer: W,
+    hasher: Hasher,
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.writer.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+// Writes a matrix piece by piece, so it never has to be in memory whole. The elements go in the
+// storage order given to `new`, in slices of any length.
+pub struct NativeWriter<W: Write, T: Element> {
+    inner: HashingWriter<W>,
+    checksum: Checksum,
+    remaining: usize,
+    marker: PhantomData<T>,
+}
+
+impl<W: Write, T: Element> NativeWriter<W, T> {
+    pub fn new(
+        mut writer: W,
+        rows: usize,
+        cols: usize,
+        order: StorageOrder,
+        checksum: Checksum,
+    ) -> Result<Self, LinalgError> {
+        let header = Header {
+            tag: tag::<T>(),
+            order,
+            checksum,
+            rows,
+            cols,
+        };
+        writer.write_all(&header.encode())?;
+        Ok(NativeWriter {
+            inner: HashingWriter {
+                writer,
+                hasher: Hasher::new(checksum),
+            },
+            checksum,
+            remaining: rows * cols,
+            marker: PhantomData,
+        })
+    }
+
+    pub fn write(&mut self, elements: &[T]) -> Result<(), LinalgError> {
+        assert!(
+            elements.len() <= self.remaining,
+            "More elements than rows * cols"
+        );
+        write_elements(&mut self.inner, elements)?;
+        self.remaining -= elements.len();
+        Ok(())
+    }
+
+    // Appends the checksum and hands back the underlying writer
+    pub fn finish(self) -> Result<W, LinalgError> {
+        assert_eq!(self.remaining, 0, "Fewer elements than rows * cols");
+        let HashingWriter { mut writer, hasher } = self.inner;
+        if self.checksum != Checksum::None {
+            writer.write_all(&hasher.finish().to_le_bytes())?;
+        }
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+// A matrix file already in memory, such as a mapped file or a received buffer, read without
+// copying the payload. The checksum is verified when the view is created.
+#[derive(Debug)]
+pub struct NativeView<'a, T: Element> {
+    header: Header,
+    payload: &'a [u8],
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: Element> NativeView<'a, T> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, LinalgError> {
+        let header_bytes = bytes
+            .first_chunk::<HEADER_SIZE>()
+            .ok_or_else(|| invalid("truncated header"))?;
+        let header = Header::decode(header_bytes)?;
+        header.check::<T>()?;
+
+        let size = header.payload_size::<T>();
+        let trailer = match header.checksum {
+            Checksum::None => 0,
+            _ => TRAILER_SIZE,
+        };
+        // The header only bounds the payload itself, not the payload with header and trailer
+        let end = size
+            .checked_add(HEADER_SIZE + trailer)
+            .ok_or_else(|| invalid("matrix is too large for this platform"))?;
+        if bytes.len() < end {
+            return Err(invalid("truncated payload"));
+        }
+        let payload = &bytes[HEADER_SIZE..HEADER_SIZE + size];
+        if header.checksum != Checksum::None {
+            let mut hasher = Hasher::new(header.checksum);
+            hasher.update(payload);
+            let stored = &bytes[HEADER_SIZE + size..end];
+            verify(
+                u64::from_le_bytes(stored.try_into().unwrap()),
+                hasher.finish(),
+            )?;
+        }
+
+        Ok(NativeView {
+            header,
+            payload,
+            marker: PhantomData,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.header.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.header.cols
+    }
+
+    pub fn order(&self) -> StorageOrder {
+        self.header.order
+    }
+
+    // The payload as a view, `None` when it is column-major or the buffer is misaligned for `T`
+    pub fn view(&self) -> Option<MatrixView<'a, T>> {
+        if self.header.order != StorageOrder::RowMajor {
+            return None;
+        }
+        cast_slice(self.payload)
+            .map(|data| MatrixView::new(self.header.rows, self.header.cols, data))
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> {
+        let data = AVec::from_iter(
+            CACHELINE_ALIGN,
+            self.payload.chunks_exact(T::SIZE).map(T::from_le_bytes),
+        );
+        self.header.into_matrix(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::{Complex32 as c32, Complex64 as c64};
+
+    fn encoded<T: Element>(m: &Matrix<T>, checksum: Checksum) -> AVec<u8> {
+        let mut bytes = Vec::new();
+        m.write_native(&mut bytes, checksum).unwrap();
+        AVec::from_slice(CACHELINE_ALIGN, &bytes)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let m = Matrix::from_fn(3, 5, |i, j| (i * 5 + j) as f32 * 0.5);
+        for checksum in [Checksum::None, Checksum::Crc32, Checksum::XxHash64] {
+            let bytes = encoded(&m, checksum);
+            let trailer = if checksum == Checksum::None { 0 } else { 8 };
+            assert_eq!(bytes.len(), HEADER_SIZE + 15 * 4 + trailer);
+            assert_eq!(Matrix::<f32>::read_native(&bytes[..]).unwrap(), m);
+
+            let view = NativeView::<f32>::parse(&bytes).unwrap();
+            assert_eq!(view.view().unwrap(), m.view(0..3, 0..5));
+            assert_eq!(view.to_matrix(), m);
+        }
+
+        let z = Matrix::new([[c32::new(1.0, -1.0)], [c32::new(0.0, 2.0)]]);
+        let bytes = encoded(&z, Checksum::Crc32);
+        assert_eq!(Matrix::<c32>::read_native(&bytes[..]).unwrap(), z);
+    }
+
+    #[test]
+    fn test_corruption_is_detected() {
+        let m = Matrix::from_fn(4, 4, |i, j| (i + j) as f64);
+        for checksum in [Checksum::Crc32, Checksum::XxHash64] {
+            let mut bytes = encoded(&m, checksum);
+            bytes[HEADER_SIZE + 17] ^= 0x40;
+            assert!(matches!(
+                Matrix::<f64>::read_native(&bytes[..]),
+                Err(LinalgError::ChecksumMismatch { .. })
+            ));
+            assert!(matches!(
+                NativeView::<f64>::parse(&bytes),
+                Err(LinalgError::ChecksumMismatch { .. })
+            ));
+        }
+
+        let bytes = encoded(&m, Checksum::Crc32);
+        assert!(matches!(
+            NativeView::<f64>::parse(&bytes[..bytes.len() - 1]),
+            Err(LinalgError::InvalidFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_header_errors() {
+        let m = Matrix::new([[1u32, 2]]);
+        let mut bytes = encoded(&m, Checksum::None);
+        assert_eq!(
+            NativeView::<i32>::parse(&bytes).unwrap_err(),
+            LinalgError::TypeMismatch {
+                expected: "i32".to_string(),
+                found: "u32".to_string()
+            }
+        );
+
+        bytes[8] = 2;
+        assert_eq!(
+            Matrix::<u32>::read_native(&bytes[..]).unwrap_err(),
+            LinalgError::UnsupportedVersion {
+                found: 2,
+                supported: 1
+            }
+        );
+
+        bytes[0] = b'X';
+        assert!(matches!(
+            NativeView::<u32>::parse(&bytes),
+            Err(LinalgError::InvalidFormat { .. })
+        ));
+
+        // The largest payload the header allows, which leaves no room for header and trailer
+        let header = Header {
+            tag: tag::<c64>(),
+            order: StorageOrder::RowMajor,
+            checksum: Checksum::Crc32,
+            rows: usize::MAX / 16,
+            cols: 1,
+        };
+        assert!(matches!(
+            NativeView::<c64>::parse(&header.encode()),
+            Err(LinalgError::InvalidFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_streaming_column_major() {
+        let mut writer = NativeWriter::new(
+            Vec::new(),
+            2,
+            3,
+            StorageOrder::ColumnMajor,
+            Checksum::XxHash64,
+        )
+        .unwrap();
+        for column in [[1i64, 4], [2, 5], [3, 6]] {
+            writer.write(&column).unwrap();
+        }
+        let bytes = writer.finish().unwrap();
+
+        let expected = Matrix::new([[1i64, 2, 3], [4, 5, 6]]);
+        assert_eq!(Matrix::<i64>::read_native(&bytes[..]).unwrap(), expected);
+        let view = NativeView::<i64>::parse(&bytes).unwrap();
+        assert_eq!(view.order(), StorageOrder::ColumnMajor);
+        assert!(view.view().is_none());
+        assert_eq!(view.to_matrix(), expected);
+    }
+}
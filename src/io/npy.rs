@@ -0,0 +1,652 @@
+use super::{
+    cast_slice, from_column_major, invalid, read_elements, write_elements, Element, ElementKind,
+};
+use crate::{
+    error::LinalgError,
+    matrix::{mat::Matrix, mat_view::MatrixView},
+};
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use memmap2::Mmap;
+#[cfg(feature = "npz")]
+use std::io::Seek;
+use std::{
+    fs::File,
+    io::{Read, Write},
+    marker::PhantomData,
+    path::Path,
+};
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+const HEADER_ALIGN: usize = 64; // numpy pads the header so the data starts 64-byte aligned
+
+impl<T: Element> Matrix<T> {
+    // Reads a .npy array. Shape (n,) becomes an n x 1 column vector, a scalar a 1 x 1 matrix, and
+    // Fortran-ordered data is rearranged into row-major storage. The dtype must match `T`, but
+    // either byte order is accepted.
+    pub fn read_npy<R: Read>(mut reader: R) -> Result<Self, LinalgError> {
+        let (header, _) = read_header(&mut reader)?;
+        header.dtype.check::<T>()?;
+        let data = read_elements(
+            &mut reader,
+            header.rows * header.cols,
+            header.dtype.big_endian,
+        )?;
+        Ok(header.into_matrix(data))
+    }
+
+    // Writes a little-endian, C-ordered 2-D .npy array
+    pub fn write_npy<W: Write>(&self, mut writer: W) -> Result<(), LinalgError> {
+        write_header(&mut writer, Dtype::of::<T>(), self.rows, self.cols)?;
+        write_elements(&mut writer, self.data.iter())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Dtype {
+    big_endian: bool,
+    kind: ElementKind,
+    size: usize,
+}
+
+impl Dtype {
+    fn of<T: Element>() -> Self {
+        Dtype {
+            big_endian: false,
+            kind: T::KIND,
+            size: T::SIZE,
+        }
+    }
+
+    // Type strings such as '<f8', '>c16' or '=i4'
+    fn parse(descr: &str) -> Result<Self, LinalgError> {
+        let unsupported = || invalid(format!("unsupported dtype '{descr}'"));
+        let mut chars = descr.chars();
+        let big_endian = match chars.next() {
+            Some('<' | '|') => false,
+            Some('>') => true,
+            Some('=') => cfg!(target_endian = "big"),
+            _ => return Err(unsupported()),
+        };
+        let kind = match chars.next() {
+            Some('f') => ElementKind::Float,
+            Some('c') => ElementKind::Complex,
+            Some('i') => ElementKind::Int,
+            Some('u') => ElementKind::UInt,
+            _ => return Err(unsupported()),
+        };
+        let size = chars.as_str().parse().map_err(|_| unsupported())?;
+        Ok(Dtype {
+            big_endian,
+            kind,
+            size,
+        })
+    }
+
+    fn descr(&self) -> String {
+        let order = if self.big_endian { '>' } else { '<' };
+        let kind = match self.kind {
+            ElementKind::Float => 'f',
+            ElementKind::Complex => 'c',
+            ElementKind::Int => 'i',
+            ElementKind::UInt => 'u',
+        };
+        format!("{order}{kind}{}", self.size)
+    }
+
+    fn check<T: Element>(&self) -> Result<(), LinalgError> {
+        if self.kind == T::KIND && self.size == T::SIZE {
+            Ok(())
+        } else {
+            Err(LinalgError::TypeMismatch {
+                expected: Dtype::of::<T>().descr(),
+                found: self.descr(),
+            })
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    dtype: Dtype,
+    fortran_order: bool,
+    rows: usize,
+    cols: usize,
+}
+
+impl Header {
+    fn into_matrix<T: Element>(self, data: AVec<T>) -> Matrix<T> {
+        if self.fortran_order && self.rows > 1 && self.cols > 1 {
+            from_column_major(self.rows, self.cols, &data)
+        } else {
+            Matrix {
+                rows: self.rows,
+                cols: self.cols,
+                data,
+            }
+        }
+    }
+}
+
+// Parses the preamble and header dictionary, returning the header and the offset of the data
+fn read_header<R: Read>(reader: &mut R) -> Result<(Header, usize), LinalgError> {
+    let mut preamble = [0u8; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != MAGIC {
+        return Err(invalid("missing NPY magic string"));
+    }
+    // Version 1.0 stores the header length in two bytes, 2.0 and 3.0 in four
+    let len_size = match preamble[6] {
+        1 => 2,
+        2 | 3 => 4,
+        major => {
+            return Err(invalid(format!(
+                "unsupported NPY version {major}.{}",
+                preamble[7]
+            )))
+        }
+    };
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len[..len_size])?;
+    let header_len = u32::from_le_bytes(len) as usize;
+
+    let mut text = vec![0u8; header_len];
+    reader.read_exact(&mut text)?;
+    let text = std::str::from_utf8(&text).map_err(|_| invalid("NPY header is not text"))?;
+    Ok((parse_header(text)?, preamble.len() + len_size + header_len))
+}
+
+fn write_header<W: Write>(
+    writer: &mut W,
+    dtype: Dtype,
+    rows: usize,
+    cols: usize,
+) -> Result<(), LinalgError> {
+    let dict = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': ({rows}, {cols}), }}",
+        dtype.descr()
+    );
+    let (version, len_size) = if dict.len() + HEADER_ALIGN < u16::MAX as usize {
+        (1, 2)
+    } else {
+        (2, 4)
+    };
+    // Spaces and a final newline fill the header up to the alignment
+    let unpadded = MAGIC.len() + 2 + len_size + dict.len() + 1;
+    let padding = unpadded.next_multiple_of(HEADER_ALIGN) - unpadded;
+    let header_len = (dict.len() + padding + 1) as u32;
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[version, 0])?;
+    writer.write_all(&header_len.to_le_bytes()[..len_size])?;
+    writer.write_all(dict.as_bytes())?;
+    writer.write_all(&vec![b' '; padding])?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+// The header is a Python dict literal, e.g.
+// {'descr': '<f8', 'fortran_order': False, 'shape': (3, 4), }
+fn parse_header(text: &str) -> Result<Header, LinalgError> {
+    let mut literal = Literal { rest: text };
+    let (mut dtype, mut fortran_order, mut shape) = (None, None, None);
+
+    literal.expect('{')?;
+    while !literal.eat('}') {
+        let key = literal.string()?;
+        literal.expect(':')?;
+        match (key, literal.value()?) {
+            ("descr", Value::Str(descr)) => dtype = Some(Dtype::parse(descr)?),
+            ("fortran_order", Value::Bool(b)) => fortran_order = Some(b),
+            ("shape", Value::Tuple(dims)) => shape = Some(dims),
+            ("descr" | "fortran_order" | "shape", _) => {
+                return Err(invalid(format!(
+                    "unexpected value for '{key}' in NPY header"
+                )))
+            }
+            _ => {}
+        }
+        if !literal.eat(',') {
+            literal.expect('}')?;
+            break;
+        }
+    }
+
+    let missing = |key| invalid(format!("NPY header has no '{key}'"));
+    let dtype = dtype.ok_or_else(|| missing("descr"))?;
+    let fortran_order = fortran_order.ok_or_else(|| missing("fortran_order"))?;
+    let (rows, cols) = match shape.ok_or_else(|| missing("shape"))?[..] {
+        [] => (1, 1),
+        [n] => (n, 1),
+        [rows, cols] => (rows, cols),
+        ref dims => {
+            return Err(invalid(format!(
+                "only 1 and 2 dimensional arrays are supported, got {} dimensions",
+                dims.len()
+            )))
+        }
+    };
+    if rows
+        .checked_mul(cols)
+        .and_then(|n| n.checked_mul(dtype.size))
+        .is_none()
+    {
+        return Err(invalid("NPY shape is too large"));
+    }
+
+    Ok(Header {
+        dtype,
+        fortran_order,
+        rows,
+        cols,
+    })
+}
+
+enum Value<'a> {
+    Str(&'a str),
+    Bool(bool),
+    Tuple(Vec<usize>),
+}
+
+// Just enough of a Python literal parser for NPY headers: strings, booleans and tuples of ints
+struct Literal<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Literal<'a> {
+    fn eat(&mut self, c: char) -> bool {
+        self.rest = self.rest.trim_start();
+        match self.rest.strip_prefix(c) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), LinalgError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(invalid(format!("expected '{c}' in NPY header")))
+        }
+    }
+
+    fn string(&mut self) -> Result<&'a str, LinalgError> {
+        self.rest = self.rest.trim_start();
+        let quote = match self.rest.chars().next() {
+            Some(q @ ('\'' | '"')) => q,
+            _ => return Err(invalid("expected a string in NPY header")),
+        };
+        let body = &self.rest[1..];
+        let end = body
+            .find(quote)
+            .ok_or_else(|| invalid("unterminated string in NPY header"))?;
+        self.rest = &body[end + 1..];
+        Ok(&body[..end])
+    }
+
+    fn value(&mut self) -> Result<Value<'a>, LinalgError> {
+        self.rest = self.rest.trim_start();
+        for (word, b) in [("True", true), ("False", false)] {
+            if let Some(rest) = self.rest.strip_prefix(word) {
+                self.rest = rest;
+                return Ok(Value::Bool(b));
+            }
+        }
+        if !self.eat('(') {
+            return self.string().map(Value::Str);
+        }
+
+        let mut dims = Vec::new();
+        while !self.eat(')') {
+            self.rest = self.rest.trim_start();
+            let digits = self
+                .rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(self.rest.len());
+            let dim = self.rest[..digits]
+                .parse()
+                .map_err(|_| invalid("expected a dimension in NPY shape"))?;
+            dims.push(dim);
+            // Python 2 wrote long integers with an L suffix
+            self.rest = self.rest[digits..].trim_start_matches('L');
+            if !self.eat(',') {
+                self.expect(')')?;
+                break;
+            }
+        }
+        Ok(Value::Tuple(dims))
+    }
+}
+
+// A .npy file mapped into memory rather than read, for arrays too large to load. Pages are only
+// read from disk when touched.
+pub struct NpyMmap<T: Element> {
+    mmap: Mmap,
+    offset: usize,
+    header: Header,
+    marker: PhantomData<T>,
+}
+
+impl<T: Element> NpyMmap<T> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, LinalgError> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only. As with any file mapping, the file must not be truncated
+        // or modified by another process while it is mapped.
+        let mmap = unsafe { Mmap::map(&file)? };
+        let (header, offset) = read_header(&mut &mmap[..])?;
+        header.dtype.check::<T>()?;
+        if mmap.len() - offset < header.rows * header.cols * T::SIZE {
+            return Err(invalid("NPY file is shorter than its header says"));
+        }
+        Ok(NpyMmap {
+            mmap,
+            offset,
+            header,
+            marker: PhantomData,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.header.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.header.cols
+    }
+
+    fn bytes(&self) -> &[u8] {
+        let len = self.header.rows * self.header.cols * T::SIZE;
+        &self.mmap[self.offset..self.offset + len]
+    }
+
+    // Borrows the mapped elements without copying. `None` when the file is Fortran-ordered, in
+    // the other byte order, or its data is misaligned, use `to_matrix` for those.
+    pub fn view(&self) -> Option<MatrixView<'_, T>> {
+        let Header {
+            dtype,
+            fortran_order,
+            rows,
+            cols,
+        } = self.header;
+        if (fortran_order && rows > 1 && cols > 1) || dtype.big_endian {
+            return None;
+        }
+        cast_slice(self.bytes()).map(|data| MatrixView::new(rows, cols, data))
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> {
+        let big_endian = self.header.dtype.big_endian;
+        let data = AVec::from_iter(
+            CACHELINE_ALIGN,
+            self.bytes()
+                .chunks_exact(T::SIZE)
+                .map(|bytes| T::from_bytes(bytes, big_endian)),
+        );
+        self.header.into_matrix(data)
+    }
+}
+
+// Reads the arrays of an .npz archive, as written by `numpy.savez` and `numpy.savez_compressed`
+#[cfg(feature = "npz")]
+pub struct NpzReader<R: Read + Seek> {
+    archive: zip::ZipArchive<R>,
+}
+
+#[cfg(feature = "npz")]
+impl<R: Read + Seek> NpzReader<R> {
+    pub fn new(reader: R) -> Result<Self, LinalgError> {
+        let archive = zip::ZipArchive::new(reader).map_err(zip_error)?;
+        Ok(NpzReader { archive })
+    }
+
+    // Array names, without the .npy suffix of the archive entries
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.archive
+            .file_names()
+            .map(|name| name.strip_suffix(".npy").unwrap_or(name))
+    }
+
+    pub fn read<T: Element>(&mut self, name: &str) -> Result<Matrix<T>, LinalgError> {
+        let entry = self
+            .archive
+            .by_name(&format!("{name}.npy"))
+            .map_err(|err| match err {
+                zip::result::ZipError::FileNotFound => {
+                    invalid(format!("no array named '{name}' in the archive"))
+                }
+                err => zip_error(err),
+            })?;
+        Matrix::read_npy(entry)
+    }
+}
+
+#[cfg(feature = "npz")]
+pub struct NpzWriter<W: Write + Seek> {
+    zip: zip::ZipWriter<W>,
+    options: zip::write::SimpleFileOptions,
+}
+
+#[cfg(feature = "npz")]
+impl<W: Write + Seek> NpzWriter<W> {
+    // Entries are stored uncompressed, like `numpy.savez`
+    pub fn new(writer: W) -> Self {
+        NpzWriter {
+            zip: zip::ZipWriter::new(writer),
+            options: zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Stored),
+        }
+    }
+
+    // Entries are deflated, like `numpy.savez_compressed`
+    pub fn compressed(writer: W) -> Self {
+        NpzWriter {
+            zip: zip::ZipWriter::new(writer),
+            options: zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated),
+        }
+    }
+
+    pub fn add<T: Element>(&mut self, name: &str, matrix: &Matrix<T>) -> Result<(), LinalgError> {
+        // Entries of 4 GiB or more need the zip64 extensions
+        let large = matrix.data.len() * T::SIZE >= u32::MAX as usize;
+        self.zip
+            .start_file(format!("{name}.npy"), self.options.large_file(large))
+            .map_err(zip_error)?;
+        matrix.write_npy(&mut self.zip)
+    }
+
+    pub fn finish(self) -> Result<W, LinalgError> {
+        self.zip.finish().map_err(zip_error)
+    }
+}
+
+#[cfg(feature = "npz")]
+fn zip_error(err: zip::result::ZipError) -> LinalgError {
+    match err {
+        zip::result::ZipError::Io(err) => err.into(),
+        err => invalid(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+    use std::io::ErrorKind;
+
+    fn npy_bytes(dict: &str, payload: &[u8]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend([1, 0]);
+        bytes.extend((dict.len() as u16 + 1).to_le_bytes());
+        bytes.extend(dict.as_bytes());
+        bytes.push(b'\n');
+        bytes.extend(payload);
+        bytes
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("linalg-{}-{name}.npy", std::process::id()))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let mut bytes = Vec::new();
+        m.write_npy(&mut bytes).unwrap();
+
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_len) % HEADER_ALIGN, 0);
+        assert!(
+            bytes[10..].starts_with(b"{'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }")
+        );
+        assert_eq!(bytes.len(), 10 + header_len + 6 * 8);
+
+        assert_eq!(Matrix::<f64>::read_npy(&bytes[..]).unwrap(), m);
+    }
+
+    #[test]
+    fn test_round_trip_other_dtypes() {
+        let z = Matrix::new([[c64::new(1.0, -2.0), c64::new(0.5, 0.0)]]);
+        let mut bytes = Vec::new();
+        z.write_npy(&mut bytes).unwrap();
+        assert_eq!(Matrix::<c64>::read_npy(&bytes[..]).unwrap(), z);
+
+        let u = Matrix::new([[u64::MAX], [7]]);
+        let mut bytes = Vec::new();
+        u.write_npy(&mut bytes).unwrap();
+        assert_eq!(Matrix::<u64>::read_npy(&bytes[..]).unwrap(), u);
+    }
+
+    #[test]
+    fn test_big_endian_fortran_order() {
+        // np.arange(6, dtype='>i4').reshape(2, 3).copy(order='F')
+        let payload: Vec<u8> = [0i32, 3, 1, 4, 2, 5]
+            .iter()
+            .flat_map(|x| x.to_be_bytes())
+            .collect();
+        let bytes = npy_bytes(
+            "{'descr': '>i4', 'fortran_order': True, 'shape': (2, 3), }",
+            &payload,
+        );
+        assert_eq!(
+            Matrix::<i32>::read_npy(&bytes[..]).unwrap(),
+            Matrix::new([[0, 1, 2], [3, 4, 5]])
+        );
+    }
+
+    #[test]
+    fn test_one_dimensional_and_scalar() {
+        let payload: Vec<u8> = [1.5f32, 2.5].iter().flat_map(|x| x.to_le_bytes()).collect();
+        let bytes = npy_bytes(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (2,), }",
+            &payload,
+        );
+        assert_eq!(
+            Matrix::<f32>::read_npy(&bytes[..]).unwrap(),
+            Matrix::new([[1.5], [2.5]])
+        );
+
+        let bytes = npy_bytes(
+            "{\"shape\": (), \"fortran_order\": False, \"descr\": \"<f4\"}",
+            &payload[..4],
+        );
+        assert_eq!(
+            Matrix::<f32>::read_npy(&bytes[..]).unwrap(),
+            Matrix::new([[1.5]])
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut bytes = Vec::new();
+        Matrix::new([[1.0f64]]).write_npy(&mut bytes).unwrap();
+        assert_eq!(
+            Matrix::<f32>::read_npy(&bytes[..]).unwrap_err(),
+            LinalgError::TypeMismatch {
+                expected: "<f4".to_string(),
+                found: "<f8".to_string()
+            }
+        );
+
+        match Matrix::<f64>::read_npy(&bytes[..bytes.len() - 1]).unwrap_err() {
+            LinalgError::Io { kind, .. } => assert_eq!(kind, ErrorKind::UnexpectedEof),
+            err => panic!("unexpected error {err:?}"),
+        }
+
+        assert!(matches!(
+            Matrix::<f64>::read_npy(&b"not an npy file"[..]),
+            Err(LinalgError::InvalidFormat { .. })
+        ));
+
+        let bytes = npy_bytes(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (2, 2, 2), }",
+            &[],
+        );
+        assert!(matches!(
+            Matrix::<f64>::read_npy(&bytes[..]),
+            Err(LinalgError::InvalidFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_mmap() {
+        let m = Matrix::from_fn(5, 4, |i, j| (10 * i + j) as f64);
+        let path = temp_path("mmap");
+        m.write_npy(File::create(&path).unwrap()).unwrap();
+
+        let mapped = NpyMmap::<f64>::open(&path).unwrap();
+        assert_eq!((mapped.rows(), mapped.cols()), (5, 4));
+        assert_eq!(mapped.view().unwrap(), m.view(0..5, 0..4));
+        assert_eq!(mapped.to_matrix(), m);
+        assert!(NpyMmap::<f32>::open(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        let payload: Vec<u8> = [1.0f64, 3.0, 2.0, 4.0]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect();
+        let path = temp_path("mmap-fortran");
+        std::fs::write(
+            &path,
+            npy_bytes(
+                "{'descr': '<f8', 'fortran_order': True, 'shape': (2, 2), }",
+                &payload,
+            ),
+        )
+        .unwrap();
+        let mapped = NpyMmap::<f64>::open(&path).unwrap();
+        assert!(mapped.view().is_none());
+        assert_eq!(mapped.to_matrix(), Matrix::new([[1.0, 2.0], [3.0, 4.0]]));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "npz")]
+    #[test]
+    fn test_npz_round_trip() {
+        use std::io::Cursor;
+
+        let a = Matrix::new([[1.0, 2.0], [3.0, 4.0]]);
+        let b = Matrix::new([[c64::new(0.0, 1.0)]]);
+        for compressed in [false, true] {
+            let cursor = Cursor::new(Vec::new());
+            let mut writer = if compressed {
+                NpzWriter::compressed(cursor)
+            } else {
+                NpzWriter::new(cursor)
+            };
+            writer.add("a", &a).unwrap();
+            writer.add("b", &b).unwrap();
+            let bytes = writer.finish().unwrap().into_inner();
+
+            let mut reader = NpzReader::new(Cursor::new(bytes)).unwrap();
+            let mut names: Vec<&str> = reader.names().collect();
+            names.sort();
+            assert_eq!(names, ["a", "b"]);
+            assert_eq!(reader.read::<f64>("a").unwrap(), a);
+            assert_eq!(reader.read::<c64>("b").unwrap(), b);
+            assert!(reader.read::<f64>("c").is_err());
+        }
+    }
+}
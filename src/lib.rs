@@ -1,4 +1,9 @@
+pub mod capi;
+pub mod error;
+pub mod io;
 pub mod matrix;
+#[cfg(feature = "python")]
+mod python;
 pub mod num {
     pub use num::complex::{Complex32 as c32, Complex64 as c64};
 }
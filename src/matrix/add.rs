@@ -1,4 +1,5 @@
 use super::{mat::Matrix, simd::SimdOps};
+use aligned_vec::{avec, AVec, CACHELINE_ALIGN};
 use rayon::prelude::*;
 use std::ops::{Add, AddAssign, Mul, Sub};
 
@@ -37,14 +38,15 @@ fn add_scalar<T>(m1: &Matrix<T>, m2: &Matrix<T>) -> Matrix<T>
 where
     T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
 {
-    let data: Vec<T> = m1
-        .data
-        .iter()
-        .zip(&m2.data)
-        .map(|(a, b)| a.clone() + b.clone())
-        .collect();
-
-    Matrix::from_vec(m1.rows, m1.cols, data)
+    let data = AVec::from_iter(
+        CACHELINE_ALIGN,
+        m1.data
+            .iter()
+            .zip(m2.data.iter())
+            .map(|(a, b)| a.clone() + b.clone()),
+    );
+
+    Matrix::from_aligned(m1.rows, m1.cols, data)
 }
 
 #[cfg(target_arch = "x86_64")]
@@ -61,7 +63,7 @@ where
 {
     let total_elements = m1.rows * m1.cols;
 
-    let mut result = vec![T::default(); total_elements];
+    let mut result = avec![T::default(); total_elements];
 
     result
         .par_chunks_mut(T::LANE_SIZE * 128)
@@ -69,6 +71,10 @@ where
         .zip(m2.data.par_chunks(T::LANE_SIZE * 128))
         .for_each(|((r, a), b)| {
             let chunks = r.len() / T::LANE_SIZE;
+            // Matrix storage is cache-line aligned and chunk sizes are a multiple of the
+            // lane width, so this only fails for storage not allocated by `Matrix`
+            let aligned =
+                T::is_aligned(a.as_ptr()) && T::is_aligned(b.as_ptr()) && T::is_aligned(r.as_ptr());
 
             for i in 0..chunks {
                 let offset = i * T::LANE_SIZE;
@@ -86,10 +92,17 @@ where
                 }
 
                 // Regular SIMD loading, addition, and storing
-                let m1_vec = T::load(a[offset..].as_ptr());
-                let m2_vec = T::load(b[offset..].as_ptr());
-                let sum = <T as SimdOps>::add(m1_vec, m2_vec);
-                T::store(r[offset..].as_mut_ptr(), sum);
+                if aligned {
+                    let m1_vec = T::load_aligned(a[offset..].as_ptr());
+                    let m2_vec = T::load_aligned(b[offset..].as_ptr());
+                    let sum = <T as SimdOps>::add(m1_vec, m2_vec);
+                    T::store_aligned(r[offset..].as_mut_ptr(), sum);
+                } else {
+                    let m1_vec = T::load(a[offset..].as_ptr());
+                    let m2_vec = T::load(b[offset..].as_ptr());
+                    let sum = <T as SimdOps>::add(m1_vec, m2_vec);
+                    T::store(r[offset..].as_mut_ptr(), sum);
+                }
             }
 
             let remaining_start = chunks * T::LANE_SIZE;
@@ -98,7 +111,7 @@ where
             }
         });
 
-    Matrix::from_vec(m1.rows, m1.cols, result)
+    Matrix::from_aligned(m1.rows, m1.cols, result)
 }
 
 fn add_assign_matrix_impl<T>(m1: &mut Matrix<T>, m2: &Matrix<T>)
@@ -133,7 +146,7 @@ where
 {
     m1.data
         .iter_mut()
-        .zip(&m2.data)
+        .zip(m2.data.iter())
         .for_each(|(a, b)| *a += b.clone());
 }
 
@@ -253,6 +266,19 @@ mod tests {
         assert_eq!(m1[(2, 2)], 10);
     }
 
+    #[test]
+    fn test_add_large_matrix_f32() {
+        let size = 1024;
+        let data1: Vec<f32> = (0..size * size).map(|i| i as f32).collect();
+        let data2 = vec![0.5f32; size * size];
+        let m1 = Matrix::from_vec(size, size, data1);
+        let m2 = Matrix::from_vec(size, size, data2);
+        let m3 = &m1 + &m2;
+        assert_eq!(m3[(0, 0)], 0.5);
+        assert_eq!(m3[(0, 7)], 7.5);
+        assert_eq!(m3[(3, 9)], (3 * size + 9) as f32 + 0.5);
+    }
+
     #[test]
     fn test_add_large_matrix_c32() {
         let size = 1024;
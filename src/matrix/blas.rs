@@ -0,0 +1,364 @@
+use super::{cholesky::cholesky_factor, lu::lu_in_place, mat::Matrix};
+use crate::error::LinalgError;
+use aligned_vec::avec;
+use num::complex::{self, Complex32 as c32, Complex64 as c64};
+use std::any::Any;
+use std::ffi::{c_char, c_int, c_void};
+use std::ops::{Add, Mul, Sub};
+
+// Kernels from an external BLAS and LAPACK, called through the standard CBLAS and LAPACKE
+// symbols. OpenBLAS is linked by default; build.rs explains how to pick another library. Only
+// f32, f64, c32 and c64 matrices go to the library, any other element type, and dimensions beyond
+// what a 32-bit LAPACK integer holds, fall back to the native kernels. `multiply_native`,
+// `lu_native` and `cholesky_native` keep the native kernels reachable with the feature on, to
+// check the backend against.
+
+const CBLAS_ROW_MAJOR: c_int = 101;
+const CBLAS_NO_TRANS: c_int = 111;
+const LAPACK_ROW_MAJOR: c_int = 101;
+
+extern "C" {
+    fn cblas_sgemm(
+        layout: c_int,
+        trans_a: c_int,
+        trans_b: c_int,
+        m: c_int,
+        n: c_int,
+        k: c_int,
+        alpha: f32,
+        a: *const f32,
+        lda: c_int,
+        b: *const f32,
+        ldb: c_int,
+        beta: f32,
+        c: *mut f32,
+        ldc: c_int,
+    );
+    fn cblas_dgemm(
+        layout: c_int,
+        trans_a: c_int,
+        trans_b: c_int,
+        m: c_int,
+        n: c_int,
+        k: c_int,
+        alpha: f64,
+        a: *const f64,
+        lda: c_int,
+        b: *const f64,
+        ldb: c_int,
+        beta: f64,
+        c: *mut f64,
+        ldc: c_int,
+    );
+    // The complex variants take alpha and beta by pointer
+    fn cblas_cgemm(
+        layout: c_int,
+        trans_a: c_int,
+        trans_b: c_int,
+        m: c_int,
+        n: c_int,
+        k: c_int,
+        alpha: *const c_void,
+        a: *const c_void,
+        lda: c_int,
+        b: *const c_void,
+        ldb: c_int,
+        beta: *const c_void,
+        c: *mut c_void,
+        ldc: c_int,
+    );
+    fn cblas_zgemm(
+        layout: c_int,
+        trans_a: c_int,
+        trans_b: c_int,
+        m: c_int,
+        n: c_int,
+        k: c_int,
+        alpha: *const c_void,
+        a: *const c_void,
+        lda: c_int,
+        b: *const c_void,
+        ldb: c_int,
+        beta: *const c_void,
+        c: *mut c_void,
+        ldc: c_int,
+    );
+
+    fn LAPACKE_sgetrf(
+        layout: c_int,
+        m: c_int,
+        n: c_int,
+        a: *mut f32,
+        lda: c_int,
+        ipiv: *mut c_int,
+    ) -> c_int;
+    fn LAPACKE_dgetrf(
+        layout: c_int,
+        m: c_int,
+        n: c_int,
+        a: *mut f64,
+        lda: c_int,
+        ipiv: *mut c_int,
+    ) -> c_int;
+    fn LAPACKE_cgetrf(
+        layout: c_int,
+        m: c_int,
+        n: c_int,
+        a: *mut c32,
+        lda: c_int,
+        ipiv: *mut c_int,
+    ) -> c_int;
+    fn LAPACKE_zgetrf(
+        layout: c_int,
+        m: c_int,
+        n: c_int,
+        a: *mut c64,
+        lda: c_int,
+        ipiv: *mut c_int,
+    ) -> c_int;
+
+    fn LAPACKE_spotrf(layout: c_int, uplo: c_char, n: c_int, a: *mut f32, lda: c_int) -> c_int;
+    fn LAPACKE_dpotrf(layout: c_int, uplo: c_char, n: c_int, a: *mut f64, lda: c_int) -> c_int;
+    fn LAPACKE_cpotrf(layout: c_int, uplo: c_char, n: c_int, a: *mut c32, lda: c_int) -> c_int;
+    fn LAPACKE_zpotrf(layout: c_int, uplo: c_char, n: c_int, a: *mut c64, lda: c_int) -> c_int;
+}
+
+// Element types with BLAS and LAPACK routines. `Complex` is `repr(C)` with the real part first,
+// the layout of the C complex types.
+trait Blas:
+    complex::ComplexFloat
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + 'static
+{
+    // c = a * b for row-major m x k a and k x n b
+    unsafe fn gemm(m: c_int, n: c_int, k: c_int, a: *const Self, b: *const Self, c: *mut Self);
+    // Returns LAPACK's info
+    unsafe fn getrf(n: c_int, a: *mut Self, ipiv: *mut c_int) -> c_int;
+    unsafe fn potrf(n: c_int, a: *mut Self) -> c_int;
+}
+
+macro_rules! impl_blas {
+    (real: $t:ty, $gemm:ident, $getrf:ident, $potrf:ident) => {
+        impl Blas for $t {
+            unsafe fn gemm(m: c_int, n: c_int, k: c_int, a: *const $t, b: *const $t, c: *mut $t) {
+                $gemm(
+                    CBLAS_ROW_MAJOR,
+                    CBLAS_NO_TRANS,
+                    CBLAS_NO_TRANS,
+                    m,
+                    n,
+                    k,
+                    1.0,
+                    a,
+                    k,
+                    b,
+                    n,
+                    0.0,
+                    c,
+                    n,
+                );
+            }
+
+            unsafe fn getrf(n: c_int, a: *mut $t, ipiv: *mut c_int) -> c_int {
+                $getrf(LAPACK_ROW_MAJOR, n, n, a, n, ipiv)
+            }
+
+            unsafe fn potrf(n: c_int, a: *mut $t) -> c_int {
+                $potrf(LAPACK_ROW_MAJOR, b'L' as c_char, n, a, n)
+            }
+        }
+    };
+    (complex: $t:ty, $gemm:ident, $getrf:ident, $potrf:ident) => {
+        impl Blas for $t {
+            unsafe fn gemm(m: c_int, n: c_int, k: c_int, a: *const $t, b: *const $t, c: *mut $t) {
+                let (one, zero) = (<$t>::new(1.0, 0.0), <$t>::new(0.0, 0.0));
+                $gemm(
+                    CBLAS_ROW_MAJOR,
+                    CBLAS_NO_TRANS,
+                    CBLAS_NO_TRANS,
+                    m,
+                    n,
+                    k,
+                    (&one as *const $t).cast(),
+                    a.cast(),
+                    k,
+                    b.cast(),
+                    n,
+                    (&zero as *const $t).cast(),
+                    c.cast(),
+                    n,
+                );
+            }
+
+            unsafe fn getrf(n: c_int, a: *mut $t, ipiv: *mut c_int) -> c_int {
+                $getrf(LAPACK_ROW_MAJOR, n, n, a, n, ipiv)
+            }
+
+            unsafe fn potrf(n: c_int, a: *mut $t) -> c_int {
+                $potrf(LAPACK_ROW_MAJOR, b'L' as c_char, n, a, n)
+            }
+        }
+    };
+}
+
+impl_blas!(real: f32, cblas_sgemm, LAPACKE_sgetrf, LAPACKE_spotrf);
+impl_blas!(real: f64, cblas_dgemm, LAPACKE_dgetrf, LAPACKE_dpotrf);
+impl_blas!(complex: c32, cblas_cgemm, LAPACKE_cgetrf, LAPACKE_cpotrf);
+impl_blas!(complex: c64, cblas_zgemm, LAPACKE_zgetrf, LAPACKE_zpotrf);
+
+// `None` for an empty dimension, which the leading dimension rules make awkward and there is
+// nothing to compute for anyway, or one too large for a LAPACK integer
+fn dim(n: usize) -> Option<c_int> {
+    c_int::try_from(n).ok().filter(|&n| n > 0)
+}
+
+// Moves a value between two names for the same type, which the caller has established with a
+// successful downcast
+fn cast<U: 'static, T: 'static>(value: U) -> T {
+    let mut slot = Some(value);
+    (&mut slot as &mut dyn Any)
+        .downcast_mut::<Option<T>>()
+        .and_then(Option::take)
+        .unwrap()
+}
+
+// `None` when the element type has no BLAS routine, the caller then multiplies natively
+pub(crate) fn multiply<T>(a: &Matrix<T>, b: &Matrix<T>) -> Option<Matrix<T>>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + 'static,
+{
+    let (a, b): (&dyn Any, &dyn Any) = (a, b);
+    gemm::<f32>(a, b)
+        .map(cast)
+        .or_else(|| gemm::<f64>(a, b).map(cast))
+        .or_else(|| gemm::<c32>(a, b).map(cast))
+        .or_else(|| gemm::<c64>(a, b).map(cast))
+}
+
+fn gemm<U: Blas>(a: &dyn Any, b: &dyn Any) -> Option<Matrix<U>> {
+    let (a, b) = (
+        a.downcast_ref::<Matrix<U>>()?,
+        b.downcast_ref::<Matrix<U>>()?,
+    );
+    let (m, k, n) = (dim(a.rows)?, dim(a.cols)?, dim(b.cols)?);
+    let mut c = Matrix {
+        rows: a.rows,
+        cols: b.cols,
+        data: avec![U::default(); a.rows * b.cols],
+    };
+    // SAFETY: the three buffers hold exactly m x k, k x n and m x n elements, row-major
+    unsafe {
+        U::gemm(
+            m,
+            n,
+            k,
+            a.data.as_ptr(),
+            b.data.as_ptr(),
+            c.data.as_mut_ptr(),
+        )
+    };
+    Some(c)
+}
+
+// Same contract as `lu_in_place`
+pub(crate) fn getrf<T>(m: &mut Matrix<T>) -> Result<Vec<usize>, LinalgError>
+where
+    T: complex::ComplexFloat + 'static,
+{
+    let any: &mut dyn Any = &mut *m;
+    let swaps = getrf_typed::<f32>(any)
+        .or_else(|| getrf_typed::<f64>(any))
+        .or_else(|| getrf_typed::<c32>(any))
+        .or_else(|| getrf_typed::<c64>(any));
+    match swaps {
+        Some(swaps) => swaps,
+        None => lu_in_place(m),
+    }
+}
+
+fn getrf_typed<U: Blas>(m: &mut dyn Any) -> Option<Result<Vec<usize>, LinalgError>> {
+    let m = m.downcast_mut::<Matrix<U>>()?;
+    let n = dim(m.rows)?;
+    let mut ipiv: Vec<c_int> = vec![0; m.rows];
+    // SAFETY: `m` is n x n and `ipiv` has room for its n pivots
+    let info = unsafe { U::getrf(n, m.data.as_mut_ptr(), ipiv.as_mut_ptr()) };
+    assert!(info >= 0, "LAPACK getrf rejected argument {}", -info);
+    if info > 0 {
+        // LAPACK finishes the factorization regardless, the native kernel stops at the first
+        // zero pivot, and reports it
+        return Some(Err(LinalgError::Singular {
+            pivot: info as usize - 1,
+        }));
+    }
+    // LAPACK numbers rows from 1
+    Some(Ok(ipiv.into_iter().map(|p| p as usize - 1).collect()))
+}
+
+// Same contract as `cholesky_factor`
+pub(crate) fn potrf<T>(a: &Matrix<T>) -> Result<Matrix<T>, LinalgError>
+where
+    T: complex::ComplexFloat + 'static,
+{
+    let any: &dyn Any = a;
+    let l = potrf_typed::<f32>(any)
+        .map(|l| l.map(cast))
+        .or_else(|| potrf_typed::<f64>(any).map(|l| l.map(cast)))
+        .or_else(|| potrf_typed::<c32>(any).map(|l| l.map(cast)))
+        .or_else(|| potrf_typed::<c64>(any).map(|l| l.map(cast)));
+    match l {
+        Some(l) => l,
+        None => cholesky_factor(a),
+    }
+}
+
+fn potrf_typed<U: Blas>(a: &dyn Any) -> Option<Result<Matrix<U>, LinalgError>> {
+    let a = a.downcast_ref::<Matrix<U>>()?;
+    let n = dim(a.rows)?;
+    let mut l = a.clone();
+    // SAFETY: `l` is n x n
+    let info = unsafe { U::potrf(n, l.data.as_mut_ptr()) };
+    assert!(info >= 0, "LAPACK potrf rejected argument {}", -info);
+    if info > 0 {
+        return Some(Err(LinalgError::NotPositiveDefinite {
+            order: info as usize,
+        }));
+    }
+    // potrf leaves the strict upper triangle as it found it
+    for (k, x) in l.data.iter_mut().enumerate() {
+        if k % a.rows > k / a.rows {
+            *x = U::zero();
+        }
+    }
+    Some(Ok(l))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::mul::multiply_native;
+
+    #[test]
+    fn test_other_types_fall_back() {
+        let a = Matrix::new([[1, 2], [3, 4]]);
+        assert!(multiply(&a, &a).is_none());
+        assert!(multiply(&Matrix::<f64>::zeros(0, 3), &Matrix::zeros(3, 2)).is_none());
+        assert_eq!(
+            multiply_native(&Matrix::<f64>::zeros(0, 3), &Matrix::zeros(3, 2)).rows,
+            0
+        );
+    }
+
+    #[test]
+    fn test_gemm_dispatch() {
+        let a = Matrix::new([[1.0f32, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+        let b = Matrix::new([[1.0f32, 0.0, -1.0], [2.0, 1.0, 0.5]]);
+        assert_eq!(multiply(&a, &b).unwrap(), multiply_native(&a, &b));
+
+        let z = Matrix::new([[c64::new(1.0, 1.0), c64::new(0.0, -2.0)]]);
+        let w = Matrix::new([[c64::new(2.0, 0.0)], [c64::new(1.0, 1.0)]]);
+        assert_eq!(multiply(&z, &w).unwrap(), multiply_native(&z, &w));
+    }
+}
@@ -0,0 +1,310 @@
+use super::{
+    mat::{Axis, Matrix},
+    mat_view::MatrixView,
+};
+use crate::error::LinalgError;
+use num_traits::{One, Zero};
+use std::ops::{Add, Mul, Range, Sub};
+
+// One entry of a block matrix. `Zeros` and `Identity` take their size from the other blocks in
+// the same block row and block column.
+#[derive(Debug, Clone)]
+pub enum Block<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    Matrix(&'a Matrix<T>),
+    View(MatrixView<'a, T>),
+    Zeros,
+    Identity,
+}
+
+impl<'a, T> Block<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn shape(&self) -> Option<(usize, usize)> {
+        match self {
+            Block::Matrix(m) => Some((m.rows, m.cols)),
+            Block::View(v) => Some((v.rows, v.cols)),
+            Block::Zeros | Block::Identity => None,
+        }
+    }
+}
+
+impl<'a, T> From<&'a Matrix<T>> for Block<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(m: &'a Matrix<T>) -> Self {
+        Block::Matrix(m)
+    }
+}
+
+impl<'a, T> From<MatrixView<'a, T>> for Block<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(v: MatrixView<'a, T>) -> Self {
+        Block::View(v)
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default + Zero + One,
+{
+    // Assembles `blocks[r][c]` into one matrix, written straight into a single allocation
+    pub fn from_blocks(blocks: &[&[Block<'_, T>]]) -> Result<Self, LinalgError> {
+        let block_cols = blocks.first().map_or(0, |row| row.len());
+        if let Some(index) = blocks.iter().position(|row| row.len() != block_cols) {
+            return Err(LinalgError::RaggedInput {
+                index,
+                expected: block_cols,
+                found: blocks[index].len(),
+            });
+        }
+
+        let heights = (0..blocks.len())
+            .map(|r| {
+                blocks[r].iter().find_map(|b| b.shape().map(|s| s.0)).ok_or(
+                    LinalgError::UnknownBlockSize {
+                        axis: Axis::Rows,
+                        index: r,
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let widths = (0..block_cols)
+            .map(|c| {
+                blocks
+                    .iter()
+                    .find_map(|row| row[c].shape().map(|s| s.1))
+                    .ok_or(LinalgError::UnknownBlockSize {
+                        axis: Axis::Cols,
+                        index: c,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (r, row) in blocks.iter().enumerate() {
+            for (c, block) in row.iter().enumerate() {
+                match block.shape() {
+                    Some(found) if found != (heights[r], widths[c]) => {
+                        return Err(LinalgError::BlockShape {
+                            block: (r, c),
+                            expected: (heights[r], widths[c]),
+                            found,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let rows = heights.iter().sum();
+        let cols = widths.iter().sum();
+        let mut result = Self::zeros(rows, cols);
+
+        let mut top = 0;
+        for (row, &height) in blocks.iter().zip(&heights) {
+            let mut left = 0;
+            for (block, &width) in row.iter().zip(&widths) {
+                for i in 0..height {
+                    let start = (top + i) * cols + left;
+                    let dst = &mut result.data[start..start + width];
+                    match block {
+                        Block::Matrix(m) => {
+                            dst.clone_from_slice(&m.data[i * m.cols..(i + 1) * m.cols])
+                        }
+                        Block::View(v) => dst.clone_from_slice(v.row_slice(i)),
+                        Block::Identity if i < width => dst[i] = T::one(),
+                        Block::Identity | Block::Zeros => {}
+                    }
+                }
+                left += width;
+            }
+            top += height;
+        }
+
+        Ok(result)
+    }
+
+    // Side by side, all inputs must have the same number of rows
+    pub fn hstack(matrices: &[&Matrix<T>]) -> Result<Self, LinalgError> {
+        let row: Vec<Block<'_, T>> = matrices.iter().map(|&m| Block::Matrix(m)).collect();
+        if row.is_empty() {
+            return Ok(Self::zeros(0, 0));
+        }
+        Self::from_blocks(&[&row])
+    }
+
+    // One above the other, all inputs must have the same number of columns
+    pub fn vstack(matrices: &[&Matrix<T>]) -> Result<Self, LinalgError> {
+        let rows: Vec<[Block<'_, T>; 1]> = matrices.iter().map(|&m| [Block::Matrix(m)]).collect();
+        let rows: Vec<&[Block<'_, T>]> = rows.iter().map(|row| &row[..]).collect();
+        Self::from_blocks(&rows)
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+{
+    // Consecutive views of `sizes[k]` rows each, the inverse of `vstack`
+    pub fn split_rows(&self, sizes: &[usize]) -> impl Iterator<Item = MatrixView<'_, T>> {
+        assert_eq!(
+            sizes.iter().sum::<usize>(),
+            self.rows,
+            "Split sizes must add up to the number of rows"
+        );
+        let cols = self.cols;
+        split_ranges(sizes).map(move |rows| self.view(rows, 0..cols))
+    }
+
+    // Consecutive views of `sizes[k]` columns each, the inverse of `hstack`
+    pub fn split_cols(&self, sizes: &[usize]) -> impl Iterator<Item = MatrixView<'_, T>> {
+        assert_eq!(
+            sizes.iter().sum::<usize>(),
+            self.cols,
+            "Split sizes must add up to the number of columns"
+        );
+        let rows = self.rows;
+        split_ranges(sizes).map(move |cols| self.view(0..rows, cols))
+    }
+
+    // Tiles of block_rows x block_cols in row-major order, smaller along the bottom and right
+    // edges when the dimensions do not divide evenly
+    pub fn blocks(
+        &self,
+        block_rows: usize,
+        block_cols: usize,
+    ) -> impl Iterator<Item = MatrixView<'_, T>> {
+        assert!(
+            block_rows > 0 && block_cols > 0,
+            "Block dimensions must be nonzero"
+        );
+        let (rows, cols) = (self.rows, self.cols);
+        (0..rows).step_by(block_rows).flat_map(move |top| {
+            (0..cols).step_by(block_cols).map(move |left| {
+                self.view(
+                    top..(top + block_rows).min(rows),
+                    left..(left + block_cols).min(cols),
+                )
+            })
+        })
+    }
+}
+
+fn split_ranges(sizes: &[usize]) -> impl Iterator<Item = Range<usize>> {
+    let mut start = 0;
+    let ranges: Vec<Range<usize>> = sizes
+        .iter()
+        .map(|&size| {
+            start += size;
+            start - size..start
+        })
+        .collect();
+    ranges.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hstack_and_vstack() {
+        let a = Matrix::new([[1, 2], [3, 4]]);
+        let b = Matrix::new([[5], [6]]);
+        assert_eq!(
+            Matrix::hstack(&[&a, &b]).unwrap(),
+            Matrix::new([[1, 2, 5], [3, 4, 6]])
+        );
+
+        let c = Matrix::new([[7, 8]]);
+        assert_eq!(
+            Matrix::vstack(&[&a, &c]).unwrap(),
+            Matrix::new([[1, 2], [3, 4], [7, 8]])
+        );
+
+        assert_eq!(
+            Matrix::vstack(&[&a, &b]).unwrap_err(),
+            LinalgError::BlockShape {
+                block: (1, 0),
+                expected: (2, 2),
+                found: (2, 1)
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_blocks_saddle_point() {
+        // [[A, B], [B^T, 0]]
+        let a = Matrix::new([[4, 1], [1, 3]]);
+        let b = Matrix::new([[1], [2]]);
+        let bt = Matrix::new([[1, 2]]);
+        let m = Matrix::from_blocks(&[
+            &[Block::from(&a), Block::from(&b)],
+            &[Block::from(&bt), Block::Zeros],
+        ])
+        .unwrap();
+        assert_eq!(m, Matrix::new([[4, 1, 1], [1, 3, 2], [1, 2, 0]]));
+    }
+
+    #[test]
+    fn test_from_blocks_views_and_identity() {
+        let big = Matrix::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        let corner = big.view(1..3, 1..3);
+        let m = Matrix::from_blocks(&[
+            &[Block::Identity, Block::from(corner.clone())],
+            &[Block::from(&big), Block::Zeros],
+        ])
+        .unwrap();
+        assert_eq!(m.rows(), 5);
+        assert_eq!(m.cols(), 5);
+        assert_eq!(
+            m.view(0..2, 0..3).to_matrix(),
+            Matrix::new([[1, 0, 0], [0, 1, 0]])
+        );
+        assert_eq!(m.view(0..2, 3..5), corner);
+        assert_eq!(m[(4, 2)], 9);
+    }
+
+    #[test]
+    fn test_from_blocks_errors() {
+        let a = Matrix::new([[1, 2]]);
+        assert_eq!(
+            Matrix::from_blocks(&[&[Block::from(&a)], &[Block::Zeros]]).unwrap_err(),
+            LinalgError::UnknownBlockSize {
+                axis: Axis::Rows,
+                index: 1
+            }
+        );
+        assert_eq!(
+            Matrix::from_blocks(&[&[Block::from(&a), Block::Zeros], &[Block::from(&a)]])
+                .unwrap_err(),
+            LinalgError::RaggedInput {
+                index: 1,
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_split_and_blocks() {
+        let m = Matrix::from_fn(5, 4, |i, j| 10 * i + j);
+        let pieces: Vec<_> = m.split_rows(&[2, 3]).collect();
+        assert_eq!(pieces[1].rows, 3);
+        assert_eq!(pieces[1][(0, 3)], 23);
+
+        let parts: Vec<Matrix<usize>> = m.split_cols(&[1, 3]).map(|v| v.to_matrix()).collect();
+        assert_eq!(Matrix::hstack(&[&parts[0], &parts[1]]).unwrap(), m);
+
+        let tiles: Vec<_> = m.blocks(2, 3).collect();
+        assert_eq!(tiles.len(), 6);
+        assert_eq!((tiles[1].rows, tiles[1].cols), (2, 1));
+        assert_eq!((tiles[5].rows, tiles[5].cols), (1, 1));
+        assert_eq!(tiles[5][(0, 0)], 43);
+    }
+}
@@ -0,0 +1,214 @@
+use super::{
+    condition::{self, Factorization},
+    lu::axpy,
+    mat::Matrix,
+    norm::norm_1,
+    svd::from_real,
+};
+use crate::error::LinalgError;
+use num::complex;
+use num_traits::{Float, Zero};
+
+// A = L L^H for Hermitian positive definite A. Only the lower triangle of A is read.
+#[derive(Debug, Clone)]
+pub struct Cholesky<T>
+where
+    T: complex::ComplexFloat,
+{
+    l: Matrix<T>,
+    anorm: T::Real,
+}
+
+impl<T> Matrix<T>
+where
+    T: complex::ComplexFloat + 'static,
+{
+    pub fn cholesky(&self) -> Result<Cholesky<T>, LinalgError> {
+        #[cfg(feature = "blas")]
+        return self.factor_cholesky(super::blas::potrf);
+        #[cfg(not(feature = "blas"))]
+        self.factor_cholesky(cholesky_factor)
+    }
+
+    #[cfg(feature = "blas")]
+    pub fn cholesky_native(&self) -> Result<Cholesky<T>, LinalgError> {
+        self.factor_cholesky(cholesky_factor)
+    }
+
+    fn factor_cholesky(
+        &self,
+        kernel: fn(&Matrix<T>) -> Result<Matrix<T>, LinalgError>,
+    ) -> Result<Cholesky<T>, LinalgError> {
+        if self.rows != self.cols {
+            return Err(LinalgError::NotSquare {
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+
+        Ok(Cholesky {
+            l: kernel(self)?,
+            anorm: hermitian_norm_1(&self.data, self.rows),
+        })
+    }
+}
+
+// The lower triangular factor of the square matrix `a`, zero above the diagonal
+pub(crate) fn cholesky_factor<T>(a: &Matrix<T>) -> Result<Matrix<T>, LinalgError>
+where
+    T: complex::ComplexFloat,
+{
+    let n = a.rows;
+    let mut l = a.clone();
+    let a = &a.data;
+    l.data.iter_mut().for_each(|x| *x = T::zero());
+
+    // Row by row (Cholesky-Banachiewicz), each entry only needs rows already finished
+    for i in 0..n {
+        for j in 0..=i {
+            let dot = (0..j).fold(T::zero(), |s, k| {
+                s + l.data[i * n + k] * l.data[j * n + k].conj()
+            });
+            let s = a[i * n + j] - dot;
+
+            if i == j {
+                let d = s.re();
+                if d.is_nan() || d <= T::Real::zero() {
+                    return Err(LinalgError::NotPositiveDefinite { order: i + 1 });
+                }
+                l.data[i * n + i] = from_real(Float::sqrt(d));
+            } else {
+                l.data[i * n + j] = s / l.data[j * n + j];
+            }
+        }
+    }
+
+    Ok(l)
+}
+
+impl<T> Cholesky<T>
+where
+    T: complex::ComplexFloat,
+{
+    pub fn l(&self) -> &Matrix<T> {
+        &self.l
+    }
+
+    pub fn solve(&self, b: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(b.rows, self.l.rows, "Incompatible matrix dimensions");
+
+        let mut x = b.clone();
+        self.solve_in_place(&mut x.data, x.cols);
+        x
+    }
+
+    // Estimate of the reciprocal 1-norm condition number, 1 / (||A||_1 ||A^-1||_1)
+    pub fn rcond(&self) -> T::Real {
+        condition::rcond(self.anorm, self)
+    }
+
+    fn solve_in_place(&self, b: &mut [T], nrhs: usize) {
+        let n = self.l.rows;
+        let l = &self.l.data;
+
+        // L y = b
+        for i in 0..n {
+            let (solved, rest) = b.split_at_mut(i * nrhs);
+            let row = &mut rest[..nrhs];
+            for (k, y) in solved.chunks_exact(nrhs).enumerate() {
+                axpy(row, l[i * n + k], y);
+            }
+            let diag = l[i * n + i];
+            row.iter_mut().for_each(|x| *x = *x / diag);
+        }
+
+        // L^H x = y
+        for i in (0..n).rev() {
+            let (head, solved) = b.split_at_mut((i + 1) * nrhs);
+            let row = &mut head[i * nrhs..];
+            for (k, x) in solved.chunks_exact(nrhs).enumerate() {
+                axpy(row, l[(i + 1 + k) * n + i].conj(), x);
+            }
+            let diag = l[i * n + i];
+            row.iter_mut().for_each(|x| *x = *x / diag);
+        }
+    }
+}
+
+impl<T> Factorization<T> for Cholesky<T>
+where
+    T: complex::ComplexFloat,
+{
+    fn order(&self) -> usize {
+        self.l.rows
+    }
+
+    fn solve_vec(&self, x: &mut [T]) {
+        self.solve_in_place(x, 1);
+    }
+
+    // A is Hermitian, so A^H x = b is the same system
+    fn solve_adjoint_vec(&self, x: &mut [T]) {
+        self.solve_in_place(x, 1);
+    }
+}
+
+// 1-norm of the Hermitian matrix whose lower triangle is stored in `a`
+fn hermitian_norm_1<T: complex::ComplexFloat>(a: &[T], n: usize) -> T::Real {
+    let mut full = a.to_vec();
+    for i in 0..n {
+        for j in i + 1..n {
+            full[i * n + j] = a[j * n + i].conj();
+        }
+    }
+    norm_1(&full, n, n, n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+
+    #[test]
+    fn test_cholesky_factor_and_solve() {
+        let a = Matrix::new([
+            [4.0, 12.0, -16.0],
+            [12.0, 37.0, -43.0],
+            [-16.0, -43.0, 98.0],
+        ]);
+        let chol = a.cholesky().unwrap();
+        assert_eq!(
+            chol.l().data.as_slice(),
+            vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0]
+        );
+
+        let x = Matrix::new([[1.0], [-2.0], [0.5]]);
+        let solved = chol.solve(&(&a * &x));
+        for (s, e) in solved.data.iter().zip(x.data.iter()) {
+            assert!((s - e).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_cholesky_complex() {
+        let a = Matrix::new([
+            [c64::new(4.0, 0.0), c64::new(2.0, -2.0)],
+            [c64::new(2.0, 2.0), c64::new(6.0, 0.0)],
+        ]);
+        let l = a.cholesky().unwrap().l().clone();
+        let adjoint = l.adjoint();
+        let product = &l * &adjoint;
+        for (p, e) in product.data.iter().zip(a.data.iter()) {
+            assert!((p - e).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cholesky_not_positive_definite() {
+        let a = Matrix::new([[1.0, 2.0], [2.0, 1.0]]);
+        assert_eq!(
+            a.cholesky().unwrap_err(),
+            LinalgError::NotPositiveDefinite { order: 2 }
+        );
+    }
+}
@@ -0,0 +1,352 @@
+use super::mat::Matrix;
+use crate::error::LinalgError;
+use aligned_vec::{avec, AVec, CACHELINE_ALIGN};
+use num_traits::{Float, NumCast, One, Zero};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+{
+    pub fn from_fn<F>(rows: usize, cols: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        Matrix {
+            rows,
+            cols,
+            data: AVec::from_iter(
+                CACHELINE_ALIGN,
+                (0..rows * cols).map(|k| f(k / cols, k % cols)),
+            ),
+        }
+    }
+
+    // Elements in row-major order
+    pub fn from_row_iter<I>(rows: usize, cols: usize, iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self::from_aligned(rows, cols, AVec::from_iter(CACHELINE_ALIGN, iter))
+    }
+
+    // Elements in column-major order
+    pub fn from_col_iter<I>(rows: usize, cols: usize, iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut data = avec![T::default(); rows * cols];
+        let mut count = 0;
+        for (k, x) in iter.into_iter().enumerate() {
+            assert!(k < rows * cols, "Data length must match rows * cols");
+            data[(k % rows) * cols + k / rows] = x;
+            count += 1;
+        }
+        assert_eq!(count, rows * cols, "Data length must match rows * cols");
+        Matrix { rows, cols, data }
+    }
+
+    pub fn from_rows<R: AsRef<[T]>>(rows: &[R]) -> Result<Self, LinalgError> {
+        let cols = check_ragged(rows)?;
+        Ok(Self::from_row_iter(
+            rows.len(),
+            cols,
+            rows.iter().flat_map(|row| row.as_ref().iter().cloned()),
+        ))
+    }
+
+    pub fn from_cols<C: AsRef<[T]>>(cols: &[C]) -> Result<Self, LinalgError> {
+        let rows = check_ragged(cols)?;
+        Ok(Self::from_col_iter(
+            rows,
+            cols.len(),
+            cols.iter().flat_map(|col| col.as_ref().iter().cloned()),
+        ))
+    }
+
+    pub fn filled(rows: usize, cols: usize, value: T) -> Self {
+        Matrix {
+            rows,
+            cols,
+            data: avec![value; rows * cols],
+        }
+    }
+
+    pub fn ones(rows: usize, cols: usize) -> Self
+    where
+        T: One,
+    {
+        Self::filled(rows, cols, T::one())
+    }
+
+    pub fn from_diagonal(diagonal: &[T]) -> Self
+    where
+        T: Zero,
+    {
+        let n = diagonal.len();
+        let mut m = Self::zeros(n, n);
+        for (i, x) in diagonal.iter().enumerate() {
+            m.data[i * n + i] = x.clone();
+        }
+        m
+    }
+
+    // Column vector of `n` evenly spaced points from `start` to `end` inclusive
+    pub fn linspace(start: T, end: T, n: usize) -> Self
+    where
+        T: Float,
+    {
+        if n == 1 {
+            return Self::from_vec(1, 1, vec![start]);
+        }
+        let last = T::from(n.saturating_sub(1)).unwrap();
+        let step = (end - start) / last;
+        let mut m = Self::from_fn(n, 1, |i, _| start + step * T::from(i).unwrap());
+        // Land exactly on `end` whatever the rounding of `step`
+        if n > 1 {
+            m.data[n - 1] = end;
+        }
+        m
+    }
+
+    // Column vector start, start + step, ... stopping before `end`
+    pub fn arange(start: T, end: T, step: T) -> Self
+    where
+        T: Float,
+    {
+        assert!(step != T::zero(), "Step must be nonzero");
+        let count = ((end - start) / step).ceil().max(T::zero());
+        let n = <usize as NumCast>::from(count).unwrap_or(0);
+        Self::from_fn(n, 1, |i, _| start + step * T::from(i).unwrap())
+    }
+
+    pub fn tridiagonal(sub: &[T], diagonal: &[T], sup: &[T]) -> Self
+    where
+        T: Zero,
+    {
+        let n = diagonal.len();
+        Self::banded(n, &[(-1, sub), (0, diagonal), (1, sup)])
+    }
+
+    // n x n matrix with `values` on diagonal `offset` for each (offset, values) band, positive
+    // offsets above the main diagonal and negative ones below
+    pub fn banded(n: usize, bands: &[(isize, &[T])]) -> Self
+    where
+        T: Zero,
+    {
+        let mut m = Self::zeros(n, n);
+        for &(offset, values) in bands {
+            let len = n.saturating_sub(offset.unsigned_abs());
+            assert_eq!(
+                values.len(),
+                len,
+                "Band {offset} of a {n}x{n} matrix must have {len} elements"
+            );
+            let (row, col) = if offset >= 0 {
+                (0, offset as usize)
+            } else {
+                (offset.unsigned_abs(), 0)
+            };
+            for (k, x) in values.iter().enumerate() {
+                m.data[(row + k) * n + col + k] = x.clone();
+            }
+        }
+        m
+    }
+
+    // H[i, j] = 1 / (i + j + 1), the classic badly conditioned test matrix
+    pub fn hilbert(n: usize) -> Self
+    where
+        T: Float,
+    {
+        Self::from_fn(n, n, |i, j| T::one() / T::from(i + j + 1).unwrap())
+    }
+
+    // V[i, j] = x[i]^j, increasing powers from 0 to cols - 1
+    pub fn vandermonde(x: &[T], cols: usize) -> Self
+    where
+        T: One,
+    {
+        let mut m = Self::filled(x.len(), cols, T::one());
+        for (i, xi) in x.iter().enumerate() {
+            for j in 1..cols {
+                m.data[i * cols + j] = m.data[i * cols + j - 1].clone() * xi.clone();
+            }
+        }
+        m
+    }
+
+    // T[i, j] = first_col[i - j] below the diagonal and first_row[j - i] above it. The diagonal
+    // comes from first_col, first_row[0] is ignored.
+    pub fn toeplitz(first_col: &[T], first_row: &[T]) -> Self {
+        Self::from_fn(first_col.len(), first_row.len(), |i, j| {
+            if i >= j {
+                first_col[i - j].clone()
+            } else {
+                first_row[j - i].clone()
+            }
+        })
+    }
+
+    // H[i, j] = first_col[i + j] while that exists, then last_row continues the anti-diagonals.
+    // last_row[0] is ignored in favour of the last entry of first_col.
+    pub fn hankel(first_col: &[T], last_row: &[T]) -> Self {
+        let rows = first_col.len();
+        Self::from_fn(rows, last_row.len(), |i, j| {
+            if i + j < rows {
+                first_col[i + j].clone()
+            } else {
+                last_row[i + j + 1 - rows].clone()
+            }
+        })
+    }
+
+    // C[i, j] = first_col[(i - j) mod n], every column is the previous one rotated down by one
+    pub fn circulant(first_col: &[T]) -> Self {
+        let n = first_col.len();
+        Self::from_fn(n, n, |i, j| first_col[(i + n - j) % n].clone())
+    }
+
+    // Companion matrix of c[0] x^n + c[1] x^(n-1) + ... + c[n], whose eigenvalues are the roots
+    pub fn companion(coefficients: &[T]) -> Self
+    where
+        T: Zero + One + Div<Output = T> + Neg<Output = T>,
+    {
+        assert!(
+            coefficients.len() >= 2,
+            "Companion matrix needs a polynomial of degree at least 1"
+        );
+        assert!(
+            !coefficients[0].is_zero(),
+            "Leading coefficient must be nonzero"
+        );
+
+        let n = coefficients.len() - 1;
+        let lead = coefficients[0].clone();
+        let mut m = Self::zeros(n, n);
+        for (j, c) in coefficients[1..].iter().enumerate() {
+            m.data[j] = -(c.clone() / lead.clone());
+        }
+        for i in 1..n {
+            m.data[i * n + i - 1] = T::one();
+        }
+        m
+    }
+}
+
+// Length shared by all inputs, or the first one that differs
+fn check_ragged<T, R: AsRef<[T]>>(lines: &[R]) -> Result<usize, LinalgError> {
+    let expected = lines.first().map_or(0, |line| line.as_ref().len());
+    match lines
+        .iter()
+        .position(|line| line.as_ref().len() != expected)
+    {
+        Some(index) => Err(LinalgError::RaggedInput {
+            index,
+            expected,
+            found: lines[index].as_ref().len(),
+        }),
+        None => Ok(expected),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_fn_and_iterators() {
+        let m = Matrix::from_fn(2, 3, |i, j| 10 * i + j);
+        assert_eq!(m.data.as_slice(), vec![0, 1, 2, 10, 11, 12]);
+        assert_eq!(
+            Matrix::from_row_iter(2, 3, 0..6),
+            Matrix::new([[0, 1, 2], [3, 4, 5]])
+        );
+        assert_eq!(
+            Matrix::from_col_iter(2, 3, 0..6),
+            Matrix::new([[0, 2, 4], [1, 3, 5]])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Data length must match rows * cols")]
+    fn test_from_col_iter_too_long() {
+        let _ = Matrix::from_col_iter(2, 2, 0..5);
+    }
+
+    #[test]
+    fn test_from_rows_and_cols() {
+        let rows = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let m = Matrix::from_rows(&rows).unwrap();
+        assert_eq!(m, Matrix::new([[1, 2, 3], [4, 5, 6]]));
+        assert_eq!(Matrix::from_cols(&[[1, 4], [2, 5], [3, 6]]).unwrap(), m);
+
+        let ragged = vec![vec![1, 2], vec![3, 4], vec![5]];
+        assert_eq!(
+            Matrix::from_rows(&ragged).unwrap_err(),
+            LinalgError::RaggedInput {
+                index: 2,
+                expected: 2,
+                found: 1
+            }
+        );
+        assert!(Matrix::<i32>::from_cols(&ragged).is_err());
+    }
+
+    #[test]
+    fn test_simple_builders() {
+        assert_eq!(Matrix::<i32>::ones(1, 2).data.as_slice(), vec![1, 1]);
+        assert_eq!(Matrix::filled(2, 1, 7).data.as_slice(), vec![7, 7]);
+        assert_eq!(
+            Matrix::from_diagonal(&[1, 2]),
+            Matrix::new([[1, 0], [0, 2]])
+        );
+        assert_eq!(
+            Matrix::linspace(0.0, 1.0, 5).data.as_slice(),
+            vec![0.0, 0.25, 0.5, 0.75, 1.0]
+        );
+        assert_eq!(
+            Matrix::arange(1.0, 2.0, 0.25).data.as_slice(),
+            vec![1.0, 1.25, 1.5, 1.75]
+        );
+        assert_eq!(Matrix::arange(2.0, 0.0, 1.0).rows(), 0);
+    }
+
+    #[test]
+    fn test_banded_builders() {
+        let t = Matrix::tridiagonal(&[1, 2], &[3, 4, 5], &[6, 7]);
+        assert_eq!(t, Matrix::new([[3, 6, 0], [1, 4, 7], [0, 2, 5]]));
+
+        let b = Matrix::banded(3, &[(2, &[9][..]), (-1, &[1, 1][..])]);
+        assert_eq!(b, Matrix::new([[0, 0, 9], [1, 0, 0], [0, 1, 0]]));
+    }
+
+    #[test]
+    fn test_special_matrices() {
+        let h = Matrix::<f64>::hilbert(3);
+        assert_eq!(h[(2, 2)], 0.2);
+        assert_eq!(h[(0, 1)], h[(1, 0)]);
+
+        assert_eq!(
+            Matrix::vandermonde(&[2, 3], 3),
+            Matrix::new([[1, 2, 4], [1, 3, 9]])
+        );
+        assert_eq!(
+            Matrix::toeplitz(&[1, 2, 3], &[0, 4]),
+            Matrix::new([[1, 4], [2, 1], [3, 2]])
+        );
+        assert_eq!(
+            Matrix::hankel(&[1, 2, 3], &[0, 4, 5]),
+            Matrix::new([[1, 2, 3], [2, 3, 4], [3, 4, 5]])
+        );
+        assert_eq!(
+            Matrix::circulant(&[1, 2, 3]),
+            Matrix::new([[1, 3, 2], [2, 1, 3], [3, 2, 1]])
+        );
+        // x^2 - 3x + 2 = (x - 1)(x - 2)
+        assert_eq!(
+            Matrix::companion(&[1.0, -3.0, 2.0]),
+            Matrix::new([[3.0, -2.0], [1.0, 0.0]])
+        );
+    }
+}
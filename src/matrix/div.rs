@@ -0,0 +1,97 @@
+use super::mat::Matrix;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+impl<T> Div<T> for Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn div(mut self, rhs: T) -> Self::Output {
+        self.map_inplace(|x| *x = x.clone() / rhs.clone());
+        self
+    }
+}
+
+impl<T> Div<&T> for Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn div(self, rhs: &T) -> Self::Output {
+        self / rhs.clone()
+    }
+}
+
+impl<T> Div<T> for &Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        self.map(|x| x.clone() / rhs.clone())
+    }
+}
+
+impl<T> Rem<T> for Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Rem<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn rem(mut self, rhs: T) -> Self::Output {
+        self.map_inplace(|x| *x = x.clone() % rhs.clone());
+        self
+    }
+}
+
+impl<T> Rem<&T> for Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Rem<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn rem(self, rhs: &T) -> Self::Output {
+        self % rhs.clone()
+    }
+}
+
+impl<T> Rem<T> for &Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Rem<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn rem(self, rhs: T) -> Self::Output {
+        self.map(|x| x.clone() % rhs.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_scalar_division() {
+        let m = Matrix::new([[2.0, 4.0], [6.0, 8.0]]);
+        let result = m / 2.0;
+        assert_eq!(result.data.as_slice(), vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_matrix_ref_scalar_division() {
+        let m = Matrix::new([[2, 4], [6, 9]]);
+        let result = &m / 2;
+        assert_eq!(result.data.as_slice(), vec![1, 2, 3, 4]);
+        assert_eq!(m.rows(), 2);
+    }
+
+    #[test]
+    fn test_matrix_scalar_remainder() {
+        let m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let result = m % 4;
+        assert_eq!(result.data.as_slice(), vec![1, 2, 3, 0, 1, 2]);
+    }
+}
@@ -0,0 +1,175 @@
+use super::{mat::Matrix, mat_view::MatrixView};
+use crate::error::LinalgError;
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use nalgebra::{DMatrix, DMatrixView, Dim, Dyn, RawStorage, Scalar};
+use std::ops::{Add, Mul, Sub};
+
+// nalgebra stores column-major, so its views of a `Matrix` step a whole row to go down a column
+// and one element to go across. The other way round only row or column vectors, and views whose
+// rows are adjacent elements, such as those made from row-major data, can be borrowed; anything
+// else fails with `NotContiguous` and `Matrix::from` makes the copy.
+
+impl<'a, T> From<&'a Matrix<T>> for DMatrixView<'a, T, Dyn, Dyn>
+where
+    T: Scalar + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(m: &'a Matrix<T>) -> Self {
+        DMatrixView::from_slice_with_strides(&m.data, m.rows, m.cols, m.cols, 1)
+    }
+}
+
+impl<'a, T> From<MatrixView<'a, T>> for DMatrixView<'a, T, Dyn, Dyn>
+where
+    T: Scalar + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(view: MatrixView<'a, T>) -> Self {
+        // Empty views may borrow an empty slice, which the parent's stride would reach past
+        let stride = if view.rows == 0 || view.cols == 0 {
+            view.cols
+        } else {
+            view.stride
+        };
+        DMatrixView::from_slice_with_strides(view.data, view.rows, view.cols, stride, 1)
+    }
+}
+
+impl<T> From<Matrix<T>> for DMatrix<T>
+where
+    T: Scalar + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(m: Matrix<T>) -> Self {
+        DMatrix::from_row_slice(m.rows, m.cols, &m.data)
+    }
+}
+
+// Borrows owned matrices and views alike
+impl<'a, T, R, C, S> TryFrom<&'a nalgebra::Matrix<T, R, C, S>> for MatrixView<'a, T>
+where
+    T: Scalar + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+{
+    type Error = LinalgError;
+
+    fn try_from(m: &'a nalgebra::Matrix<T, R, C, S>) -> Result<Self, Self::Error> {
+        let (rows, cols) = m.shape();
+        let (rstride, cstride) = m.strides();
+        // A single row or column leaves the stride along it meaningless
+        let stride = if rows <= 1 { cols } else { rstride };
+        if stride < cols || (cols > 1 && cstride != 1) {
+            return Err(LinalgError::NotContiguous {
+                strides: (rstride as isize, cstride as isize),
+            });
+        }
+        let data = if rows == 0 || cols == 0 {
+            &[]
+        } else {
+            // SAFETY: the span from the first element to the last holds every element of `m`,
+            // and the storage keeps it alive and unchanged for 'a
+            unsafe { std::slice::from_raw_parts(m.data.ptr(), (rows - 1) * stride + cols) }
+        };
+        Ok(MatrixView::with_stride(rows, cols, stride, data))
+    }
+}
+
+impl<T, R, C, S> From<&nalgebra::Matrix<T, R, C, S>> for Matrix<T>
+where
+    T: Scalar + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+{
+    fn from(m: &nalgebra::Matrix<T, R, C, S>) -> Self {
+        let (rows, cols) = m.shape();
+        Matrix {
+            rows,
+            cols,
+            data: AVec::from_iter(
+                CACHELINE_ALIGN,
+                (0..rows).flat_map(|i| (0..cols).map(move |j| m[(i, j)].clone())),
+            ),
+        }
+    }
+}
+
+impl<T> From<DMatrix<T>> for Matrix<T>
+where
+    T: Scalar + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(m: DMatrix<T>) -> Self {
+        Matrix::from(&m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DVector;
+
+    #[test]
+    fn test_borrowed_views() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let v = DMatrixView::from(&m);
+        assert_eq!(
+            v,
+            DMatrix::from_row_slice(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+        );
+        assert_eq!(v.as_ptr(), m.data.as_ptr());
+        assert_eq!(
+            DMatrixView::from(m.view(1..2, 1..3)),
+            DMatrix::from_row_slice(1, 2, &[5.0, 6.0])
+        );
+
+        // Back again, still without a copy
+        let back = MatrixView::try_from(&v).unwrap();
+        assert_eq!(back.data.as_ptr(), m.data.as_ptr());
+        assert_eq!(back.to_matrix(), m);
+
+        // A view of an inner block keeps the row stride of the whole matrix
+        let columns = v.columns(1, 2);
+        let block = MatrixView::try_from(&columns).unwrap();
+        assert_eq!(block.data.as_ptr(), m.data[1..].as_ptr());
+        assert_eq!(block.to_matrix(), Matrix::new([[2.0, 3.0], [5.0, 6.0]]));
+
+        let column = DVector::from_vec(vec![1, 2, 3]);
+        let view = MatrixView::try_from(&column).unwrap();
+        assert_eq!((view.rows, view.cols), (3, 1));
+        assert_eq!(view[(2, 0)], 3);
+    }
+
+    #[test]
+    fn test_empty_views() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        for (view, shape) in [
+            (m.view(0..2, 1..1), (2, 0)),
+            (m.view(1..1, 0..2), (0, 2)),
+            (m.view(2..2, 0..0), (0, 0)),
+        ] {
+            assert_eq!(DMatrixView::from(view).shape(), shape);
+        }
+    }
+
+    #[test]
+    fn test_column_major_is_copied() {
+        let n = DMatrix::from_row_slice(2, 3, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            MatrixView::try_from(&n).unwrap_err(),
+            LinalgError::NotContiguous { strides: (1, 2) }
+        );
+        assert_eq!(Matrix::from(&n), Matrix::new([[1, 2, 3], [4, 5, 6]]));
+        assert_eq!(
+            Matrix::from(&n.columns(1, 2)),
+            Matrix::new([[2, 3], [5, 6]])
+        );
+    }
+
+    #[test]
+    fn test_owned_round_trip() {
+        let m = Matrix::from_fn(3, 4, |i, j| (i * 4 + j) as f64 - 1.5);
+        let n = DMatrix::from(m.clone());
+        assert_eq!(n.shape(), (3, 4));
+        assert_eq!(n[(2, 1)], 7.5);
+        assert_eq!(Matrix::from(n), m);
+    }
+}
@@ -0,0 +1,204 @@
+use super::{mat::Matrix, mat_view::MatrixView};
+use crate::error::LinalgError;
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use ndarray::{Array2, ArrayBase, ArrayView2, Data, Ix2, ShapeBuilder};
+use std::ops::{Add, Mul, Sub};
+
+// Views go both ways without copying where the layouts agree. A borrowed ndarray has to keep the
+// elements of each row adjacent to become a `MatrixView`, as standard layout and column slices of
+// it do; otherwise the conversion fails with `NotContiguous` and `Matrix::from` makes the copy.
+// Owned conversions always copy, since `Matrix` keeps its elements cache-line aligned.
+
+impl<'a, T> From<&'a Matrix<T>> for ArrayView2<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(m: &'a Matrix<T>) -> Self {
+        ArrayView2::from_shape((m.rows, m.cols), &m.data).unwrap()
+    }
+}
+
+impl<'a, T> From<MatrixView<'a, T>> for ArrayView2<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(view: MatrixView<'a, T>) -> Self {
+        // Empty views may borrow an empty slice, which the parent's stride would reach past
+        if view.rows == 0 || view.cols == 0 {
+            return ArrayView2::from_shape((view.rows, view.cols), view.data).unwrap();
+        }
+        ArrayView2::from_shape((view.rows, view.cols).strides((view.stride, 1)), view.data).unwrap()
+    }
+}
+
+impl<T> From<Matrix<T>> for Array2<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(m: Matrix<T>) -> Self {
+        Array2::from_shape_vec((m.rows, m.cols), m.data.to_vec()).unwrap()
+    }
+}
+
+impl<'a, T, S> TryFrom<&'a ArrayBase<S, Ix2>> for MatrixView<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    S: Data<Elem = T>,
+{
+    type Error = LinalgError;
+
+    fn try_from(array: &'a ArrayBase<S, Ix2>) -> Result<Self, Self::Error> {
+        // SAFETY: the pointer, shape and strides all come from `array`, borrowed for 'a
+        unsafe { row_major_view(array.as_ptr(), array.dim(), array.strides()) }
+    }
+}
+
+impl<'a, T> TryFrom<ArrayView2<'a, T>> for MatrixView<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Error = LinalgError;
+
+    fn try_from(array: ArrayView2<'a, T>) -> Result<Self, Self::Error> {
+        // SAFETY: the pointer, shape and strides all come from `array`, which borrows for 'a
+        unsafe { row_major_view(array.as_ptr(), array.dim(), array.strides()) }
+    }
+}
+
+// Rows of adjacent elements, each starting a non-negative row stride after the previous one.
+// `ptr` has to point to the first element of an array with this shape and these strides that
+// stays alive and unchanged for 'a.
+unsafe fn row_major_view<'a, T>(
+    ptr: *const T,
+    (rows, cols): (usize, usize),
+    strides: &[isize],
+) -> Result<MatrixView<'a, T>, LinalgError>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    // A single row or column leaves the stride along it meaningless
+    let row_stride = if rows <= 1 {
+        Some(cols)
+    } else {
+        usize::try_from(strides[0]).ok()
+    };
+    let stride = match row_stride {
+        Some(stride) if stride >= cols && (cols <= 1 || strides[1] == 1) => stride,
+        _ => return Err(not_contiguous(strides)),
+    };
+    let data = if rows == 0 || cols == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptr, (rows - 1) * stride + cols)
+    };
+    Ok(MatrixView::with_stride(rows, cols, stride, data))
+}
+
+// Copies from any layout, including transposed and sliced arrays
+impl<T, S> From<&ArrayBase<S, Ix2>> for Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    S: Data<Elem = T>,
+{
+    fn from(array: &ArrayBase<S, Ix2>) -> Self {
+        let (rows, cols) = array.dim();
+        Matrix {
+            rows,
+            cols,
+            data: AVec::from_iter(CACHELINE_ALIGN, array.iter().cloned()),
+        }
+    }
+}
+
+impl<T> From<Array2<T>> for Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(array: Array2<T>) -> Self {
+        Matrix::from(&array)
+    }
+}
+
+fn not_contiguous(strides: &[isize]) -> LinalgError {
+    LinalgError::NotContiguous {
+        strides: (strides[0], strides[1]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, s};
+
+    #[test]
+    fn test_borrowed_views() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let a = ArrayView2::from(&m);
+        assert_eq!(a, array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert_eq!(a.as_ptr(), m.data.as_ptr());
+
+        let sub = ArrayView2::from(m.view(0..2, 1..3));
+        assert_eq!(sub, array![[2.0, 3.0], [5.0, 6.0]]);
+        assert_eq!(sub.strides(), [3, 1]);
+
+        let owned = array![[1, 2], [3, 4], [5, 6]];
+        let view = MatrixView::try_from(&owned).unwrap();
+        assert_eq!(view.data.as_ptr(), owned.as_ptr());
+        assert_eq!(view.to_matrix(), Matrix::new([[1, 2], [3, 4], [5, 6]]));
+        let rows = MatrixView::try_from(owned.slice(s![1.., ..])).unwrap();
+        assert_eq!(rows[(1, 0)], 5);
+    }
+
+    #[test]
+    fn test_empty_views() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        for (view, dim) in [
+            (m.view(0..2, 1..1), (2, 0)),
+            (m.view(1..1, 0..2), (0, 2)),
+            (m.view(2..2, 0..0), (0, 0)),
+        ] {
+            assert_eq!(ArrayView2::from(view).dim(), dim);
+        }
+    }
+
+    #[test]
+    fn test_other_layouts_are_copied() {
+        let a = array![[1, 2, 3], [4, 5, 6]];
+        let t = a.t();
+        assert_eq!(
+            MatrixView::try_from(t).unwrap_err(),
+            LinalgError::NotContiguous { strides: (1, 3) }
+        );
+        assert_eq!(Matrix::from(&t), Matrix::new([[1, 4], [2, 5], [3, 6]]));
+
+        assert_eq!(
+            MatrixView::try_from(a.slice(s![.., ..;2])).unwrap_err(),
+            LinalgError::NotContiguous { strides: (3, 2) }
+        );
+    }
+
+    #[test]
+    fn test_column_slices_are_borrowed() {
+        let a = array![[1, 2, 3], [4, 5, 6]];
+        let columns = a.slice(s![.., 1..]);
+        let view = MatrixView::try_from(columns).unwrap();
+        assert_eq!(view.data.as_ptr(), columns.as_ptr());
+        assert_eq!(view.to_matrix(), Matrix::new([[2, 3], [5, 6]]));
+        assert_eq!(Matrix::from(&columns), Matrix::new([[2, 3], [5, 6]]));
+
+        let last = a.slice(s![.., 2..]);
+        let column = MatrixView::try_from(&last).unwrap();
+        assert_eq!(column.to_matrix(), Matrix::new([[3], [6]]));
+        let empty = MatrixView::try_from(a.slice(s![.., 1..1])).unwrap();
+        assert_eq!((empty.rows, empty.cols), (2, 0));
+    }
+
+    #[test]
+    fn test_owned_round_trip() {
+        let m = Matrix::from_fn(4, 3, |i, j| (i * 3 + j) as f32);
+        let a = Array2::from(m.clone());
+        assert_eq!(a.dim(), (4, 3));
+        assert_eq!(a[[2, 1]], 7.0);
+        assert_eq!(Matrix::from(a), m);
+    }
+}
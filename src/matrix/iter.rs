@@ -0,0 +1,364 @@
+use super::{mat::Matrix, mat_view::MatrixView};
+use aligned_vec::AVec;
+use rayon::{iter::Either, prelude::*};
+use std::ops::{Add, Mul, Sub};
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    // Elements in row-major order
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let cols = self.cols;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(k, x)| ((k / cols, k % cols), x))
+    }
+
+    pub fn row(&self, i: usize) -> MatrixView<'_, T> {
+        self.as_view().row(i)
+    }
+
+    pub fn col(&self, j: usize) -> MatrixView<'_, T> {
+        self.as_view().col(j)
+    }
+
+    // `rows()` and `cols()` already give the dimensions, hence the `_iter` suffix
+    pub fn row_iter(&self) -> impl Iterator<Item = MatrixView<'_, T>> {
+        self.as_view().row_iter()
+    }
+
+    pub fn col_iter(&self) -> impl Iterator<Item = MatrixView<'_, T>> {
+        self.as_view().col_iter()
+    }
+
+    // Main diagonal, min(rows, cols) elements
+    pub fn diagonal(&self) -> impl Iterator<Item = &T> {
+        self.data
+            .iter()
+            .step_by(self.cols + 1)
+            .take(self.rows.min(self.cols))
+    }
+
+    pub fn diagonal_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let n = self.rows.min(self.cols);
+        self.data.iter_mut().step_by(self.cols + 1).take(n)
+    }
+
+    fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView::new(self.rows, self.cols, &self.data)
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    pub fn par_iter(&self) -> rayon::slice::Iter<'_, T> {
+        self.data.par_iter()
+    }
+
+    pub fn par_iter_mut(&mut self) -> rayon::slice::IterMut<'_, T> {
+        self.data.par_iter_mut()
+    }
+
+    // Each item is one row as a slice. Zero-width matrices have no data to chunk, but still
+    // yield one empty slice per row, as `row_iter` does.
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = &[T]> {
+        if self.cols == 0 {
+            Either::Right((0..self.rows).into_par_iter().map(|_| &[][..]))
+        } else {
+            Either::Left(self.data.par_chunks_exact(self.cols))
+        }
+    }
+
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [T]> {
+        if self.cols == 0 {
+            Either::Right(
+                (0..self.rows)
+                    .into_par_iter()
+                    .map(|_| <&mut [T]>::default()),
+            )
+        } else {
+            Either::Left(self.data.par_chunks_exact_mut(self.cols))
+        }
+    }
+
+    // Bands of `rows_per_chunk` whole rows (fewer in the last one), as row-major slices
+    pub fn par_chunks(&self, rows_per_chunk: usize) -> rayon::slice::Chunks<'_, T> {
+        assert!(rows_per_chunk > 0, "Chunk size must be nonzero");
+        self.data.par_chunks((rows_per_chunk * self.cols).max(1))
+    }
+
+    pub fn par_chunks_mut(&mut self, rows_per_chunk: usize) -> rayon::slice::ChunksMut<'_, T> {
+        assert!(rows_per_chunk > 0, "Chunk size must be nonzero");
+        self.data
+            .par_chunks_mut((rows_per_chunk * self.cols).max(1))
+    }
+}
+
+impl<'a, T> MatrixView<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter {
+            view: self.clone(),
+            row: 0,
+            col: 0,
+        }
+    }
+
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        let view = self.clone();
+        (0..self.rows).flat_map(move |i| {
+            view.row_slice(i)
+                .iter()
+                .enumerate()
+                .map(move |(j, x)| ((i, j), x))
+        })
+    }
+
+    pub fn row(&self, i: usize) -> MatrixView<'a, T> {
+        assert!(i < self.rows, "Row index out of bounds");
+        MatrixView::new(1, self.cols, self.row_slice(i))
+    }
+
+    pub fn col(&self, j: usize) -> MatrixView<'a, T> {
+        assert!(j < self.cols, "Column index out of bounds");
+        // Matrices without rows may have no data at all
+        if self.rows == 0 {
+            return MatrixView::with_stride(0, 1, self.stride, &[]);
+        }
+        let end = (self.rows - 1) * self.stride + j + 1;
+        MatrixView::with_stride(self.rows, 1, self.stride, &self.data[j..end])
+    }
+
+    pub fn row_iter(&self) -> impl Iterator<Item = MatrixView<'a, T>> {
+        let view = self.clone();
+        (0..self.rows).map(move |i| view.row(i))
+    }
+
+    pub fn col_iter(&self) -> impl Iterator<Item = MatrixView<'a, T>> {
+        let view = self.clone();
+        (0..self.cols).map(move |j| view.col(j))
+    }
+
+    pub fn diagonal(&self) -> impl Iterator<Item = &'a T> {
+        let view = self.clone();
+        (0..self.rows.min(self.cols)).map(move |i| &view.data[i * view.stride + i])
+    }
+}
+
+// Row-major iterator over the elements of a possibly strided view
+#[derive(Debug, Clone)]
+pub struct Iter<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    view: MatrixView<'a, T>,
+    row: usize,
+    col: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.col == self.view.cols {
+            self.row += 1;
+            self.col = 0;
+        }
+        if self.row >= self.view.rows || self.view.cols == 0 {
+            return None;
+        }
+        let x = &self.view.data[self.row * self.view.stride + self.col];
+        self.col += 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let done = self.row * self.view.cols + self.col;
+        let left = (self.view.rows * self.view.cols).saturating_sub(done);
+        (left, Some(left))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>
+{
+}
+
+impl<'a, T> IntoIterator for MatrixView<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter {
+            view: self,
+            row: 0,
+            col: 0,
+        }
+    }
+}
+
+// Owning row-major iterator. The storage is reversed up front so each step is a cheap pop.
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    data: AVec<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.data.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.data.len(), Some(self.data.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.data.reverse();
+        IntoIter { data: self.data }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_element_iterators() {
+        let mut m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(m.iter().sum::<i32>(), 21);
+        m.iter_mut().for_each(|x| *x *= 2);
+        for x in &mut m {
+            *x += 1;
+        }
+        assert_eq!(
+            (&m).into_iter().copied().collect::<Vec<_>>(),
+            [3, 5, 7, 9, 11, 13]
+        );
+        assert_eq!(
+            m.clone().into_iter().collect::<Vec<_>>(),
+            [3, 5, 7, 9, 11, 13]
+        );
+        assert_eq!(m.into_iter().len(), 6);
+    }
+
+    #[test]
+    fn test_indexed_iter() {
+        let m = Matrix::new([[1, 2], [3, 4]]);
+        let indexed: Vec<_> = m.indexed_iter().collect();
+        assert_eq!(indexed[3], ((1, 1), &4));
+        let view_indexed: Vec<_> = m.view(0..2, 1..2).indexed_iter().collect();
+        assert_eq!(view_indexed, [((0, 0), &2), ((1, 0), &4)]);
+    }
+
+    #[test]
+    fn test_rows_cols_and_diagonal() {
+        let m = Matrix::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(m.row(1).iter().copied().collect::<Vec<_>>(), [4, 5, 6]);
+        assert_eq!(m.col(2).iter().copied().collect::<Vec<_>>(), [3, 6, 9]);
+
+        let row_sums: Vec<i32> = m.row_iter().map(|r| r.iter().sum()).collect();
+        assert_eq!(row_sums, [6, 15, 24]);
+        let col_sums: Vec<i32> = m.col_iter().map(|c| c.into_iter().sum()).collect();
+        assert_eq!(col_sums, [12, 15, 18]);
+
+        assert_eq!(m.diagonal().copied().collect::<Vec<_>>(), [1, 5, 9]);
+        let view = m.view(1..3, 0..3);
+        assert_eq!(view.diagonal().copied().collect::<Vec<_>>(), [4, 8]);
+        assert_eq!(view.col(1).iter().copied().collect::<Vec<_>>(), [5, 8]);
+        assert_eq!(view.iter().len(), 6);
+    }
+
+    #[test]
+    fn test_diagonal_mut_rectangular() {
+        let mut m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        m.diagonal_mut().for_each(|x| *x = 0);
+        assert_eq!(m, Matrix::new([[0, 2, 3], [4, 0, 6]]));
+    }
+
+    #[test]
+    fn test_parallel_rows() {
+        let mut m = Matrix::from_fn(100, 8, |i, _| i as f64);
+        m.par_rows_mut()
+            .enumerate()
+            .for_each(|(i, row)| row.iter_mut().for_each(|x| *x += i as f64));
+        assert_eq!(m[(99, 7)], 198.0);
+
+        let sums: Vec<f64> = m.par_rows().map(|row| row.iter().sum()).collect();
+        assert_eq!(sums[10], 160.0);
+
+        let chunk_lengths: Vec<usize> = m.par_chunks(30).map(|c| c.len()).collect();
+        assert_eq!(chunk_lengths, [240, 240, 240, 80]);
+        m.par_chunks_mut(30).for_each(|c| c.fill(1.0));
+        assert_eq!(m.par_iter().sum::<f64>(), 800.0);
+    }
+
+    #[test]
+    fn test_empty_rows_and_columns() {
+        let m = Matrix::<f64>::zeros(0, 3);
+        assert_eq!(m.col(1).rows, 0);
+        assert_eq!(m.col_iter().count(), 3);
+        assert_eq!(m.col_iter().map(|c| c.iter().count()).sum::<usize>(), 0);
+
+        let mut m = Matrix::<f64>::zeros(3, 0);
+        assert_eq!(m.row_iter().count(), 3);
+        assert_eq!(m.par_rows().count(), 3);
+        assert!(m.par_rows().all(|row| row.is_empty()));
+        assert_eq!(m.par_rows_mut().count(), 3);
+    }
+}
@@ -0,0 +1,166 @@
+use super::mat::Matrix;
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use num_traits::Zero;
+use std::ops::{Add, Mul, Sub};
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+{
+    // A ⊗ B, block (i, j) of the result is a_ij B
+    pub fn kron(&self, other: &Matrix<T>) -> Matrix<T> {
+        let (p, q) = (other.rows, other.cols);
+        let cols = self.cols * q;
+        Matrix::from_fn(self.rows * p, cols, |r, c| {
+            self.data[(r / p) * self.cols + c / q].clone() * other.data[(r % p) * q + c % q].clone()
+        })
+    }
+
+    // Column-wise Kronecker product, column j is a_j ⊗ b_j
+    pub fn khatri_rao(&self, other: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(
+            self.cols, other.cols,
+            "Matrices must have the same number of columns"
+        );
+        let (n, p) = (self.cols, other.rows);
+        Matrix::from_fn(self.rows * p, n, |r, j| {
+            self.data[(r / p) * n + j].clone() * other.data[(r % p) * n + j].clone()
+        })
+    }
+
+    // Row-wise Kronecker (transposed Khatri-Rao) product, row i is a_i ⊗ b_i
+    pub fn face_splitting(&self, other: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(
+            self.rows, other.rows,
+            "Matrices must have the same number of rows"
+        );
+        let q = other.cols;
+        Matrix::from_fn(self.rows, self.cols * q, |i, c| {
+            self.data[i * self.cols + c / q].clone() * other.data[i * q + c % q].clone()
+        })
+    }
+
+    // Stacks the columns into one (rows * cols) x 1 column
+    pub fn vec(&self) -> Matrix<T> {
+        Matrix {
+            rows: self.rows * self.cols,
+            cols: 1,
+            data: AVec::from_iter(
+                CACHELINE_ALIGN,
+                (0..self.cols).flat_map(|j| {
+                    (0..self.rows).map(move |i| self.data[i * self.cols + j].clone())
+                }),
+            ),
+        }
+    }
+
+    // Inverse of `vec`, cuts the elements into columns of `rows` elements
+    pub fn unvec(&self, rows: usize) -> Matrix<T> {
+        let len = self.data.len();
+        assert!(
+            rows > 0 && len.is_multiple_of(rows),
+            "Length must be a multiple of the number of rows"
+        );
+        Matrix::from_col_iter(rows, len / rows, self.data.iter().cloned())
+    }
+
+    // (A ⊗ B) X without forming A ⊗ B, one column x of X at a time through
+    // (A ⊗ B) vec(Y) = vec(B Y A^T) with x = vec(Y). Costs O(pq n + pnm) per column instead of
+    // O(mp nq).
+    pub fn kron_apply(&self, other: &Matrix<T>, x: &Matrix<T>) -> Matrix<T>
+    where
+        T: Zero,
+    {
+        let (m, n) = (self.rows, self.cols);
+        let (p, q) = (other.rows, other.cols);
+        assert_eq!(x.rows, n * q, "Incompatible matrix dimensions");
+
+        let cols = x.cols;
+        let mut y = Matrix::zeros(m * p, cols);
+        let mut by = vec![T::zero(); p * n];
+        for c in 0..cols {
+            // Y is q x n, stored column-major in column c of x; BY is p x n
+            let x_at = |k: usize| x.data[k * cols + c].clone();
+            for i in 0..p {
+                for j in 0..n {
+                    by[i * n + j] = (0..q).fold(T::zero(), |s, k| {
+                        s + other.data[i * q + k].clone() * x_at(j * q + k)
+                    });
+                }
+            }
+
+            // vec(BY A^T): element (i, r) of the p x m result lands in row r * p + i
+            for r in 0..m {
+                for i in 0..p {
+                    y.data[(r * p + i) * cols + c] = (0..n).fold(T::zero(), |s, j| {
+                        s + by[i * n + j].clone() * self.data[r * n + j].clone()
+                    });
+                }
+            }
+        }
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kron() {
+        let a = Matrix::new([[1, 2], [3, 4]]);
+        let b = Matrix::new([[0, 5], [6, 7]]);
+        assert_eq!(
+            a.kron(&b),
+            Matrix::new([
+                [0, 5, 0, 10],
+                [6, 7, 12, 14],
+                [0, 15, 0, 20],
+                [18, 21, 24, 28]
+            ])
+        );
+    }
+
+    #[test]
+    fn test_khatri_rao_and_face_splitting() {
+        let a = Matrix::new([[1, 2], [3, 4]]);
+        let b = Matrix::new([[1, 0], [2, 1], [0, 3]]);
+        assert_eq!(
+            a.khatri_rao(&b),
+            Matrix::new([[1, 0], [2, 2], [0, 6], [3, 0], [6, 4], [0, 12]])
+        );
+
+        let c = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let d = Matrix::new([[1, -1], [2, 0]]);
+        assert_eq!(
+            c.face_splitting(&d),
+            Matrix::new([[1, -1, 2, -2, 3, -3], [8, 0, 10, 0, 12, 0]])
+        );
+        assert_eq!(c.face_splitting(&d).rows(), 2);
+    }
+
+    #[test]
+    fn test_vec_unvec_roundtrip() {
+        let m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let v = m.vec();
+        assert_eq!(v.data.as_slice(), vec![1, 4, 2, 5, 3, 6]);
+        assert_eq!(v.unvec(2), m);
+    }
+
+    #[test]
+    fn test_kron_apply_matches_explicit_product() {
+        let a = Matrix::from_fn(3, 2, |i, j| (i * 2 + j) as i64 - 2);
+        let b = Matrix::from_fn(2, 4, |i, j| (i + 3 * j) as i64 % 5);
+        let x = Matrix::from_fn(8, 1, |i, _| i as i64 - 3);
+        assert_eq!(a.kron_apply(&b, &x), &a.kron(&b) * &x);
+    }
+
+    #[test]
+    fn test_kron_apply_several_columns() {
+        let a = Matrix::from_fn(2, 3, |i, j| (i + 2 * j) as i64 - 1);
+        let b = Matrix::from_fn(3, 2, |i, j| (2 * i + j) as i64 % 4);
+        let x = Matrix::from_fn(6, 3, |i, j| (i * 3 + j) as i64 - 7);
+        assert_eq!(a.kron_apply(&b, &x), &a.kron(&b) * &x);
+        assert_eq!(a.kron_apply(&b, &Matrix::zeros(6, 0)).rows(), 6);
+    }
+}
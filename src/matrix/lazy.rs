@@ -0,0 +1,339 @@
+use super::{mat::Matrix, simd::SimdOps};
+use aligned_vec::avec;
+use rayon::prelude::*;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Sub};
+
+const SIMD_THRESHOLD: usize = 512 * 512; // Minimum elements for SIMD to be worth it
+
+// A node of an element-wise expression tree, addressed by flat row-major index
+pub trait Expression<T: SimdOps>: Sync {
+    fn get(&self, index: usize) -> T;
+
+    /// # Safety
+    ///
+    /// `index` must be a multiple of LANE_SIZE with at least LANE_SIZE elements remaining
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn load(&self, index: usize) -> T::Vector;
+}
+
+pub trait BinaryOp<T: SimdOps>: Sync {
+    fn apply(a: T, b: T) -> T;
+
+    /// # Safety
+    ///
+    /// The CPU must support the instruction set `T::has_simd_support` checks for
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn apply_simd(a: T::Vector, b: T::Vector) -> T::Vector;
+}
+
+pub struct AddOp;
+pub struct SubOp;
+
+impl<T> BinaryOp<T> for AddOp
+where
+    T: Add<Output = T> + SimdOps,
+{
+    fn apply(a: T, b: T) -> T {
+        a + b
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn apply_simd(a: T::Vector, b: T::Vector) -> T::Vector {
+        <T as SimdOps>::add(a, b)
+    }
+}
+
+impl<T> BinaryOp<T> for SubOp
+where
+    T: Sub<Output = T> + SimdOps,
+{
+    fn apply(a: T, b: T) -> T {
+        a - b
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn apply_simd(a: T::Vector, b: T::Vector) -> T::Vector {
+        <T as SimdOps>::sub(a, b)
+    }
+}
+
+pub struct Leaf<'a, T> {
+    data: &'a [T],
+}
+
+impl<'a, T> Expression<T> for Leaf<'a, T>
+where
+    T: Clone + SimdOps + Sync,
+{
+    fn get(&self, index: usize) -> T {
+        self.data[index].clone()
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn load(&self, index: usize) -> T::Vector {
+        // Leaves borrow cache-line aligned Matrix storage and are only loaded at lane offsets
+        debug_assert!(T::is_aligned(self.data[index..].as_ptr()));
+        T::load_aligned(self.data[index..].as_ptr())
+    }
+}
+
+pub struct Binary<L, R, Op> {
+    lhs: L,
+    rhs: R,
+    op: PhantomData<Op>,
+}
+
+impl<T, L, R, Op> Expression<T> for Binary<L, R, Op>
+where
+    T: SimdOps,
+    L: Expression<T>,
+    R: Expression<T>,
+    Op: BinaryOp<T>,
+{
+    fn get(&self, index: usize) -> T {
+        Op::apply(self.lhs.get(index), self.rhs.get(index))
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn load(&self, index: usize) -> T::Vector {
+        Op::apply_simd(self.lhs.load(index), self.rhs.load(index))
+    }
+}
+
+// An unevaluated element-wise expression, built with `Matrix::lazy` and the `+`/`-` operators
+pub struct Lazy<E> {
+    rows: usize,
+    cols: usize,
+    expr: E,
+}
+
+impl<E> Lazy<E> {
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn combine<F, Op>(self, other: Lazy<F>) -> Lazy<Binary<E, F, Op>> {
+        assert_eq!(
+            self.rows, other.rows,
+            "Matrices must have the same number of rows"
+        );
+        assert_eq!(
+            self.cols, other.cols,
+            "Matrices must have the same number of columns"
+        );
+
+        Lazy {
+            rows: self.rows,
+            cols: self.cols,
+            expr: Binary {
+                lhs: self.expr,
+                rhs: other.expr,
+                op: PhantomData,
+            },
+        }
+    }
+
+    pub fn eval<T>(&self) -> Matrix<T>
+    where
+        T: Clone
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Default
+            + SimdOps
+            + Send
+            + Sync,
+        E: Expression<T>,
+    {
+        let mut result = Matrix::from_aligned(
+            self.rows,
+            self.cols,
+            avec![T::default(); self.rows * self.cols],
+        );
+        self.eval_into(&mut result);
+        result
+    }
+
+    pub fn eval_into<T>(&self, dst: &mut Matrix<T>)
+    where
+        T: Clone
+            + Add<Output = T>
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Default
+            + SimdOps
+            + Send
+            + Sync,
+        E: Expression<T>,
+    {
+        assert_eq!(
+            self.rows, dst.rows,
+            "Matrices must have the same number of rows"
+        );
+        assert_eq!(
+            self.cols, dst.cols,
+            "Matrices must have the same number of columns"
+        );
+
+        let total_elements = self.rows * self.cols;
+
+        if total_elements >= SIMD_THRESHOLD && T::has_simd_support() {
+            unsafe { eval_simd(&self.expr, &mut dst.data) }
+        } else {
+            eval_scalar(&self.expr, &mut dst.data)
+        }
+    }
+}
+
+fn eval_scalar<T, E>(expr: &E, dst: &mut [T])
+where
+    T: SimdOps,
+    E: Expression<T>,
+{
+    dst.iter_mut()
+        .enumerate()
+        .for_each(|(i, r)| *r = expr.get(i));
+}
+
+#[cfg(target_arch = "x86_64")]
+unsafe fn eval_simd<T, E>(expr: &E, dst: &mut [T])
+where
+    T: SimdOps + Send,
+    E: Expression<T>,
+{
+    let chunk_size = T::LANE_SIZE * 128;
+
+    dst.par_chunks_mut(chunk_size)
+        .enumerate()
+        .for_each(|(chunk, r)| {
+            let base = chunk * chunk_size;
+            let lanes = r.len() / T::LANE_SIZE;
+
+            // A single pass over every operand: each lane is loaded, combined through the whole
+            // tree in registers and stored once
+            for i in 0..lanes {
+                let offset = i * T::LANE_SIZE;
+                T::store_aligned(r[offset..].as_mut_ptr(), expr.load(base + offset));
+            }
+
+            let remaining_start = lanes * T::LANE_SIZE;
+            r.iter_mut()
+                .enumerate()
+                .skip(remaining_start)
+                .for_each(|(i, x)| *x = expr.get(base + i));
+        });
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    pub fn lazy(&self) -> Lazy<Leaf<'_, T>> {
+        Lazy {
+            rows: self.rows,
+            cols: self.cols,
+            expr: Leaf { data: &self.data },
+        }
+    }
+}
+
+impl<E, F> Add<Lazy<F>> for Lazy<E> {
+    type Output = Lazy<Binary<E, F, AddOp>>;
+
+    fn add(self, other: Lazy<F>) -> Self::Output {
+        self.combine(other)
+    }
+}
+
+impl<E, F> Sub<Lazy<F>> for Lazy<E> {
+    type Output = Lazy<Binary<E, F, SubOp>>;
+
+    fn sub(self, other: Lazy<F>) -> Self::Output {
+        self.combine(other)
+    }
+}
+
+impl<'a, E, T> Add<&'a Matrix<T>> for Lazy<E>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = Lazy<Binary<E, Leaf<'a, T>, AddOp>>;
+
+    fn add(self, other: &'a Matrix<T>) -> Self::Output {
+        self.combine(other.lazy())
+    }
+}
+
+impl<'a, E, T> Sub<&'a Matrix<T>> for Lazy<E>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = Lazy<Binary<E, Leaf<'a, T>, SubOp>>;
+
+    fn sub(self, other: &'a Matrix<T>) -> Self::Output {
+        self.combine(other.lazy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+
+    #[test]
+    fn test_lazy_add_sub() {
+        let a: Matrix<i32> = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let b: Matrix<i32> = Matrix::new([[1, 1, 1], [1, 1, 1]]);
+        let c: Matrix<i32> = Matrix::new([[0, 2, 0], [2, 0, 2]]);
+
+        let result = (a.lazy() + &b - &c).eval();
+
+        assert_eq!(result, &(&a + &b) - &c);
+    }
+
+    #[test]
+    fn test_lazy_nested_expressions() {
+        let a: Matrix<f64> = Matrix::new([[1.0, 2.0], [3.0, 4.0]]);
+        let b: Matrix<f64> = Matrix::new([[0.5, 0.5], [0.5, 0.5]]);
+
+        let result = (a.lazy() - (b.lazy() + &b)).eval();
+
+        assert_eq!(result.data.as_slice(), vec![0.0, 1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_lazy_eval_into() {
+        let a: Matrix<c64> = Matrix::new([[c64::new(1.0, 1.0); 2]; 2]);
+        let b: Matrix<c64> = Matrix::new([[c64::new(2.0, -1.0); 2]; 2]);
+        let mut dst: Matrix<c64> = Matrix::zeros(2, 2);
+
+        (a.lazy() + &b + &a).eval_into(&mut dst);
+
+        assert_eq!(dst[(1, 1)], c64::new(4.0, 1.0));
+    }
+
+    #[test]
+    fn test_lazy_large_matrix_f32() {
+        let size = 1024 + 3;
+        let a = Matrix::from_vec(size, size, (0..size * size).map(|i| i as f32).collect());
+        let b = Matrix::from_vec(size, size, vec![2.0f32; size * size]);
+        let c = Matrix::from_vec(size, size, vec![0.5f32; size * size]);
+
+        let result = (a.lazy() + &b - &c).eval();
+
+        assert_eq!(result, &(&a + &b) - &c);
+    }
+
+    #[test]
+    #[should_panic(expected = "Matrices must have the same number of rows")]
+    fn test_lazy_dimension_mismatch() {
+        let a: Matrix<i32> = Matrix::new([[1, 2], [3, 4]]);
+        let b: Matrix<i32> = Matrix::new([[1, 2]]);
+        let _ = a.lazy() + &b;
+    }
+}
@@ -0,0 +1,316 @@
+use super::{
+    condition::{self, Factorization},
+    mat::Matrix,
+    norm::norm_1,
+    permutation::Permutation,
+};
+use crate::error::LinalgError;
+use num::complex;
+
+// PA = LU with partial pivoting. L (unit diagonal, not stored) and U share one matrix.
+#[derive(Debug, Clone)]
+pub struct Lu<T>
+where
+    T: complex::ComplexFloat,
+{
+    factors: Matrix<T>,
+    permutation: Permutation,
+    // 1-norm of the factored matrix, needed by the condition estimate
+    anorm: T::Real,
+}
+
+impl<T> Matrix<T>
+where
+    T: complex::ComplexFloat + 'static,
+{
+    pub fn lu(&self) -> Result<Lu<T>, LinalgError> {
+        #[cfg(feature = "blas")]
+        return self.factor_lu(super::blas::getrf);
+        #[cfg(not(feature = "blas"))]
+        self.factor_lu(lu_in_place)
+    }
+
+    #[cfg(feature = "blas")]
+    pub fn lu_native(&self) -> Result<Lu<T>, LinalgError> {
+        self.factor_lu(lu_in_place)
+    }
+
+    fn factor_lu(
+        &self,
+        kernel: fn(&mut Matrix<T>) -> Result<Vec<usize>, LinalgError>,
+    ) -> Result<Lu<T>, LinalgError> {
+        if self.rows != self.cols {
+            return Err(LinalgError::NotSquare {
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+
+        let n = self.rows;
+        let anorm = norm_1(&self.data, n, n, n);
+        let mut factors = self.clone();
+        let swaps = kernel(&mut factors)?;
+
+        Ok(Lu {
+            factors,
+            permutation: Permutation::from_swaps(n, &swaps)?,
+            anorm,
+        })
+    }
+}
+
+// Factors the square matrix `m` in place, returning the row swapped with row k at step k
+pub(crate) fn lu_in_place<T>(m: &mut Matrix<T>) -> Result<Vec<usize>, LinalgError>
+where
+    T: complex::ComplexFloat,
+{
+    let n = m.rows;
+    let mut swaps = Vec::with_capacity(n);
+    let a = &mut m.data;
+
+    for k in 0..n {
+        // Bring the largest entry of column k up to the diagonal
+        let p = (k..n).fold(k, |p, i| {
+            if a[i * n + k].abs() > a[p * n + k].abs() {
+                i
+            } else {
+                p
+            }
+        });
+        if a[p * n + k] == T::zero() {
+            return Err(LinalgError::Singular { pivot: k });
+        }
+        if p != k {
+            for j in 0..n {
+                a.swap(k * n + j, p * n + j);
+            }
+        }
+        swaps.push(p);
+
+        let (top, bottom) = a.split_at_mut((k + 1) * n);
+        let pivot_row = &top[k * n..];
+        let pivot = pivot_row[k];
+        for row in bottom.chunks_exact_mut(n) {
+            let factor = row[k] / pivot;
+            row[k] = factor;
+            if factor == T::zero() {
+                continue;
+            }
+            for (x, &u) in row[k + 1..].iter_mut().zip(&pivot_row[k + 1..]) {
+                *x = *x - factor * u;
+            }
+        }
+    }
+
+    Ok(swaps)
+}
+
+impl<T> Lu<T>
+where
+    T: complex::ComplexFloat,
+{
+    pub fn solve(&self, b: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(b.rows, self.factors.rows, "Incompatible matrix dimensions");
+
+        let mut x = b.clone();
+        self.solve_in_place(&mut x.data, x.cols);
+        x
+    }
+
+    // Unit lower triangular factor
+    pub fn l(&self) -> Matrix<T> {
+        let mut l = self.factors.clone();
+        let n = l.cols;
+        for (k, x) in l.data.iter_mut().enumerate() {
+            let (i, j) = (k / n, k % n);
+            if i == j {
+                *x = T::one();
+            } else if j > i {
+                *x = T::zero();
+            }
+        }
+        l
+    }
+
+    // Upper triangular factor
+    pub fn u(&self) -> Matrix<T> {
+        let mut u = self.factors.clone();
+        let n = u.cols;
+        for (k, x) in u.data.iter_mut().enumerate() {
+            if k % n < k / n {
+                *x = T::zero();
+            }
+        }
+        u
+    }
+
+    pub fn permutation(&self) -> &Permutation {
+        &self.permutation
+    }
+
+    pub fn det(&self) -> T {
+        let n = self.factors.rows;
+        let det = (0..n).fold(T::one(), |d, i| d * self.factors.data[i * n + i]);
+        if self.permutation.sign() > 0 {
+            det
+        } else {
+            -det
+        }
+    }
+
+    // Estimate of the reciprocal 1-norm condition number, 1 / (||A||_1 ||A^-1||_1)
+    pub fn rcond(&self) -> T::Real {
+        condition::rcond(self.anorm, self)
+    }
+
+    // Solves A X = B for the n x nrhs row-major block `b`
+    fn solve_in_place(&self, b: &mut [T], nrhs: usize) {
+        let n = self.factors.rows;
+        let lu = &self.factors.data;
+
+        self.permutation.gather_rows(b, nrhs);
+
+        // L y = P b
+        for i in 1..n {
+            let (solved, rest) = b.split_at_mut(i * nrhs);
+            let row = &mut rest[..nrhs];
+            for (k, y) in solved.chunks_exact(nrhs).enumerate() {
+                axpy(row, lu[i * n + k], y);
+            }
+        }
+
+        // U x = y
+        for i in (0..n).rev() {
+            let (head, solved) = b.split_at_mut((i + 1) * nrhs);
+            let row = &mut head[i * nrhs..];
+            for (k, x) in solved.chunks_exact(nrhs).enumerate() {
+                axpy(row, lu[i * n + i + 1 + k], x);
+            }
+            let diag = lu[i * n + i];
+            row.iter_mut().for_each(|x| *x = *x / diag);
+        }
+    }
+
+    // Solves A^H X = B, where A^H = U^H L^H P
+    fn solve_adjoint_in_place(&self, b: &mut [T], nrhs: usize) {
+        let n = self.factors.rows;
+        let lu = &self.factors.data;
+
+        // U^H w = b, U^H is lower triangular
+        for i in 0..n {
+            let (solved, rest) = b.split_at_mut(i * nrhs);
+            let row = &mut rest[..nrhs];
+            for (k, w) in solved.chunks_exact(nrhs).enumerate() {
+                axpy(row, lu[k * n + i].conj(), w);
+            }
+            let diag = lu[i * n + i].conj();
+            row.iter_mut().for_each(|x| *x = *x / diag);
+        }
+
+        // L^H v = w, L^H is unit upper triangular
+        for i in (0..n).rev() {
+            let (head, solved) = b.split_at_mut((i + 1) * nrhs);
+            let row = &mut head[i * nrhs..];
+            for (k, v) in solved.chunks_exact(nrhs).enumerate() {
+                axpy(row, lu[(i + 1 + k) * n + i].conj(), v);
+            }
+        }
+
+        self.permutation.scatter_rows(b, nrhs);
+    }
+}
+
+impl<T> Factorization<T> for Lu<T>
+where
+    T: complex::ComplexFloat,
+{
+    fn order(&self) -> usize {
+        self.factors.rows
+    }
+
+    fn solve_vec(&self, x: &mut [T]) {
+        self.solve_in_place(x, 1);
+    }
+
+    fn solve_adjoint_vec(&self, x: &mut [T]) {
+        self.solve_adjoint_in_place(x, 1);
+    }
+}
+
+// row -= alpha * x
+pub(crate) fn axpy<T: complex::ComplexFloat>(row: &mut [T], alpha: T, x: &[T]) {
+    if alpha == T::zero() {
+        return;
+    }
+    for (r, &v) in row.iter_mut().zip(x) {
+        *r = *r - alpha * v;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+
+    fn assert_close(actual: &Matrix<f64>, expected: &Matrix<f64>) {
+        for (a, e) in actual.data.iter().zip(expected.data.iter()) {
+            assert!((a - e).abs() < 1e-12, "{a} != {e}");
+        }
+    }
+
+    #[test]
+    fn test_lu_solve() {
+        let a = Matrix::new([[2.0, 1.0, 1.0], [4.0, -6.0, 0.0], [-2.0, 7.0, 2.0]]);
+        let x = Matrix::new([[1.0, 2.0], [-1.0, 0.0], [3.0, 1.0]]);
+        let b = &a * &x;
+
+        let lu = a.lu().unwrap();
+        assert_close(&lu.solve(&b), &x);
+        assert!((lu.det() - -16.0).abs() < 1e-12);
+        assert_close(&(lu.permutation() * &a), &(&lu.l() * &lu.u()));
+    }
+
+    #[test]
+    fn test_lu_solve_complex() {
+        let a = Matrix::new([
+            [c64::new(1.0, 1.0), c64::new(2.0, 0.0)],
+            [c64::new(0.0, -1.0), c64::new(3.0, 2.0)],
+        ]);
+        let x = Matrix::new([[c64::new(1.0, -2.0)], [c64::new(0.5, 1.0)]]);
+        let b = &a * &x;
+
+        let solved = a.lu().unwrap().solve(&b);
+        for (s, e) in solved.data.iter().zip(x.data.iter()) {
+            assert!((s - e).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_lu_adjoint_solve() {
+        let a = Matrix::new([[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]]);
+        let x = [1.0, -2.0, 0.5];
+        let mut b: Vec<f64> = (0..3)
+            .map(|j| (0..3).map(|i| a[(i, j)] * x[i]).sum())
+            .collect();
+
+        a.lu().unwrap().solve_adjoint_in_place(&mut b, 1);
+        for (s, e) in b.iter().zip(x) {
+            assert!((s - e).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_lu_errors() {
+        let singular = Matrix::new([[1.0, 2.0], [2.0, 4.0]]);
+        assert_eq!(
+            singular.lu().unwrap_err(),
+            LinalgError::Singular { pivot: 1 }
+        );
+
+        let wide = Matrix::new([[1.0, 2.0, 3.0]]);
+        assert_eq!(
+            wide.lu().unwrap_err(),
+            LinalgError::NotSquare { rows: 1, cols: 3 }
+        );
+    }
+}
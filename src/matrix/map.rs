@@ -0,0 +1,227 @@
+use super::mat::Matrix;
+use aligned_vec::{avec, AVec, CACHELINE_ALIGN};
+use rayon::prelude::*;
+use std::ops::{Add, Div, Mul, Sub};
+
+const PARALLEL_THRESHOLD: usize = 512 * 512; // Minimum elements for rayon to be worth it
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    pub fn map<U, F>(&self, f: F) -> Matrix<U>
+    where
+        U: Clone + Add<Output = U> + Sub<Output = U> + Mul<Output = U>,
+        F: Fn(&T) -> U,
+    {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: AVec::from_iter(CACHELINE_ALIGN, self.data.iter().map(f)),
+        }
+    }
+
+    pub fn map_inplace<F>(&mut self, f: F)
+    where
+        F: Fn(&mut T),
+    {
+        self.data.iter_mut().for_each(f);
+    }
+
+    pub fn zip_map<U, V, F>(&self, other: &Matrix<U>, f: F) -> Matrix<V>
+    where
+        U: Clone + Add<Output = U> + Sub<Output = U> + Mul<Output = U>,
+        V: Clone + Add<Output = V> + Sub<Output = V> + Mul<Output = V>,
+        F: Fn(&T, &U) -> V,
+    {
+        assert_same_shape(self, other);
+
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: AVec::from_iter(
+                CACHELINE_ALIGN,
+                self.data
+                    .iter()
+                    .zip(other.data.iter())
+                    .map(|(a, b)| f(a, b)),
+            ),
+        }
+    }
+
+    pub fn hadamard(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.zip_map(other, |a, b| a.clone() * b.clone())
+    }
+
+    pub fn div_elementwise(&self, other: &Matrix<T>) -> Matrix<T>
+    where
+        T: Div<Output = T>,
+    {
+        self.zip_map(other, |a, b| a.clone() / b.clone())
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    pub fn par_map<U, F>(&self, f: F) -> Matrix<U>
+    where
+        U: Clone + Add<Output = U> + Sub<Output = U> + Mul<Output = U> + Default + Send,
+        F: Fn(&T) -> U + Sync + Send,
+    {
+        if self.data.len() < PARALLEL_THRESHOLD {
+            return self.map(f);
+        }
+
+        let mut data = avec![U::default(); self.data.len()];
+        data.par_iter_mut()
+            .zip(self.data.par_iter())
+            .for_each(|(r, a)| *r = f(a));
+
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data,
+        }
+    }
+
+    pub fn par_map_inplace<F>(&mut self, f: F)
+    where
+        F: Fn(&mut T) + Sync + Send,
+    {
+        if self.data.len() < PARALLEL_THRESHOLD {
+            return self.map_inplace(f);
+        }
+
+        self.data.par_iter_mut().for_each(f);
+    }
+
+    pub fn par_zip_map<U, V, F>(&self, other: &Matrix<U>, f: F) -> Matrix<V>
+    where
+        U: Clone + Add<Output = U> + Sub<Output = U> + Mul<Output = U> + Sync,
+        V: Clone + Add<Output = V> + Sub<Output = V> + Mul<Output = V> + Default + Send,
+        F: Fn(&T, &U) -> V + Sync + Send,
+    {
+        if self.data.len() < PARALLEL_THRESHOLD {
+            return self.zip_map(other, f);
+        }
+
+        assert_same_shape(self, other);
+
+        let mut data = avec![V::default(); self.data.len()];
+        data.par_iter_mut()
+            .zip(self.data.par_iter().zip(other.data.par_iter()))
+            .for_each(|(r, (a, b))| *r = f(a, b));
+
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data,
+        }
+    }
+
+    pub fn par_hadamard(&self, other: &Matrix<T>) -> Matrix<T>
+    where
+        T: Default,
+    {
+        self.par_zip_map(other, |a, b| a.clone() * b.clone())
+    }
+
+    pub fn par_div_elementwise(&self, other: &Matrix<T>) -> Matrix<T>
+    where
+        T: Div<Output = T> + Default,
+    {
+        self.par_zip_map(other, |a, b| a.clone() / b.clone())
+    }
+}
+
+fn assert_same_shape<T, U>(m1: &Matrix<T>, m2: &Matrix<U>)
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    U: Clone + Add<Output = U> + Sub<Output = U> + Mul<Output = U>,
+{
+    assert_eq!(
+        m1.rows, m2.rows,
+        "Matrices must have the same number of rows"
+    );
+    assert_eq!(
+        m1.cols, m2.cols,
+        "Matrices must have the same number of columns"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+
+    #[test]
+    fn test_map() {
+        let m = Matrix::new([[1, 2], [3, 4]]);
+        let result = m.map(|x| x * x);
+        assert_eq!(result.data.as_slice(), vec![1, 4, 9, 16]);
+    }
+
+    #[test]
+    fn test_map_changes_type() {
+        let m = Matrix::new([[c64::new(3.0, 4.0), c64::new(0.0, 1.0)]]);
+        let result: Matrix<f64> = m.map(|z| z.norm());
+        assert_eq!(result.data.as_slice(), vec![5.0, 1.0]);
+    }
+
+    #[test]
+    fn test_map_inplace() {
+        let mut m = Matrix::new([[1, 2], [3, 4]]);
+        m.map_inplace(|x| *x += 10);
+        assert_eq!(m.data.as_slice(), vec![11, 12, 13, 14]);
+    }
+
+    #[test]
+    fn test_zip_map() {
+        let m1 = Matrix::new([[1, 2], [3, 4]]);
+        let m2 = Matrix::new([[4, 3], [2, 1]]);
+        let result = m1.zip_map(&m2, |a, b| a.max(b) * 2);
+        assert_eq!(result.data.as_slice(), vec![8, 6, 6, 8]);
+    }
+
+    #[test]
+    fn test_hadamard() {
+        let m1 = Matrix::new([[1, 2], [3, 4]]);
+        let m2 = Matrix::new([[5, 6], [7, 8]]);
+        assert_eq!(m1.hadamard(&m2).data.as_slice(), vec![5, 12, 21, 32]);
+    }
+
+    #[test]
+    fn test_div_elementwise() {
+        let m1 = Matrix::new([[1.0, 4.0], [9.0, 16.0]]);
+        let m2 = Matrix::new([[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(
+            m1.div_elementwise(&m2).data.as_slice(),
+            vec![1.0, 2.0, 3.0, 4.0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Matrices must have the same number of columns")]
+    fn test_hadamard_dimension_mismatch() {
+        let m1 = Matrix::new([[1, 2], [3, 4]]);
+        let m2 = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let _ = m1.hadamard(&m2);
+    }
+
+    #[test]
+    fn test_par_variants_large_matrix() {
+        let size = 1024;
+        let m1 = Matrix::from_vec(size, size, (0..size * size).map(|i| i as f64).collect());
+        let m2 = Matrix::from_vec(size, size, vec![2.0; size * size]);
+
+        assert_eq!(m1.par_map(|x| x * 3.0), m1.map(|x| x * 3.0));
+        assert_eq!(m1.par_hadamard(&m2), m1.hadamard(&m2));
+        assert_eq!(m1.par_div_elementwise(&m2), m1.div_elementwise(&m2));
+
+        let mut m3 = m1.clone();
+        m3.par_map_inplace(|x| *x -= 1.0);
+        assert_eq!(m3[(size - 1, size - 1)], (size * size - 2) as f64);
+    }
+}
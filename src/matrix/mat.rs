@@ -1,8 +1,17 @@
+use aligned_vec::{avec, AVec, CACHELINE_ALIGN};
 use num_traits;
 use std::fmt;
 use std::ops::{Add, Index, IndexMut, Mul, Range, Sub};
 
-use super::mat_view::MatrixView;
+use super::mat_view::{MatrixView, MatrixViewMut};
+
+// Direction of an axis-wise operation: `Rows` works along each row and yields one result per row,
+// `Cols` works along each column and yields one result per column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Rows,
+    Cols,
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Matrix<T>
@@ -11,7 +20,8 @@ where
 {
     pub(crate) rows: usize,
     pub(crate) cols: usize,
-    pub(crate) data: Vec<T>,
+    // Cache-line aligned so the SIMD kernels can use aligned loads and stores
+    pub(crate) data: AVec<T>,
 }
 
 impl<T> Matrix<T>
@@ -19,12 +29,10 @@ where
     T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
 {
     pub fn new<const R: usize, const C: usize>(data: [[T; C]; R]) -> Self {
-        let vec: Vec<T> = data.into_iter().flatten().collect();
-
         Matrix {
             rows: R,
             cols: C,
-            data: vec,
+            data: AVec::from_iter(CACHELINE_ALIGN, data.into_iter().flatten()),
         }
     }
 
@@ -32,7 +40,7 @@ where
         Matrix {
             rows,
             cols,
-            data: vec![T::default(); rows * cols],
+            data: avec![T::default(); rows * cols],
         }
     }
 
@@ -40,7 +48,7 @@ where
     where
         T: num_traits::One + num_traits::Zero,
     {
-        let mut data = vec![T::zero(); rows * cols];
+        let mut data = avec![T::zero(); rows * cols];
         let max_dim = std::cmp::min(rows, cols);
         for i in 0..max_dim {
             data[i * cols + i] = T::one();
@@ -55,11 +63,15 @@ where
         Matrix {
             rows,
             cols,
-            data: vec![T::zero(); rows * cols],
+            data: avec![T::zero(); rows * cols],
         }
     }
 
     pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        Self::from_aligned(rows, cols, AVec::from_iter(CACHELINE_ALIGN, data))
+    }
+
+    pub(crate) fn from_aligned(rows: usize, cols: usize, data: AVec<T>) -> Self {
         assert_eq!(
             rows * cols,
             data.len(),
@@ -69,13 +81,57 @@ where
     }
 
     pub fn view(&self, row_range: Range<usize>, col_range: Range<usize>) -> MatrixView<'_, T> {
-        let start = row_range.start * self.cols + col_range.start;
-        let end = row_range.end * self.cols + col_range.end;
-        MatrixView::new(
-            row_range.end - row_range.start,
-            col_range.end - col_range.start,
-            &self.data[start..end],
-        )
+        assert!(
+            row_range.start <= row_range.end && row_range.end <= self.rows,
+            "Row range out of bounds"
+        );
+        assert!(
+            col_range.start <= col_range.end && col_range.end <= self.cols,
+            "Column range out of bounds"
+        );
+
+        let rows = row_range.end - row_range.start;
+        let cols = col_range.end - col_range.start;
+        // The last row of the view ends at its own column range, not at the end of the stride.
+        // Empty views borrow nothing, their corner may lie past the end of the data.
+        let (start, end) = if rows == 0 || cols == 0 {
+            (0, 0)
+        } else {
+            (
+                row_range.start * self.cols + col_range.start,
+                (row_range.end - 1) * self.cols + col_range.end,
+            )
+        };
+
+        MatrixView::with_stride(rows, cols, self.cols, &self.data[start..end])
+    }
+
+    pub fn view_mut(
+        &mut self,
+        row_range: Range<usize>,
+        col_range: Range<usize>,
+    ) -> MatrixViewMut<'_, T> {
+        assert!(
+            row_range.start <= row_range.end && row_range.end <= self.rows,
+            "Row range out of bounds"
+        );
+        assert!(
+            col_range.start <= col_range.end && col_range.end <= self.cols,
+            "Column range out of bounds"
+        );
+
+        let rows = row_range.end - row_range.start;
+        let cols = col_range.end - col_range.start;
+        let (start, end) = if rows == 0 || cols == 0 {
+            (0, 0)
+        } else {
+            (
+                row_range.start * self.cols + col_range.start,
+                (row_range.end - 1) * self.cols + col_range.end,
+            )
+        };
+
+        MatrixViewMut::with_stride(rows, cols, self.cols, &mut self.data[start..end])
     }
 
     pub fn get(&self, row: usize, col: usize) -> &T {
@@ -177,6 +233,27 @@ mod tests {
         assert_eq!(m_view.rows, 2);
         assert_eq!(m_view.cols, 2);
         assert_eq!(m_view[(0, 0)], 1);
+        assert_eq!(m_view[(1, 0)], 4);
+        assert_eq!(m_view[(1, 1)], 5);
+    }
+
+    #[test]
+    fn test_zero_width_view() {
+        let m: Matrix<i32> = Matrix::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        let m_view = m.view(0..2, 1..1);
+        let copy = m_view.to_matrix();
+        assert_eq!((copy.rows, copy.cols), (2, 0));
+        assert_eq!(m_view, m.view(1..3, 2..2));
+        assert_eq!(m.view(3..3, 3..3).to_matrix().rows, 0);
+        assert_eq!(m.view(1..3, 3..3).to_matrix().rows, 2);
+    }
+
+    #[test]
+    fn test_view_inner_block() {
+        let m: Matrix<i32> = Matrix::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        let m_view = m.view(1..3, 1..3);
+        assert_eq!(m_view[(0, 0)], 5);
+        assert_eq!(m_view[(1, 1)], 9);
     }
 
     #[test]
@@ -213,7 +290,7 @@ mod tests {
     #[test]
     fn test_zeros() {
         let m: Matrix<u32> = Matrix::zeros(3, 3);
-        assert_eq!(m.data, vec![0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(m.data.as_slice(), vec![0, 0, 0, 0, 0, 0, 0, 0, 0]);
     }
 
     #[test]
@@ -225,7 +302,15 @@ mod tests {
     #[test]
     fn test_identity() {
         let m: Matrix<u32> = Matrix::identity(3, 3);
-        assert_eq!(m.data, vec![1, 0, 0, 0, 1, 0, 0, 0, 1]);
+        assert_eq!(m.data.as_slice(), vec![1, 0, 0, 0, 1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_storage_is_aligned() {
+        let m: Matrix<f32> = Matrix::from_vec(3, 5, vec![1.0; 15]);
+        assert_eq!(m.data.as_ptr() as usize % CACHELINE_ALIGN, 0);
+        let m: Matrix<c64> = Matrix::zeros(7, 3);
+        assert_eq!(m.data.as_ptr() as usize % CACHELINE_ALIGN, 0);
     }
 
     #[test]
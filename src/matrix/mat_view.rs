@@ -1,13 +1,17 @@
-use std::ops::{Add, Index, Mul, Sub};
+use super::mat::Matrix;
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct MatrixView<'a, T>
 where
     T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
 {
     pub rows: usize,
     pub cols: usize,
-    data: &'a [T],
+    // Distance in elements between the starts of consecutive rows
+    pub(crate) stride: usize,
+    pub(crate) data: &'a [T],
 }
 
 impl<'a, T> MatrixView<'a, T>
@@ -15,11 +19,57 @@ where
     T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
 {
     pub fn new(rows: usize, cols: usize, data: &'a [T]) -> Self {
-        MatrixView { rows, cols, data }
+        MatrixView {
+            rows,
+            cols,
+            stride: cols,
+            data,
+        }
+    }
+
+    pub fn with_stride(rows: usize, cols: usize, stride: usize, data: &'a [T]) -> Self {
+        check_layout(rows, cols, stride, data.len());
+        MatrixView {
+            rows,
+            cols,
+            stride,
+            data,
+        }
     }
 
     fn get(&self, row: usize, col: usize) -> &T {
-        &self.data[row * self.cols + col]
+        &self.data[row * self.stride + col]
+    }
+
+    pub(crate) fn row_slice(&self, row: usize) -> &'a [T] {
+        // Zero-width views may borrow an empty slice whatever their stride
+        if self.cols == 0 {
+            return &[];
+        }
+        let start = row * self.stride;
+        &self.data[start..start + self.cols]
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: AVec::from_iter(
+                CACHELINE_ALIGN,
+                (0..self.rows).flat_map(|i| self.row_slice(i).iter().cloned()),
+            ),
+        }
+    }
+}
+
+impl<'a, T> PartialEq for MatrixView<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows
+            && self.cols == other.cols
+            && (0..self.rows).all(|i| self.row_slice(i) == other.row_slice(i))
     }
 }
 
@@ -34,6 +84,113 @@ where
     }
 }
 
+fn check_layout(rows: usize, cols: usize, stride: usize, len: usize) {
+    assert!(
+        stride >= cols,
+        "Row stride must be at least the number of columns"
+    );
+    // The last row only needs its own columns, not a full stride
+    let required = match (rows, cols) {
+        (0, _) | (_, 0) => Some(0),
+        _ => (rows - 1)
+            .checked_mul(stride)
+            .and_then(|n| n.checked_add(cols)),
+    };
+    assert!(
+        required.is_some_and(|n| len >= n),
+        "Data is too short for the view's rows, columns and stride"
+    );
+}
+
+// A mutable window into row-major storage owned elsewhere, such as a matrix or a mapped file
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    pub rows: usize,
+    pub cols: usize,
+    pub(crate) stride: usize,
+    pub(crate) data: &'a mut [T],
+}
+
+impl<'a, T> MatrixViewMut<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    pub fn new(rows: usize, cols: usize, data: &'a mut [T]) -> Self {
+        Self::with_stride(rows, cols, cols, data)
+    }
+
+    pub fn with_stride(rows: usize, cols: usize, stride: usize, data: &'a mut [T]) -> Self {
+        check_layout(rows, cols, stride, data.len());
+        MatrixViewMut {
+            rows,
+            cols,
+            stride,
+            data,
+        }
+    }
+
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            rows: self.rows,
+            cols: self.cols,
+            stride: self.stride,
+            data: self.data,
+        }
+    }
+
+    pub(crate) fn row_slice_mut(&mut self, row: usize) -> &mut [T] {
+        if self.cols == 0 {
+            return &mut [];
+        }
+        let start = row * self.stride;
+        &mut self.data[start..start + self.cols]
+    }
+
+    pub fn copy_from(&mut self, src: &MatrixView<T>) {
+        assert_eq!(
+            (self.rows, self.cols),
+            (src.rows, src.cols),
+            "Incompatible matrix dimensions"
+        );
+        for i in 0..self.rows {
+            self.row_slice_mut(i).clone_from_slice(src.row_slice(i));
+        }
+    }
+
+    pub fn fill(&mut self, value: T) {
+        for i in 0..self.rows {
+            self.row_slice_mut(i).fill(value.clone());
+        }
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> {
+        self.as_view().to_matrix()
+    }
+}
+
+impl<'a, T> Index<(usize, usize)> for MatrixViewMut<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.data[row * self.stride + col]
+    }
+}
+
+impl<'a, T> IndexMut<(usize, usize)> for MatrixViewMut<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[row * self.stride + col]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,6 +222,15 @@ mod tests {
         assert_eq!(view[(1, 1)], 5);
     }
 
+    #[test]
+    fn test_strided_view() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let view = MatrixView::with_stride(2, 2, 3, &data[4..]);
+        assert_eq!(view[(0, 0)], 5);
+        assert_eq!(view[(1, 1)], 9);
+        assert_eq!(view.to_matrix().data.as_slice(), vec![5, 6, 8, 9]);
+    }
+
     #[test]
     fn test_matrix_equality() {
         let data = [1, 2, 3, 4];
@@ -81,4 +247,40 @@ mod tests {
         let view2 = MatrixView::new(2, 2, &data2);
         assert_ne!(view1, view2);
     }
+
+    #[test]
+    fn test_mutable_view() {
+        let mut m = Matrix::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        let mut view = m.view_mut(1..3, 0..2);
+        view[(0, 1)] = 50;
+        assert_eq!(view.as_view()[(0, 1)], 50);
+        view.copy_from(&MatrixView::new(2, 2, &[10, 11, 12, 13]));
+        assert_eq!(m.data.as_slice(), [1, 2, 3, 10, 11, 6, 12, 13, 9]);
+
+        m.view_mut(0..1, 1..3).fill(-1);
+        assert_eq!(m.data.as_slice(), [1, -1, -1, 10, 11, 6, 12, 13, 9]);
+    }
+
+    #[test]
+    fn test_zero_width_mutable_view() {
+        let mut m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let mut view = m.view_mut(0..2, 1..1);
+        view.fill(0);
+        assert_eq!(view.to_matrix().rows(), 2);
+        assert_eq!(m.data.as_slice(), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(m.view_mut(2..2, 3..3).to_matrix().rows(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Data is too short")]
+    fn test_mutable_view_checks_length() {
+        let mut data = [1, 2, 3, 4, 5];
+        MatrixViewMut::with_stride(2, 2, 4, &mut data);
+    }
+
+    #[test]
+    #[should_panic(expected = "Data is too short")]
+    fn test_view_checks_length() {
+        MatrixView::with_stride(2, 2, 4, &[1, 2, 3, 4, 5]);
+    }
 }
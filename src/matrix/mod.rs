@@ -1,7 +1,38 @@
 mod add;
+#[cfg(feature = "blas")]
+mod blas;
+pub mod block;
+pub mod cholesky;
+mod condition;
+mod construct;
+mod div;
+#[cfg(feature = "nalgebra")]
+mod interop_nalgebra;
+#[cfg(feature = "ndarray")]
+mod interop_ndarray;
+pub mod iter;
+mod kron;
+pub mod lazy;
+pub mod lu;
+mod map;
 pub mod mat;
 pub mod mat_view;
-mod mul;
+pub mod mul;
+mod neg;
+mod norm;
+pub mod out_of_core;
+pub mod permutation;
+pub mod random;
+mod reduce;
+mod reshape;
+mod riccati;
+pub mod scalar;
+mod schur;
+#[cfg(feature = "serde")]
+mod serialize;
 mod simd;
+pub mod solve;
 mod sub;
-mod transpose;
+mod svd;
+mod sylvester;
+pub mod transpose;
@@ -1,12 +1,35 @@
 use super::mat::Matrix;
+use aligned_vec::{AVec, CACHELINE_ALIGN};
 use std::ops::{Add, Mul, Sub};
 
+// Element types of matrix products. The `blas` feature recognises the types it can hand to
+// `?gemm` by `TypeId`, which needs `'static`, so only then does this ask for it.
+#[cfg(feature = "blas")]
+pub trait ProductElement: 'static {}
+#[cfg(feature = "blas")]
+impl<T: 'static> ProductElement for T {}
+#[cfg(not(feature = "blas"))]
+pub trait ProductElement {}
+#[cfg(not(feature = "blas"))]
+impl<T> ProductElement for T {}
+
 fn multiply_matrices<T>(lhs: &Matrix<T>, rhs: &Matrix<T>) -> Matrix<T>
 where
-    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default + ProductElement,
 {
     assert_eq!(lhs.cols(), rhs.rows(), "Incompatible matrix dimensions");
 
+    #[cfg(feature = "blas")]
+    if let Some(product) = super::blas::multiply(lhs, rhs) {
+        return product;
+    }
+    multiply_native(lhs, rhs)
+}
+
+pub(crate) fn multiply_native<T>(lhs: &Matrix<T>, rhs: &Matrix<T>) -> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+{
     let rows = lhs.rows();
     let cols = rhs.cols();
     let mut result: Matrix<T> = Matrix::empty(rows, cols);
@@ -22,6 +45,17 @@ where
     result
 }
 
+#[cfg(feature = "blas")]
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+{
+    pub fn multiply_native(&self, rhs: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(self.cols(), rhs.rows(), "Incompatible matrix dimensions");
+        multiply_native(self, rhs)
+    }
+}
+
 impl<T> Mul<T> for Matrix<T>
 where
     T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
@@ -32,7 +66,10 @@ where
         Matrix {
             rows: self.rows,
             cols: self.cols,
-            data: self.data.into_iter().map(|x| x * rhs.clone()).collect(),
+            data: AVec::from_iter(
+                CACHELINE_ALIGN,
+                self.data.iter().map(|x| x.clone() * rhs.clone()),
+            ),
         }
     }
 }
@@ -50,7 +87,7 @@ where
 
 impl<T> Mul<Matrix<T>> for Matrix<T>
 where
-    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default + ProductElement,
 {
     type Output = Matrix<T>;
 
@@ -61,7 +98,7 @@ where
 
 impl<T> Mul<&Matrix<T>> for Matrix<T>
 where
-    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default + ProductElement,
 {
     type Output = Matrix<T>;
 
@@ -72,7 +109,7 @@ where
 
 impl<T> Mul<Matrix<T>> for &Matrix<T>
 where
-    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default + ProductElement,
 {
     type Output = Matrix<T>;
 
@@ -83,7 +120,7 @@ where
 
 impl<T> Mul<&Matrix<T>> for &Matrix<T>
 where
-    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default + ProductElement,
 {
     type Output = Matrix<T>;
 
@@ -100,7 +137,7 @@ mod tests {
     fn test_matrix_scalar_multiplication() {
         let m = Matrix::new([[1, 2], [3, 4]]);
         let result = m * 2;
-        assert_eq!(result.data, &[2, 4, 6, 8]);
+        assert_eq!(result.data.as_slice(), &[2, 4, 6, 8]);
     }
 
     #[test]
@@ -115,7 +152,7 @@ mod tests {
     fn test_matrix_scalar_multiplication_float() {
         let m = Matrix::new([[1.0, 2.0], [3.0, 4.0]]);
         let result = m * 0.5;
-        assert_eq!(result.data, vec![0.5, 1.0, 1.5, 2.0]);
+        assert_eq!(result.data.as_slice(), vec![0.5, 1.0, 1.5, 2.0]);
     }
 
     #[test]
@@ -123,7 +160,7 @@ mod tests {
         let m1 = Matrix::new([[1, 2], [3, 4]]);
         let m2 = Matrix::new([[5, 6], [7, 8]]);
         let result = m1 * m2;
-        assert_eq!(result.data, vec![19, 22, 43, 50]);
+        assert_eq!(result.data.as_slice(), vec![19, 22, 43, 50]);
     }
 
     #[test]
@@ -148,7 +185,7 @@ mod tests {
         let m1 = Matrix::new([[1, 2], [3, 4]]);
         let m2 = Matrix::new([[5, 6], [7, 8]]);
         let result = &m1 * &m2;
-        assert_eq!(result.data, vec![19, 22, 43, 50]);
+        assert_eq!(result.data.as_slice(), vec![19, 22, 43, 50]);
     }
 
     #[test]
@@ -156,7 +193,7 @@ mod tests {
         let m1 = Matrix::new([[1, 2], [3, 4]]);
         let m2 = Matrix::new([[5, 6], [7, 8]]);
         let result = m1 * &m2;
-        assert_eq!(result.data, vec![19, 22, 43, 50]);
+        assert_eq!(result.data.as_slice(), vec![19, 22, 43, 50]);
     }
 
     #[test]
@@ -164,6 +201,6 @@ mod tests {
         let m1 = Matrix::new([[1, 2], [3, 4]]);
         let m2 = Matrix::new([[5, 6], [7, 8]]);
         let result = &m1 * m2;
-        assert_eq!(result.data, vec![19, 22, 43, 50]);
+        assert_eq!(result.data.as_slice(), vec![19, 22, 43, 50]);
     }
 }
@@ -0,0 +1,45 @@
+use super::mat::Matrix;
+use std::ops::{Add, Mul, Neg, Sub};
+
+impl<T> Neg for Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Neg<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn neg(mut self) -> Self::Output {
+        self.map_inplace(|x| *x = -x.clone());
+        self
+    }
+}
+
+impl<T> Neg for &Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Neg<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Self::Output {
+        self.map(|x| -x.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex32 as c32;
+
+    #[test]
+    fn test_negate_matrix() {
+        let m = Matrix::new([[1, -2], [3, 0]]);
+        let result = -m;
+        assert_eq!(result.data.as_slice(), vec![-1, 2, -3, 0]);
+    }
+
+    #[test]
+    fn test_negate_complex_matrix_ref() {
+        let m = Matrix::new([[c32::new(1.0, -2.0)]]);
+        let result = -&m;
+        assert_eq!(result[(0, 0)], c32::new(-1.0, 2.0));
+    }
+}
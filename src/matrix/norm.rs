@@ -0,0 +1,397 @@
+use super::{mat::Matrix, mat_view::MatrixView, svd::singular_values};
+use num::complex;
+use num_traits::{Float, One, Zero};
+use rayon::prelude::*;
+
+const PARALLEL_THRESHOLD: usize = 512 * 512; // Minimum elements for rayon to be worth it
+
+impl<T> Matrix<T>
+where
+    T: complex::ComplexFloat + Send + Sync,
+    T::Real: Send + Sync,
+{
+    pub fn norm_fro(&self) -> T::Real {
+        norm_fro(&self.data, self.rows, self.cols, self.cols)
+    }
+
+    // Maximum absolute column sum
+    pub fn norm_1(&self) -> T::Real {
+        norm_1(&self.data, self.rows, self.cols, self.cols)
+    }
+
+    // Maximum absolute row sum
+    pub fn norm_inf(&self) -> T::Real {
+        norm_inf(&self.data, self.rows, self.cols, self.cols)
+    }
+
+    // Largest absolute entry
+    pub fn norm_max(&self) -> T::Real {
+        norm_max(&self.data, self.rows, self.cols, self.cols)
+    }
+
+    // Entry-wise p-norm, treating the matrix as one long vector
+    pub fn norm_p(&self, p: T::Real) -> T::Real {
+        norm_p(&self.data, self.rows, self.cols, self.cols, p)
+    }
+
+    // Spectral norm, the largest singular value
+    pub fn norm_2(&self) -> T::Real {
+        largest(&self.singular_values())
+    }
+
+    // Nuclear (trace) norm, the sum of the singular values
+    pub fn norm_nuclear(&self) -> T::Real {
+        total(&self.singular_values())
+    }
+}
+
+impl<'a, T> MatrixView<'a, T>
+where
+    T: complex::ComplexFloat + Send + Sync,
+    T::Real: Send + Sync,
+{
+    pub fn norm_fro(&self) -> T::Real {
+        norm_fro(self.data, self.rows, self.cols, self.stride)
+    }
+
+    pub fn norm_1(&self) -> T::Real {
+        norm_1(self.data, self.rows, self.cols, self.stride)
+    }
+
+    pub fn norm_inf(&self) -> T::Real {
+        norm_inf(self.data, self.rows, self.cols, self.stride)
+    }
+
+    pub fn norm_max(&self) -> T::Real {
+        norm_max(self.data, self.rows, self.cols, self.stride)
+    }
+
+    pub fn norm_p(&self, p: T::Real) -> T::Real {
+        norm_p(self.data, self.rows, self.cols, self.stride, p)
+    }
+
+    pub fn norm_2(&self) -> T::Real {
+        largest(&singular_values(
+            self.data,
+            self.rows,
+            self.cols,
+            self.stride,
+        ))
+    }
+
+    pub fn norm_nuclear(&self) -> T::Real {
+        total(&singular_values(
+            self.data,
+            self.rows,
+            self.cols,
+            self.stride,
+        ))
+    }
+}
+
+fn row<T>(data: &[T], cols: usize, stride: usize, i: usize) -> &[T] {
+    // Zero-width views may borrow an empty slice whatever their stride
+    if cols == 0 {
+        return &[];
+    }
+    &data[i * stride..i * stride + cols]
+}
+
+// Running `scale * sqrt(sumsq)` as in LAPACK's xLASSQ: every term is divided by the largest
+// magnitude seen so far before squaring, so nothing overflows or underflows prematurely
+#[derive(Clone, Copy)]
+struct ScaledSumSquares<R> {
+    scale: R,
+    sumsq: R,
+}
+
+impl<R: Float> ScaledSumSquares<R> {
+    fn new() -> Self {
+        ScaledSumSquares {
+            scale: R::zero(),
+            sumsq: R::one(),
+        }
+    }
+
+    fn push(&mut self, a: R) {
+        if a.is_nan() {
+            self.sumsq = a;
+        } else if a.is_infinite() {
+            // inf / inf would be NaN below; the sum is infinite unless a NaN was already seen
+            self.scale = a;
+            if !self.sumsq.is_nan() {
+                self.sumsq = R::one();
+            }
+        } else if a > self.scale {
+            let ratio = self.scale / a;
+            self.sumsq = R::one() + self.sumsq * ratio * ratio;
+            self.scale = a;
+        } else if a > R::zero() {
+            let ratio = a / self.scale;
+            self.sumsq = self.sumsq + ratio * ratio;
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        let (big, small) = if self.scale >= other.scale {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if big.scale.is_infinite() {
+            let sumsq = if big.sumsq.is_nan() || small.sumsq.is_nan() {
+                R::nan()
+            } else {
+                R::one()
+            };
+            return ScaledSumSquares {
+                scale: big.scale,
+                sumsq,
+            };
+        }
+        if small.scale == R::zero() {
+            return ScaledSumSquares {
+                scale: big.scale,
+                sumsq: big.sumsq + small.sumsq - R::one(),
+            };
+        }
+
+        let ratio = small.scale / big.scale;
+        ScaledSumSquares {
+            scale: big.scale,
+            sumsq: big.sumsq + small.sumsq * ratio * ratio,
+        }
+    }
+
+    fn value(&self) -> R {
+        self.scale * self.sumsq.sqrt()
+    }
+}
+
+fn row_sum_squares<T>(row: &[T]) -> ScaledSumSquares<T::Real>
+where
+    T: complex::ComplexFloat,
+{
+    let mut ssq = ScaledSumSquares::new();
+    // Real and imaginary parts go in separately, |z| itself could overflow
+    for x in row {
+        ssq.push(Float::abs(x.re()));
+        ssq.push(Float::abs(x.im()));
+    }
+    ssq
+}
+
+fn norm_fro<T>(data: &[T], rows: usize, cols: usize, stride: usize) -> T::Real
+where
+    T: complex::ComplexFloat + Sync,
+    T::Real: Send + Sync,
+{
+    let rows_ssq = |i| row_sum_squares(row(data, cols, stride, i));
+    let empty = ScaledSumSquares::new;
+
+    let ssq = if rows * cols >= PARALLEL_THRESHOLD {
+        (0..rows)
+            .into_par_iter()
+            .map(rows_ssq)
+            .reduce(empty, ScaledSumSquares::merge)
+    } else {
+        (0..rows)
+            .map(rows_ssq)
+            .fold(empty(), ScaledSumSquares::merge)
+    };
+
+    ssq.value()
+}
+
+pub(crate) fn norm_1<T>(data: &[T], rows: usize, cols: usize, stride: usize) -> T::Real
+where
+    T: complex::ComplexFloat,
+{
+    let mut sums = vec![T::Real::zero(); cols];
+    for i in 0..rows {
+        sums.iter_mut()
+            .zip(row(data, cols, stride, i))
+            .for_each(|(s, x)| *s = *s + x.abs());
+    }
+    largest(&sums)
+}
+
+fn norm_inf<T>(data: &[T], rows: usize, cols: usize, stride: usize) -> T::Real
+where
+    T: complex::ComplexFloat + Sync,
+    T::Real: Send + Sync,
+{
+    let row_sum = |i| {
+        row(data, cols, stride, i)
+            .iter()
+            .fold(T::Real::zero(), |s, x| s + x.abs())
+    };
+
+    if rows * cols >= PARALLEL_THRESHOLD {
+        (0..rows)
+            .into_par_iter()
+            .map(row_sum)
+            .reduce(T::Real::zero, Float::max)
+    } else {
+        (0..rows).map(row_sum).fold(T::Real::zero(), Float::max)
+    }
+}
+
+fn norm_max<T>(data: &[T], rows: usize, cols: usize, stride: usize) -> T::Real
+where
+    T: complex::ComplexFloat + Sync,
+    T::Real: Send + Sync,
+{
+    let row_max = |i| {
+        row(data, cols, stride, i)
+            .iter()
+            .fold(T::Real::zero(), |m, x| Float::max(m, x.abs()))
+    };
+
+    if rows * cols >= PARALLEL_THRESHOLD {
+        (0..rows)
+            .into_par_iter()
+            .map(row_max)
+            .reduce(T::Real::zero, Float::max)
+    } else {
+        (0..rows).map(row_max).fold(T::Real::zero(), Float::max)
+    }
+}
+
+fn norm_p<T>(data: &[T], rows: usize, cols: usize, stride: usize, p: T::Real) -> T::Real
+where
+    T: complex::ComplexFloat + Sync,
+    T::Real: Send + Sync,
+{
+    assert!(p >= T::Real::one(), "p must be at least 1");
+
+    if p.is_infinite() {
+        return norm_max(data, rows, cols, stride);
+    }
+    if p == T::Real::one() + T::Real::one() {
+        return norm_fro(data, rows, cols, stride);
+    }
+
+    // Dividing by the largest magnitude keeps |x|^p within range
+    let scale = norm_max(data, rows, cols, stride);
+    if scale == T::Real::zero() || !scale.is_finite() {
+        return scale;
+    }
+
+    let sum = (0..rows)
+        .flat_map(|i| row(data, cols, stride, i))
+        .fold(T::Real::zero(), |s, x| s + (x.abs() / scale).powf(p));
+    scale * sum.powf(p.recip())
+}
+
+fn largest<R: Float>(values: &[R]) -> R {
+    values.iter().fold(R::zero(), |m, &x| m.max(x))
+}
+
+fn total<R: Float>(values: &[R]) -> R {
+    values.iter().fold(R::zero(), |s, &x| s + x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::{Complex32 as c32, Complex64 as c64};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() <= 1e-12 * expected.abs().max(1.0),
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn test_basic_norms() {
+        let m = Matrix::new([[1.0, -2.0], [-3.0, 4.0]]);
+        assert_close(m.norm_fro(), 30.0f64.sqrt());
+        assert_close(m.norm_1(), 6.0);
+        assert_close(m.norm_inf(), 7.0);
+        assert_close(m.norm_max(), 4.0);
+        assert_close(m.norm_p(1.0), 10.0);
+        assert_close(m.norm_p(3.0), 100.0f64.cbrt());
+        assert_close(m.norm_p(f64::INFINITY), 4.0);
+    }
+
+    #[test]
+    fn test_spectral_and_nuclear_norms() {
+        let m = Matrix::new([[3.0, 0.0], [4.0, 5.0]]);
+        assert_close(m.norm_2(), 45.0f64.sqrt());
+        assert_close(m.norm_nuclear(), 45.0f64.sqrt() + 5.0f64.sqrt());
+    }
+
+    #[test]
+    fn test_complex_norms_are_real() {
+        let m = Matrix::new([[c64::new(3.0, 4.0), c64::new(0.0, 1.0)]]);
+        let fro: f64 = m.norm_fro();
+        assert_close(fro, 26.0f64.sqrt());
+        assert_close(m.norm_inf(), 6.0);
+        assert_close(m.norm_1(), 5.0);
+        assert_close(m.norm_2(), 26.0f64.sqrt());
+    }
+
+    #[test]
+    fn test_norm_fro_does_not_overflow() {
+        let m = Matrix::new([[3e30f32, 4e30f32], [0.0, 0.0]]);
+        let norm = m.norm_fro();
+        assert!((norm - 5e30).abs() / 5e30 < 1e-6);
+
+        let m = Matrix::new([[c32::new(3e30, 4e30)]]);
+        assert!((m.norm_fro() - 5e30).abs() / 5e30 < 1e-6);
+    }
+
+    #[test]
+    fn test_norm_fro_does_not_underflow() {
+        let m = Matrix::new([[3e-30f32, 4e-30f32]]);
+        let norm = m.norm_fro();
+        assert!((norm - 5e-30).abs() / 5e-30 < 1e-6);
+    }
+
+    #[test]
+    fn test_norm_fro_infinite_entries() {
+        let inf = f64::INFINITY;
+        assert_eq!(Matrix::new([[inf, inf]]).norm_fro(), inf);
+        assert_eq!(Matrix::new([[1.0, inf, 2.0, -inf]]).norm_fro(), inf);
+        assert!(Matrix::new([[inf, f64::NAN, inf]]).norm_fro().is_nan());
+
+        // Row sums merged in parallel, each infinite
+        let size = 1024;
+        let m = Matrix::from_fn(size, size, |i, j| if i == j { inf } else { 1.0 });
+        assert_eq!(m.norm_fro(), inf);
+        let mut m = m;
+        m.data[size + 3] = f64::NAN;
+        assert!(m.norm_fro().is_nan());
+    }
+
+    #[test]
+    fn test_norm_fro_large_matrix() {
+        let size = 1024;
+        let m = Matrix::from_vec(size, size, vec![2.0; size * size]);
+        assert_close(m.norm_fro(), 2.0 * size as f64);
+    }
+
+    #[test]
+    fn test_view_norms() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        let view = m.view(1..3, 0..2);
+        assert_close(view.norm_fro(), (16.0f64 + 25.0 + 49.0 + 64.0).sqrt());
+        assert_close(view.norm_1(), 13.0);
+        assert_close(view.norm_inf(), 15.0);
+        assert_close(view.norm_max(), 8.0);
+        assert_close(view.norm_2(), view.to_matrix().norm_2());
+    }
+
+    #[test]
+    fn test_zero_width_view_norms() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let view = m.view(0..2, 1..1);
+        assert_eq!(view.norm_fro(), 0.0);
+        assert_eq!(view.norm_1(), 0.0);
+        assert_eq!(view.norm_inf(), 0.0);
+        assert_eq!(view.norm_max(), 0.0);
+        assert_eq!(view.norm_p(3.0), 0.0);
+    }
+}
@@ -0,0 +1,166 @@
+use super::mat_view::{MatrixView, MatrixViewMut};
+use rayon::prelude::*;
+use std::ops::{Add, Mul, Sub};
+
+// Tiled kernels for operands too large to hold in memory, typically views of mapped files. Each
+// works through square tiles copied into buffers of at most `budget` bytes together, so the pages
+// in use at any time stay bounded no matter how large the operands are. Work within a tile is
+// spread over the rayon pool.
+
+const MIN_TILE: usize = 8;
+
+// Largest tile edge such that `buffers` square tiles of `T` fit in `budget` bytes
+fn tile_edge<T>(budget: usize, buffers: usize) -> usize {
+    let elements = budget / std::mem::size_of::<T>().max(1) / buffers;
+    let edge = elements.isqrt();
+    assert!(
+        edge >= MIN_TILE,
+        "Memory budget of {budget} bytes is too small for out-of-core tiles"
+    );
+    edge
+}
+
+// Copies the tile at `rows` x `cols` of `src` into `buf`, row by row
+fn load_tile<T>(
+    src: &MatrixView<T>,
+    rows: std::ops::Range<usize>,
+    cols: std::ops::Range<usize>,
+    buf: &mut Vec<T>,
+) where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    buf.clear();
+    for i in rows {
+        buf.extend_from_slice(&src.row_slice(i)[cols.clone()]);
+    }
+}
+
+// c = a * b
+pub fn multiply<T>(a: &MatrixView<T>, b: &MatrixView<T>, c: &mut MatrixViewMut<T>, budget: usize)
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default + Send + Sync,
+{
+    assert_eq!(a.cols, b.rows, "Incompatible matrix dimensions");
+    assert_eq!(
+        (c.rows, c.cols),
+        (a.rows, b.cols),
+        "Incompatible matrix dimensions"
+    );
+
+    // One tile each of a, b and the accumulated result
+    let edge = tile_edge::<T>(budget, 3);
+    let (mut a_tile, mut b_tile) = (Vec::new(), Vec::new());
+    let mut acc = Vec::new();
+
+    for i0 in (0..c.rows).step_by(edge) {
+        let i1 = (i0 + edge).min(c.rows);
+        for j0 in (0..c.cols).step_by(edge) {
+            let j1 = (j0 + edge).min(c.cols);
+            let width = j1 - j0;
+            acc.clear();
+            acc.resize((i1 - i0) * width, T::default());
+
+            for k0 in (0..a.cols).step_by(edge) {
+                let k1 = (k0 + edge).min(a.cols);
+                let depth = k1 - k0;
+                load_tile(a, i0..i1, k0..k1, &mut a_tile);
+                load_tile(b, k0..k1, j0..j1, &mut b_tile);
+
+                acc.par_chunks_mut(width)
+                    .zip(a_tile.par_chunks(depth))
+                    .for_each(|(acc_row, a_row)| {
+                        for (a_ik, b_row) in a_row.iter().zip(b_tile.chunks(width)) {
+                            for (acc_ij, b_kj) in acc_row.iter_mut().zip(b_row) {
+                                *acc_ij = acc_ij.clone() + a_ik.clone() * b_kj.clone();
+                            }
+                        }
+                    });
+            }
+
+            for (i, acc_row) in (i0..i1).zip(acc.chunks(width)) {
+                c.row_slice_mut(i)[j0..j1].clone_from_slice(acc_row);
+            }
+        }
+    }
+}
+
+// dst = src^T
+pub fn transpose<T>(src: &MatrixView<T>, dst: &mut MatrixViewMut<T>, budget: usize)
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    assert_eq!(
+        (dst.rows, dst.cols),
+        (src.cols, src.rows),
+        "Incompatible matrix dimensions"
+    );
+
+    let edge = tile_edge::<T>(budget, 1);
+    let mut tile = Vec::new();
+
+    for i0 in (0..src.rows).step_by(edge) {
+        let i1 = (i0 + edge).min(src.rows);
+        let height = i1 - i0;
+        for j0 in (0..src.cols).step_by(edge) {
+            let j1 = (j0 + edge).min(src.cols);
+            load_tile(src, i0..i1, j0..j1, &mut tile);
+
+            // Rows j0..j1 of the destination, each receiving one column of the tile
+            let stride = dst.stride;
+            let start = j0 * stride;
+            let end = (j1 - 1) * stride + dst.cols;
+            let width = j1 - j0;
+            dst.data[start..end]
+                .par_chunks_mut(stride)
+                .enumerate()
+                .for_each(|(j, dst_row)| {
+                    for (i, x) in (0..height).zip(&mut dst_row[i0..i1]) {
+                        *x = tile[i * width + j].clone();
+                    }
+                });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::mat::Matrix;
+
+    // Room for 8x8 tiles, so the shapes below span several tiles with ragged edges
+    const SMALL_BUDGET: usize = 3 * 8 * 8 * 8;
+
+    #[test]
+    fn test_multiply_matches_in_memory() {
+        let a = Matrix::from_fn(21, 13, |i, j| (i * 13 + j) as f64 * 0.25 - 7.0);
+        let b = Matrix::from_fn(13, 17, |i, j| (i as f64 - j as f64) * 0.5);
+        let mut c = Matrix::zeros(21, 17);
+        super::multiply(
+            &a.view(0..21, 0..13),
+            &b.view(0..13, 0..17),
+            &mut c.view_mut(0..21, 0..17),
+            SMALL_BUDGET,
+        );
+        assert_eq!(c, &a * &b);
+    }
+
+    #[test]
+    fn test_transpose_matches_in_memory() {
+        let m = Matrix::from_fn(19, 30, |i, j| (i * 30 + j) as i64);
+        let mut t = Matrix::zeros(40, 40);
+        super::transpose(
+            &m.view(0..19, 0..30),
+            &mut t.view_mut(5..35, 2..21),
+            SMALL_BUDGET,
+        );
+        assert_eq!(t.view(5..35, 2..21).to_matrix(), m.transpose());
+        assert_eq!(t[(4, 2)], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "too small")]
+    fn test_tiny_budget() {
+        let m = Matrix::from_fn(2, 2, |i, j| (i + j) as f32);
+        let mut t = Matrix::zeros(2, 2);
+        super::transpose(&m.view(0..2, 0..2), &mut t.view_mut(0..2, 0..2), 16);
+    }
+}
@@ -0,0 +1,209 @@
+use super::mat::Matrix;
+use crate::error::LinalgError;
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use num_traits::{One, Zero};
+use std::ops::{Add, Mul, Sub};
+
+// Permutation matrix P stored as the indices it gathers: row i of P A is row `indices[i]` of A,
+// i.e. P[i, indices[i]] = 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permutation {
+    indices: Vec<usize>,
+}
+
+impl Permutation {
+    pub fn identity(n: usize) -> Self {
+        Permutation {
+            indices: (0..n).collect(),
+        }
+    }
+
+    pub fn from_indices(indices: Vec<usize>) -> Result<Self, LinalgError> {
+        let mut seen = vec![false; indices.len()];
+        for (index, &i) in indices.iter().enumerate() {
+            if i >= seen.len() || seen[i] {
+                return Err(LinalgError::InvalidPermutation { index });
+            }
+            seen[i] = true;
+        }
+        Ok(Permutation { indices })
+    }
+
+    // From LAPACK style pivots, where step k swapped row k with row `swaps[k]`. A step that
+    // names a row outside 0..n is an `InvalidPermutation` error at that step.
+    pub fn from_swaps(n: usize, swaps: &[usize]) -> Result<Self, LinalgError> {
+        let mut indices: Vec<usize> = (0..n).collect();
+        for (k, &p) in swaps.iter().enumerate() {
+            if k >= n || p >= n {
+                return Err(LinalgError::InvalidPermutation { index: k });
+            }
+            indices.swap(k, p);
+        }
+        Ok(Permutation { indices })
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut indices = vec![0; self.indices.len()];
+        for (i, &p) in self.indices.iter().enumerate() {
+            indices[p] = i;
+        }
+        Permutation { indices }
+    }
+
+    // det(P), +1 for an even permutation and -1 for an odd one
+    pub fn sign(&self) -> i32 {
+        let mut visited = vec![false; self.indices.len()];
+        let mut transpositions = 0;
+        for start in 0..self.indices.len() {
+            let mut i = start;
+            let mut length = 0usize;
+            while !visited[i] {
+                visited[i] = true;
+                i = self.indices[i];
+                length += 1;
+            }
+            transpositions += length.saturating_sub(1);
+        }
+        if transpositions % 2 == 0 {
+            1
+        } else {
+            -1
+        }
+    }
+
+    pub fn to_matrix<T>(&self) -> Matrix<T>
+    where
+        T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default + Zero + One,
+    {
+        let n = self.indices.len();
+        let mut m = Matrix::zeros(n, n);
+        for (i, &p) in self.indices.iter().enumerate() {
+            m[(i, p)] = T::one();
+        }
+        m
+    }
+
+    // Gathers `width`-element rows of `data` in place, row i becomes old row `indices[i]`
+    pub(crate) fn gather_rows<T: Clone>(&self, data: &mut [T], width: usize) {
+        let source = data.to_vec();
+        for (i, &p) in self.indices.iter().enumerate() {
+            data[i * width..(i + 1) * width].clone_from_slice(&source[p * width..(p + 1) * width]);
+        }
+    }
+
+    // The inverse of `gather_rows`, old row i ends up at row `indices[i]`
+    pub(crate) fn scatter_rows<T: Clone>(&self, data: &mut [T], width: usize) {
+        let source = data.to_vec();
+        for (i, &p) in self.indices.iter().enumerate() {
+            data[p * width..(p + 1) * width].clone_from_slice(&source[i * width..(i + 1) * width]);
+        }
+    }
+}
+
+// P A, permutes the rows of A
+impl<T> Mul<&Matrix<T>> for &Permutation
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: &Matrix<T>) -> Self::Output {
+        assert_eq!(self.len(), rhs.rows, "Incompatible matrix dimensions");
+
+        let cols = rhs.cols;
+        Matrix {
+            rows: rhs.rows,
+            cols,
+            data: AVec::from_iter(
+                CACHELINE_ALIGN,
+                self.indices
+                    .iter()
+                    .flat_map(|&p| rhs.data[p * cols..(p + 1) * cols].iter().cloned()),
+            ),
+        }
+    }
+}
+
+// A P, permutes the columns of A: column `indices[j]` of A P is column j of A
+impl<T> Mul<&Permutation> for &Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: &Permutation) -> Self::Output {
+        assert_eq!(self.cols, rhs.len(), "Incompatible matrix dimensions");
+
+        let inverse = rhs.inverse();
+        let cols = self.cols;
+        Matrix {
+            rows: self.rows,
+            cols,
+            data: AVec::from_iter(
+                CACHELINE_ALIGN,
+                (0..self.rows)
+                    .flat_map(|i| inverse.indices.iter().map(move |&q| i * cols + q))
+                    .map(|k| self.data[k].clone()),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permutation_matches_matrix_product() {
+        let p = Permutation::from_indices(vec![2, 0, 1]).unwrap();
+        let a = Matrix::from_fn(3, 3, |i, j| (10 * i + j) as i64);
+        let pm: Matrix<i64> = p.to_matrix();
+
+        assert_eq!(&p * &a, &pm * &a);
+        assert_eq!(&a * &p, &a * &pm);
+        assert_eq!((&p * &a).data[..3], [20, 21, 22]);
+        assert_eq!(&p.inverse() * &(&p * &a), a);
+    }
+
+    #[test]
+    fn test_invalid_permutation() {
+        assert_eq!(
+            Permutation::from_indices(vec![0, 2, 0]).unwrap_err(),
+            LinalgError::InvalidPermutation { index: 2 }
+        );
+        assert!(Permutation::from_indices(vec![1, 0]).is_ok());
+    }
+
+    #[test]
+    fn test_from_swaps_and_sign() {
+        let p = Permutation::from_swaps(4, &[2, 2, 3, 3]).unwrap();
+        assert_eq!(p.indices(), &[2, 0, 3, 1]);
+        assert_eq!(p.sign(), -1);
+        assert_eq!(Permutation::identity(3).sign(), 1);
+        assert_eq!(Permutation::from_indices(vec![1, 2, 0]).unwrap().sign(), 1);
+    }
+
+    #[test]
+    fn test_from_swaps_out_of_range() {
+        assert_eq!(
+            Permutation::from_swaps(3, &[1, 3]),
+            Err(LinalgError::InvalidPermutation { index: 1 })
+        );
+        assert_eq!(
+            Permutation::from_swaps(2, &[1, 1, 2]),
+            Err(LinalgError::InvalidPermutation { index: 2 })
+        );
+    }
+}
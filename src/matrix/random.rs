@@ -0,0 +1,386 @@
+use super::{mat::Matrix, svd::from_real};
+use aligned_vec::{avec, AVec, CACHELINE_ALIGN};
+use num::complex::{self, Complex};
+use num_traits::{Float, NumCast, One, Zero};
+use rand::{
+    distributions::{uniform::SampleUniform, Distribution, Uniform},
+    rngs::StdRng,
+    Rng, SeedableRng,
+};
+use rayon::prelude::*;
+use std::ops::{Add, Mul, Sub};
+
+const PARALLEL_THRESHOLD: usize = 512 * 512; // Minimum elements for rayon to be worth it
+const CHUNK_SIZE: usize = 64 * 1024; // Elements drawn from each seeded sub-generator
+
+// Standard normal distribution. Complex samples are circularly symmetric with E|z|^2 = 1, i.e.
+// real and imaginary parts are independent N(0, 1/2).
+#[derive(Debug, Clone, Copy)]
+pub struct StandardNormal;
+
+// Marsaglia's polar method, yields two independent N(0, 1) samples
+fn normal_pair<R: Rng + ?Sized>(rng: &mut R) -> (f64, f64) {
+    loop {
+        let u = rng.gen::<f64>() * 2.0 - 1.0;
+        let v = rng.gen::<f64>() * 2.0 - 1.0;
+        let s = u * u + v * v;
+        if s > 0.0 && s < 1.0 {
+            let factor = (-2.0 * s.ln() / s).sqrt();
+            return (u * factor, v * factor);
+        }
+    }
+}
+
+impl Distribution<f64> for StandardNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        normal_pair(rng).0
+    }
+}
+
+impl Distribution<f32> for StandardNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        normal_pair(rng).0 as f32
+    }
+}
+
+impl Distribution<Complex<f64>> for StandardNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Complex<f64> {
+        let (re, im) = normal_pair(rng);
+        Complex::new(re, im) * std::f64::consts::FRAC_1_SQRT_2
+    }
+}
+
+impl Distribution<Complex<f32>> for StandardNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Complex<f32> {
+        let (re, im) = normal_pair(rng);
+        Complex::new(re as f32, im as f32) * std::f32::consts::FRAC_1_SQRT_2
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default + Send,
+{
+    // Entries drawn row by row from `distribution`. Large matrices are filled in parallel, each
+    // chunk from its own generator seeded off `rng`, so a seeded `rng` still gives the same matrix
+    // no matter how many threads run.
+    pub fn random<D, R>(rows: usize, cols: usize, distribution: &D, rng: &mut R) -> Self
+    where
+        D: Distribution<T> + Sync,
+        R: Rng + ?Sized,
+    {
+        let len = rows * cols;
+        if len < PARALLEL_THRESHOLD {
+            return Matrix {
+                rows,
+                cols,
+                data: AVec::from_iter(CACHELINE_ALIGN, (0..len).map(|_| distribution.sample(rng))),
+            };
+        }
+
+        let generators: Vec<StdRng> = (0..len.div_ceil(CHUNK_SIZE))
+            .map(|_| StdRng::from_rng(&mut *rng).expect("Failed to seed generator"))
+            .collect();
+
+        let mut data = avec![T::default(); len];
+        data.par_chunks_mut(CHUNK_SIZE)
+            .zip(generators)
+            .for_each(|(chunk, mut generator)| {
+                chunk
+                    .iter_mut()
+                    .for_each(|x| *x = distribution.sample(&mut generator));
+            });
+
+        Matrix { rows, cols, data }
+    }
+
+    // Entries uniform in [low, high)
+    pub fn random_uniform<R>(rows: usize, cols: usize, low: T, high: T, rng: &mut R) -> Self
+    where
+        T: SampleUniform,
+        T::Sampler: Sync,
+        R: Rng + ?Sized,
+    {
+        Self::random(rows, cols, &Uniform::new(low, high), rng)
+    }
+
+    // Standard normal entries, complex normal for complex T
+    pub fn random_normal<R>(rows: usize, cols: usize, rng: &mut R) -> Self
+    where
+        StandardNormal: Distribution<T>,
+        R: Rng + ?Sized,
+    {
+        Self::random(rows, cols, &StandardNormal, rng)
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: complex::ComplexFloat + Default + Send + Sync,
+    T::Real: Send + Sync,
+    StandardNormal: Distribution<T>,
+{
+    // Haar distributed orthogonal matrix, or unitary for complex T
+    pub fn random_orthogonal<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Self {
+        let q = haar_columns(n, rng);
+        let mut result = Matrix {
+            rows: n,
+            cols: n,
+            data: avec![T::zero(); n * n],
+        };
+        for (j, column) in q.iter().enumerate() {
+            for (i, &x) in column.iter().enumerate() {
+                result.data[i * n + j] = x;
+            }
+        }
+        result
+    }
+
+    // Hermitian (symmetric for real T) positive definite, G G^H / n + I with G standard normal.
+    // Its eigenvalues lie roughly in [1, 5], so it is always comfortably conditioned.
+    pub fn random_positive_definite<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Self {
+        let g = Self::random_normal(n, n, rng);
+        let columns = columns_of(&g);
+        let weight = T::Real::one() / to_real::<T>(n.max(1));
+        let mut result = hermitian_product(&columns, &vec![weight; n]);
+        for i in 0..n {
+            result.data[i * n + i] = result.data[i * n + i] + T::one();
+        }
+        result
+    }
+
+    // U S V^H with U, V Haar and singular values spaced geometrically from 1 down to 1 / cond,
+    // so the 2-norm condition number is `cond`
+    pub fn random_with_condition<R: Rng + ?Sized>(n: usize, cond: T::Real, rng: &mut R) -> Self {
+        assert!(
+            cond >= T::Real::one(),
+            "Condition number must be at least 1"
+        );
+
+        let last = to_real::<T>(n.saturating_sub(1).max(1));
+        let sigma: Vec<T::Real> = (0..n).map(|k| cond.powf(-to_real::<T>(k) / last)).collect();
+
+        let u = haar_columns(n, rng);
+        let v = haar_columns(n, rng);
+        let mut result = Matrix {
+            rows: n,
+            cols: n,
+            data: avec![T::zero(); n * n],
+        };
+        fill_rows(&mut result, |i, j| {
+            (0..n).fold(T::zero(), |s, k| {
+                s + u[k][i] * from_real::<T>(sigma[k]) * v[k][j].conj()
+            })
+        });
+        result
+    }
+
+    // Hermitian (symmetric for real T) matrix Q diag(eigenvalues) Q^H with Q Haar
+    pub fn random_with_spectrum<R: Rng + ?Sized>(eigenvalues: &[T::Real], rng: &mut R) -> Self {
+        let q = haar_columns(eigenvalues.len(), rng);
+        hermitian_product(&q, eigenvalues)
+    }
+}
+
+fn to_real<T: complex::ComplexFloat>(n: usize) -> T::Real {
+    <T::Real as NumCast>::from(n).unwrap()
+}
+
+fn columns_of<T: complex::ComplexFloat>(m: &Matrix<T>) -> Vec<Vec<T>> {
+    (0..m.cols)
+        .map(|j| (0..m.rows).map(|i| m.data[i * m.cols + j]).collect())
+        .collect()
+}
+
+// Gram-Schmidt on a standard normal matrix. R ends up with a positive diagonal, which is exactly
+// the normalisation that makes Q Haar distributed (Mezzadri, 2007).
+fn haar_columns<T, R>(n: usize, rng: &mut R) -> Vec<Vec<T>>
+where
+    T: complex::ComplexFloat + Default + Send + Sync,
+    StandardNormal: Distribution<T>,
+    R: Rng + ?Sized,
+{
+    let mut q = columns_of(&Matrix::random_normal(n, n, rng));
+
+    for j in 0..n {
+        let (done, rest) = q.split_at_mut(j);
+        let column = &mut rest[0];
+        // Orthogonalising twice keeps the columns orthonormal to working precision
+        for _ in 0..2 {
+            for previous in done.iter() {
+                let r = previous
+                    .iter()
+                    .zip(column.iter())
+                    .fold(T::zero(), |s, (&p, &c)| s + p.conj() * c);
+                column
+                    .iter_mut()
+                    .zip(previous)
+                    .for_each(|(c, &p)| *c = *c - r * p);
+            }
+        }
+        let norm = column
+            .iter()
+            .fold(T::Real::zero(), |s, x| s + x.abs() * x.abs())
+            .sqrt();
+        let inv_norm = from_real::<T>(T::Real::one() / norm);
+        column.iter_mut().for_each(|x| *x = *x * inv_norm);
+    }
+    q
+}
+
+// sum_k w_k q_k q_k^H. Only the lower triangle is computed and then mirrored, so the result is
+// exactly Hermitian.
+fn hermitian_product<T>(q: &[Vec<T>], weights: &[T::Real]) -> Matrix<T>
+where
+    T: complex::ComplexFloat + Send + Sync,
+    T::Real: Sync,
+{
+    let n = q.first().map_or(0, |c| c.len());
+    let mut result = Matrix {
+        rows: n,
+        cols: n,
+        data: avec![T::zero(); n * n],
+    };
+    fill_rows(&mut result, |i, j| {
+        if j > i {
+            return T::zero();
+        }
+        q.iter().zip(weights).fold(T::zero(), |s, (c, &w)| {
+            s + c[i] * from_real::<T>(w) * c[j].conj()
+        })
+    });
+
+    for i in 0..n {
+        result.data[i * n + i] = from_real(result.data[i * n + i].re());
+        for j in i + 1..n {
+            result.data[i * n + j] = result.data[j * n + i].conj();
+        }
+    }
+    result
+}
+
+fn fill_rows<T, F>(m: &mut Matrix<T>, f: F)
+where
+    T: complex::ComplexFloat + Send,
+    F: Fn(usize, usize) -> T + Sync,
+{
+    let cols = m.cols;
+    if cols == 0 {
+        return;
+    }
+    let fill = |(i, row): (usize, &mut [T])| {
+        row.iter_mut().enumerate().for_each(|(j, x)| *x = f(i, j));
+    };
+
+    if m.data.len() >= PARALLEL_THRESHOLD {
+        m.data.par_chunks_mut(cols).enumerate().for_each(fill);
+    } else {
+        m.data.chunks_mut(cols).enumerate().for_each(fill);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+
+    fn assert_unitary(q: &Matrix<c64>) {
+        let n = q.rows();
+        for i in 0..n {
+            for j in 0..n {
+                let dot = (0..n).fold(c64::zero(), |s, k| s + q[(k, i)].conj() * q[(k, j)]);
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((dot - expected).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_seeded_generation_is_reproducible() {
+        let a = Matrix::<f64>::random_normal(8, 5, &mut StdRng::seed_from_u64(7));
+        let b = Matrix::<f64>::random_normal(8, 5, &mut StdRng::seed_from_u64(7));
+        let c = Matrix::<f64>::random_normal(8, 5, &mut StdRng::seed_from_u64(8));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_large_random_is_parallel_and_reproducible() {
+        let size = 1024;
+        let a = Matrix::<f32>::random_uniform(size, size, -1.0, 1.0, &mut StdRng::seed_from_u64(1));
+        let b = Matrix::<f32>::random_uniform(size, size, -1.0, 1.0, &mut StdRng::seed_from_u64(1));
+        assert_eq!(a, b);
+        assert!(a.data.iter().all(|x| (-1.0..1.0).contains(x)));
+        // Chunks must not repeat each other
+        assert_ne!(&a.data[..CHUNK_SIZE], &a.data[CHUNK_SIZE..2 * CHUNK_SIZE]);
+    }
+
+    #[test]
+    fn test_normal_moments() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let m = Matrix::<f64>::random_normal(400, 400, &mut rng);
+        let mean = m.mean().unwrap();
+        let var = m.var(0);
+        assert!(mean.abs() < 0.01);
+        assert!((var - 1.0).abs() < 0.02);
+
+        let z = Matrix::<c64>::random_normal(200, 200, &mut rng);
+        let power = z.data.iter().map(|x| x.norm_sqr()).sum::<f64>() / 40000.0;
+        assert!((power - 1.0).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_random_orthogonal_and_unitary() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let q = Matrix::<f64>::random_orthogonal(6, &mut rng);
+        let sv = q.singular_values();
+        assert!(sv.iter().all(|s| (s - 1.0).abs() < 1e-12));
+
+        assert_unitary(&Matrix::<c64>::random_orthogonal(5, &mut rng));
+    }
+
+    #[test]
+    fn test_random_positive_definite() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let a = Matrix::<f64>::random_positive_definite(10, &mut rng);
+        assert_eq!(a, a.transpose());
+        assert!(a.cholesky().is_ok());
+
+        let h = Matrix::<c64>::random_positive_definite(6, &mut rng);
+        assert!(h.cholesky().is_ok());
+    }
+
+    #[test]
+    fn test_random_with_condition_and_spectrum() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let a = Matrix::<f64>::random_with_condition(8, 1e6, &mut rng);
+        assert!((a.cond() / 1e6 - 1.0).abs() < 1e-6);
+
+        let eigenvalues = [4.0, 3.0, 2.0, 0.5];
+        let h = Matrix::<c64>::random_with_spectrum(&eigenvalues, &mut rng);
+        let trace: c64 = (0..4).map(|i| h[(i, i)]).sum();
+        assert!((trace - 9.5).norm() < 1e-12);
+        // For a Hermitian matrix the singular values are the absolute eigenvalues
+        for (s, e) in h.singular_values().iter().zip(eigenvalues) {
+            assert!((s - e).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_empty_structured_matrices() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let empty = [
+            Matrix::<f64>::random_orthogonal(0, &mut rng),
+            Matrix::<f64>::random_positive_definite(0, &mut rng),
+            Matrix::<f64>::random_with_condition(0, 10.0, &mut rng),
+            Matrix::<f64>::random_with_spectrum(&[], &mut rng),
+        ];
+        for m in empty {
+            assert_eq!((m.rows(), m.cols()), (0, 0));
+        }
+        assert_eq!(
+            Matrix::<c64>::random_positive_definite(0, &mut rng).rows(),
+            0
+        );
+    }
+}
@@ -0,0 +1,733 @@
+use super::{
+    mat::{Axis, Matrix},
+    simd::SimdOps,
+    transpose::TransposeElement,
+};
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use num_traits::{Float, FromPrimitive, One, Zero};
+use rayon::prelude::*;
+use std::ops::{Add, Div, Mul, Sub};
+
+const PAIRWISE_BLOCK: usize = 128; // Elements summed directly, a multiple of every SIMD lane size
+const PARALLEL_THRESHOLD: usize = 512 * 512; // Minimum elements for rayon to be worth it
+const PARALLEL_CHUNK: usize = 64 * 1024; // Elements per rayon task for chunked reductions
+
+impl<T> Matrix<T>
+where
+    T: Copy
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Default
+        + Zero
+        + SimdOps
+        + Send
+        + Sync,
+{
+    // Pairwise summation, the rounding error grows with log(n) rather than n
+    pub fn sum(&self) -> T {
+        pairwise_sum(&self.data, T::has_simd_support())
+    }
+
+    // One sum per row, as a rows x 1 matrix
+    pub fn sum_rows(&self) -> Matrix<T> {
+        self.sum_axis(Axis::Rows)
+    }
+
+    // One sum per column, as a 1 x cols matrix
+    pub fn sum_cols(&self) -> Matrix<T> {
+        self.sum_axis(Axis::Cols)
+    }
+
+    pub fn sum_axis(&self, axis: Axis) -> Matrix<T> {
+        let simd = T::has_simd_support();
+
+        match axis {
+            Axis::Rows => Matrix::from_aligned(
+                self.rows,
+                1,
+                reduce_rows(&self.data, self.rows, self.cols, |row| {
+                    pairwise_sum(row, simd)
+                }),
+            ),
+            Axis::Cols => {
+                let sums = if self.cols == 0 {
+                    Vec::new()
+                } else {
+                    column_sums(&self.data, self.cols)
+                };
+                Matrix::from_aligned(1, self.cols, AVec::from_iter(CACHELINE_ALIGN, sums))
+            }
+        }
+    }
+
+    // None for an empty matrix. Integer means round toward zero, as integer division does.
+    pub fn mean(&self) -> Option<T>
+    where
+        T: FromPrimitive + Div<Output = T>,
+    {
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(self.sum() / from_count(self.data.len()))
+    }
+
+    // Means along an axis of length zero divide by zero: NaN for floats, a panic for integers
+    pub fn mean_axis(&self, axis: Axis) -> Matrix<T>
+    where
+        T: FromPrimitive + Div<Output = T>,
+    {
+        let count: T = match axis {
+            Axis::Rows => from_count(self.cols),
+            Axis::Cols => from_count(self.rows),
+        };
+
+        let mut means = self.sum_axis(axis);
+        means.map_inplace(|x| *x = *x / count);
+        means
+    }
+
+    // The empty product is one
+    pub fn product(&self) -> T
+    where
+        T: One,
+    {
+        let multiply = |chunk: &[T]| chunk.iter().fold(T::one(), |acc, &x| acc * x);
+        if self.data.len() >= PARALLEL_THRESHOLD {
+            self.data
+                .par_chunks(PARALLEL_CHUNK)
+                .map(multiply)
+                .reduce(T::one, |a, b| a * b)
+        } else {
+            multiply(&self.data)
+        }
+    }
+
+    pub fn product_axis(&self, axis: Axis) -> Matrix<T>
+    where
+        T: One,
+    {
+        match axis {
+            Axis::Rows => Matrix::from_aligned(
+                self.rows,
+                1,
+                reduce_rows(&self.data, self.rows, self.cols, |row| {
+                    row.iter().fold(T::one(), |acc, &x| acc * x)
+                }),
+            ),
+            Axis::Cols => {
+                let mut products =
+                    AVec::from_iter(CACHELINE_ALIGN, (0..self.cols).map(|_| T::one()));
+                for row in self.data.chunks_exact(self.cols.max(1)) {
+                    products.iter_mut().zip(row).for_each(|(p, &x)| *p = *p * x);
+                }
+                Matrix::from_aligned(1, self.cols, products)
+            }
+        }
+    }
+
+    // Inclusive running sums along each row (`Axis::Rows`) or down each column (`Axis::Cols`)
+    pub fn cumsum(&self, axis: Axis) -> Matrix<T> {
+        let mut result = self.clone();
+        if self.cols == 0 {
+            return result;
+        }
+
+        match axis {
+            Axis::Rows => {
+                let prefix = |row: &mut [T]| {
+                    for j in 1..row.len() {
+                        row[j] = row[j - 1] + row[j];
+                    }
+                };
+                if self.data.len() >= PARALLEL_THRESHOLD {
+                    result.data.par_chunks_mut(self.cols).for_each(prefix);
+                } else {
+                    result.data.chunks_mut(self.cols).for_each(prefix);
+                }
+            }
+            Axis::Cols => {
+                for i in 1..self.rows {
+                    let (done, rest) = result.data.split_at_mut(i * self.cols);
+                    let previous = &done[(i - 1) * self.cols..];
+                    rest[..self.cols]
+                        .iter_mut()
+                        .zip(previous)
+                        .for_each(|(x, &p)| *x = p + *x);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Float
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Default
+        + SimdOps
+        + TransposeElement
+        + Send
+        + Sync,
+{
+    // Variance with `ddof` delta degrees of freedom: 0 for the population, 1 for a sample
+    pub fn var(&self, ddof: usize) -> T {
+        moments(&self.data).variance(ddof)
+    }
+
+    pub fn var_axis(&self, axis: Axis, ddof: usize) -> Matrix<T> {
+        match axis {
+            Axis::Rows => Matrix::from_aligned(
+                self.rows,
+                1,
+                reduce_rows(&self.data, self.rows, self.cols, |row| {
+                    moments(row).variance(ddof)
+                }),
+            ),
+            Axis::Cols => {
+                let mut variances = self.transpose().var_axis(Axis::Rows, ddof);
+                variances.transpose_in_place();
+                variances
+            }
+        }
+    }
+
+    pub fn std(&self, ddof: usize) -> T {
+        self.var(ddof).sqrt()
+    }
+
+    pub fn std_axis(&self, axis: Axis, ddof: usize) -> Matrix<T> {
+        let mut deviations = self.var_axis(axis, ddof);
+        deviations.map_inplace(|x| *x = x.sqrt());
+        deviations
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Copy
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Default
+        + PartialOrd
+        + SimdOps
+        + TransposeElement
+        + Send
+        + Sync,
+{
+    // Unordered values such as NaN win every comparison, so they propagate to the result
+    pub fn min(&self) -> Option<T> {
+        self.argmin().map(|index| self[index])
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.argmax().map(|index| self[index])
+    }
+
+    // Position of the first minimum, None for an empty matrix
+    pub fn argmin(&self) -> Option<(usize, usize)> {
+        arg_extremum(&self.data, |a, b| a < b).map(|k| (k / self.cols, k % self.cols))
+    }
+
+    // Position of the first maximum, None for an empty matrix
+    pub fn argmax(&self) -> Option<(usize, usize)> {
+        arg_extremum(&self.data, |a, b| a > b).map(|k| (k / self.cols, k % self.cols))
+    }
+
+    pub fn min_axis(&self, axis: Axis) -> Matrix<T> {
+        self.extremum_axis(axis, |a, b| a < b)
+    }
+
+    pub fn max_axis(&self, axis: Axis) -> Matrix<T> {
+        self.extremum_axis(axis, |a, b| a > b)
+    }
+
+    // Column index of each row's minimum (`Axis::Rows`) or row index of each column's
+    pub fn argmin_axis(&self, axis: Axis) -> Vec<usize> {
+        self.arg_extremum_axis(axis, |a, b| a < b)
+    }
+
+    // Column index of each row's maximum (`Axis::Rows`) or row index of each column's
+    pub fn argmax_axis(&self, axis: Axis) -> Vec<usize> {
+        self.arg_extremum_axis(axis, |a, b| a > b)
+    }
+
+    fn extremum_axis(&self, axis: Axis, better: fn(&T, &T) -> bool) -> Matrix<T> {
+        let indices = self.arg_extremum_axis(axis, better);
+
+        match axis {
+            Axis::Rows => Matrix::from_aligned(
+                self.rows,
+                1,
+                AVec::from_iter(
+                    CACHELINE_ALIGN,
+                    indices.iter().enumerate().map(|(i, &j)| self[(i, j)]),
+                ),
+            ),
+            Axis::Cols => Matrix::from_aligned(
+                1,
+                self.cols,
+                AVec::from_iter(
+                    CACHELINE_ALIGN,
+                    indices.iter().enumerate().map(|(j, &i)| self[(i, j)]),
+                ),
+            ),
+        }
+    }
+
+    fn arg_extremum_axis(&self, axis: Axis, better: fn(&T, &T) -> bool) -> Vec<usize> {
+        match axis {
+            Axis::Rows => {
+                assert!(
+                    self.cols > 0,
+                    "Cannot reduce rows of a matrix with no columns"
+                );
+                reduce_rows(&self.data, self.rows, self.cols, |row| {
+                    arg_extremum(row, better).unwrap()
+                })
+                .to_vec()
+            }
+            Axis::Cols => {
+                assert!(
+                    self.rows > 0,
+                    "Cannot reduce columns of a matrix with no rows"
+                );
+                self.transpose().arg_extremum_axis(Axis::Rows, better)
+            }
+        }
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Sync,
+{
+    pub fn any<F>(&self, predicate: F) -> bool
+    where
+        F: Fn(&T) -> bool + Sync + Send,
+    {
+        if self.data.len() >= PARALLEL_THRESHOLD {
+            self.data.par_iter().any(predicate)
+        } else {
+            self.data.iter().any(predicate)
+        }
+    }
+
+    pub fn all<F>(&self, predicate: F) -> bool
+    where
+        F: Fn(&T) -> bool + Sync + Send,
+    {
+        if self.data.len() >= PARALLEL_THRESHOLD {
+            self.data.par_iter().all(predicate)
+        } else {
+            self.data.iter().all(predicate)
+        }
+    }
+}
+
+fn from_count<T: FromPrimitive>(count: usize) -> T {
+    T::from_usize(count).expect("Element count must be representable in the element type")
+}
+
+// Applies `f` to every row, in parallel for large matrices
+fn reduce_rows<T, U, F>(data: &[T], rows: usize, cols: usize, f: F) -> AVec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&[T]) -> U + Sync + Send,
+{
+    if cols == 0 {
+        AVec::from_iter(CACHELINE_ALIGN, (0..rows).map(|_| f(&[])))
+    } else if data.len() >= PARALLEL_THRESHOLD {
+        let results: Vec<U> = data.par_chunks(cols).map(f).collect();
+        AVec::from_iter(CACHELINE_ALIGN, results)
+    } else {
+        AVec::from_iter(CACHELINE_ALIGN, data.chunks(cols).map(f))
+    }
+}
+
+fn pairwise_sum<T>(data: &[T], simd: bool) -> T
+where
+    T: Copy + Add<Output = T> + Default + Zero + SimdOps + Send + Sync,
+{
+    if data.len() <= PAIRWISE_BLOCK {
+        return block_sum(data, simd);
+    }
+
+    // Split on a lane boundary so both halves keep full SIMD vectors
+    let mid = data.len() / 2 / T::LANE_SIZE * T::LANE_SIZE;
+    let (left, right) = data.split_at(mid);
+
+    if data.len() >= PARALLEL_THRESHOLD {
+        let (a, b) = rayon::join(|| pairwise_sum(left, simd), || pairwise_sum(right, simd));
+        a + b
+    } else {
+        pairwise_sum(left, simd) + pairwise_sum(right, simd)
+    }
+}
+
+#[cfg_attr(not(target_arch = "x86_64"), allow(unused_variables))]
+fn block_sum<T>(data: &[T], simd: bool) -> T
+where
+    T: Copy + Add<Output = T> + Default + Zero + SimdOps,
+{
+    #[cfg(target_arch = "x86_64")]
+    if simd && data.len() >= T::LANE_SIZE {
+        return unsafe { block_sum_simd(data) };
+    }
+
+    data.iter().fold(T::zero(), |acc, &x| acc + x)
+}
+
+#[cfg(target_arch = "x86_64")]
+unsafe fn block_sum_simd<T>(data: &[T]) -> T
+where
+    T: Copy + Add<Output = T> + Default + Zero + SimdOps,
+{
+    let lanes = data.len() / T::LANE_SIZE;
+    let mut acc = T::zeroed();
+
+    for i in 0..lanes {
+        let vec = T::load(data[i * T::LANE_SIZE..].as_ptr());
+        acc = <T as SimdOps>::add(acc, vec);
+    }
+
+    let tail = data[lanes * T::LANE_SIZE..]
+        .iter()
+        .fold(T::zero(), |acc, &x| acc + x);
+
+    T::reduce_add(acc) + tail
+}
+
+// Pairwise over rows: blocks of rows are accumulated directly, then the partial sums are combined
+fn column_sums<T>(data: &[T], cols: usize) -> Vec<T>
+where
+    T: Copy + Add<Output = T> + Zero + Send + Sync,
+{
+    let rows = data.len() / cols;
+
+    if rows <= PAIRWISE_BLOCK {
+        let mut sums = vec![T::zero(); cols];
+        for row in data.chunks_exact(cols) {
+            sums.iter_mut().zip(row).for_each(|(s, &x)| *s = *s + x);
+        }
+        return sums;
+    }
+
+    let (top, bottom) = data.split_at(rows / 2 * cols);
+    let (a, b) = if data.len() >= PARALLEL_THRESHOLD {
+        rayon::join(|| column_sums(top, cols), || column_sums(bottom, cols))
+    } else {
+        (column_sums(top, cols), column_sums(bottom, cols))
+    };
+
+    a.into_iter().zip(b).map(|(x, y)| x + y).collect()
+}
+
+// Running count, mean and sum of squared deviations (Welford), mergeable across chunks (Chan et al.)
+#[derive(Clone, Copy)]
+struct Moments<T> {
+    count: usize,
+    mean: T,
+    m2: T,
+}
+
+impl<T: Float> Moments<T> {
+    fn empty() -> Self {
+        Moments {
+            count: 0,
+            mean: T::zero(),
+            m2: T::zero(),
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        if self.count == 0 {
+            return other;
+        }
+        if other.count == 0 {
+            return self;
+        }
+
+        let count = self.count + other.count;
+        let (n_a, n_b, n) = (
+            T::from(self.count).unwrap(),
+            T::from(other.count).unwrap(),
+            T::from(count).unwrap(),
+        );
+        let delta = other.mean - self.mean;
+
+        Moments {
+            count,
+            mean: self.mean + delta * n_b / n,
+            m2: self.m2 + other.m2 + delta * delta * n_a * n_b / n,
+        }
+    }
+
+    fn variance(&self, ddof: usize) -> T {
+        if self.count <= ddof {
+            return T::nan();
+        }
+        self.m2 / T::from(self.count - ddof).unwrap()
+    }
+}
+
+fn moments<T: Float + Send + Sync>(data: &[T]) -> Moments<T> {
+    if data.len() >= PARALLEL_THRESHOLD {
+        return data
+            .par_chunks(PARALLEL_CHUNK)
+            .map(moments)
+            .reduce(Moments::empty, Moments::merge);
+    }
+
+    let mut m = Moments::empty();
+    for &x in data {
+        m.count += 1;
+        let delta = x - m.mean;
+        m.mean = m.mean + delta / T::from(m.count).unwrap();
+        m.m2 = m.m2 + delta * (x - m.mean);
+    }
+    m
+}
+
+// Index of the element `better` prefers over all others, the first one on ties
+fn arg_extremum<T>(data: &[T], better: fn(&T, &T) -> bool) -> Option<usize>
+where
+    T: PartialOrd + Sync,
+{
+    let unordered = |x: &T| x.partial_cmp(x).is_none();
+
+    if data.len() >= PARALLEL_THRESHOLD {
+        return data
+            .par_chunks(PARALLEL_CHUNK)
+            .enumerate()
+            .filter_map(|(c, chunk)| arg_extremum(chunk, better).map(|k| c * PARALLEL_CHUNK + k))
+            .reduce_with(|a, b| {
+                if unordered(&data[a]) || !(unordered(&data[b]) || better(&data[b], &data[a])) {
+                    a
+                } else {
+                    b
+                }
+            });
+    }
+
+    let mut best = 0;
+    for (k, x) in data.iter().enumerate() {
+        if unordered(x) {
+            return Some(k);
+        }
+        if better(x, &data[best]) {
+            best = k;
+        }
+    }
+
+    (!data.is_empty()).then_some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+
+    #[test]
+    fn test_sum() {
+        let m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(m.sum(), 21);
+    }
+
+    #[test]
+    fn test_sum_complex() {
+        let m = Matrix::from_vec(10, 30, vec![c64::new(1.0, -0.5); 300]);
+        assert_eq!(m.sum(), c64::new(300.0, -150.0));
+    }
+
+    #[test]
+    fn test_sum_is_accurate() {
+        let n = 1_000_000;
+        let m = Matrix::from_vec(1000, 1000, vec![0.1f32; n]);
+
+        let naive = m.data.iter().fold(0.0f32, |acc, &x| acc + x);
+        let exact = 0.1f64 * n as f64;
+
+        assert!((m.sum() as f64 - exact).abs() < 1e-4 * exact);
+        assert!((m.sum() as f64 - exact).abs() < (naive as f64 - exact).abs());
+    }
+
+    #[test]
+    fn test_sum_rows_and_cols() {
+        let m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+
+        let rows = m.sum_rows();
+        assert_eq!((rows.rows(), rows.cols()), (2, 1));
+        assert_eq!(rows.data.as_slice(), vec![6, 15]);
+
+        let cols = m.sum_cols();
+        assert_eq!((cols.rows(), cols.cols()), (1, 3));
+        assert_eq!(cols.data.as_slice(), vec![5, 7, 9]);
+    }
+
+    #[test]
+    fn test_sum_axis_large_matrix() {
+        let (rows, cols) = (700, 513);
+        let m = Matrix::from_vec(
+            rows,
+            cols,
+            (0..rows * cols).map(|i| (i % 7) as i64).collect(),
+        );
+
+        let row_sums = m.sum_rows();
+        let col_sums = m.sum_cols();
+
+        for i in [0, 1, rows - 1] {
+            let expected: i64 = (0..cols).map(|j| m[(i, j)]).sum();
+            assert_eq!(row_sums[(i, 0)], expected);
+        }
+        for j in [0, 7, cols - 1] {
+            let expected: i64 = (0..rows).map(|i| m[(i, j)]).sum();
+            assert_eq!(col_sums[(0, j)], expected);
+        }
+        assert_eq!(m.sum(), row_sums.sum());
+    }
+
+    #[test]
+    fn test_product() {
+        let m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(m.product(), 720);
+        assert_eq!(m.product_axis(Axis::Rows).data.as_slice(), vec![6, 120]);
+        assert_eq!(m.product_axis(Axis::Cols).data.as_slice(), vec![4, 10, 18]);
+
+        let empty: Matrix<f64> = Matrix::from_vec(2, 0, vec![]);
+        assert_eq!(empty.product(), 1.0);
+        assert_eq!(
+            empty.product_axis(Axis::Rows).data.as_slice(),
+            vec![1.0, 1.0]
+        );
+        assert_eq!(empty.product_axis(Axis::Cols).cols(), 0);
+    }
+
+    #[test]
+    fn test_product_large_matrix() {
+        let (rows, cols) = (600, 520);
+        let m = Matrix::from_vec(
+            rows,
+            cols,
+            (0..rows * cols)
+                .map(|i| if i % 1000 == 0 { -1i64 } else { 1 })
+                .collect(),
+        );
+        let expected = if (rows * cols).div_ceil(1000) % 2 == 0 {
+            1
+        } else {
+            -1
+        };
+
+        assert_eq!(m.product(), expected);
+        assert_eq!(m.product_axis(Axis::Rows).product(), expected);
+        assert_eq!(m.product_axis(Axis::Cols).product(), expected);
+    }
+
+    #[test]
+    fn test_mean() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert_eq!(m.mean(), Some(3.5));
+        assert_eq!(Matrix::new([[1, 2], [3, 5]]).mean(), Some(2));
+        assert_eq!(Matrix::<i32>::zeros(0, 3).mean(), None);
+        assert_eq!(Matrix::<f64>::zeros(2, 0).mean(), None);
+        assert_eq!(m.mean_axis(Axis::Rows).data.as_slice(), vec![2.0, 5.0]);
+        assert_eq!(m.mean_axis(Axis::Cols).data.as_slice(), vec![2.5, 3.5, 4.5]);
+    }
+
+    #[test]
+    fn test_var_and_std() {
+        let m = Matrix::new([[2.0, 4.0, 4.0, 4.0], [5.0, 5.0, 7.0, 9.0]]);
+        assert_eq!(m.var(0), 4.0);
+        assert_eq!(m.std(0), 2.0);
+        assert!((m.var(1) - 32.0 / 7.0).abs() < 1e-12);
+
+        let rows = m.var_axis(Axis::Rows, 0);
+        assert!((rows[(0, 0)] - 0.75).abs() < 1e-12);
+        assert!((rows[(1, 0)] - 2.75).abs() < 1e-12);
+        let cols = m.std_axis(Axis::Cols, 0);
+        assert_eq!((cols.rows(), cols.cols()), (1, 4));
+        assert_eq!(cols.data.as_slice(), vec![1.5, 0.5, 1.5, 2.5]);
+    }
+
+    #[test]
+    fn test_var_large_matrix() {
+        let size = 1024;
+        let m = Matrix::from_vec(
+            size,
+            size,
+            (0..size * size).map(|i| 1e6 + (i % 2) as f64).collect(),
+        );
+        assert!((m.var(0) - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let m = Matrix::new([[3, -1, 4], [1, 5, -1]]);
+        assert_eq!(m.min(), Some(-1));
+        assert_eq!(m.max(), Some(5));
+        assert_eq!(m.argmin(), Some((0, 1)));
+        assert_eq!(m.argmax(), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_min_max_axis() {
+        let m = Matrix::new([[3, -1, 4], [1, 5, -2]]);
+        assert_eq!(m.min_axis(Axis::Rows).data.as_slice(), vec![-1, -2]);
+        assert_eq!(m.max_axis(Axis::Cols).data.as_slice(), vec![3, 5, 4]);
+        assert_eq!(m.argmin_axis(Axis::Cols), vec![1, 0, 1]);
+        assert_eq!(m.argmax_axis(Axis::Rows), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_min_max_propagate_nan() {
+        let m = Matrix::new([[1.0, f64::NAN], [-3.0, 2.0]]);
+        assert!(m.min().unwrap().is_nan());
+        assert_eq!(m.argmax(), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_argmax_large_matrix() {
+        let size = 1024;
+        let mut data: Vec<f32> = (0..size * size).map(|i| (i % 1000) as f32).collect();
+        data[777_777] = 5000.0;
+        data[900_000] = 5000.0;
+        let m = Matrix::from_vec(size, size, data);
+
+        assert_eq!(m.argmax(), Some((777_777 / size, 777_777 % size)));
+        assert_eq!(m.min(), Some(0.0));
+    }
+
+    #[test]
+    fn test_empty_matrix_has_no_extremum() {
+        let m: Matrix<f64> = Matrix::zeros(0, 3);
+        assert_eq!(m.max(), None);
+        assert_eq!(m.sum(), 0.0);
+    }
+
+    #[test]
+    fn test_any_all() {
+        let m = Matrix::new([[1, 2], [3, 4]]);
+        assert!(m.any(|&x| x > 3));
+        assert!(!m.any(|&x| x > 4));
+        assert!(m.all(|&x| x > 0));
+        assert!(!m.all(|&x| x > 1));
+    }
+
+    #[test]
+    fn test_cumsum() {
+        let m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(
+            m.cumsum(Axis::Rows).data.as_slice(),
+            vec![1, 3, 6, 4, 9, 15]
+        );
+        assert_eq!(m.cumsum(Axis::Cols).data.as_slice(), vec![1, 2, 3, 5, 7, 9]);
+    }
+}
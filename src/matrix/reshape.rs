@@ -0,0 +1,200 @@
+use super::mat::Matrix;
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use std::ops::{Add, Mul, Sub};
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    // Reinterprets the row-major data with new dimensions, no copy
+    pub fn reshape(self, rows: usize, cols: usize) -> Self {
+        assert_eq!(
+            rows * cols,
+            self.data.len(),
+            "Reshape must preserve the number of elements"
+        );
+        Matrix {
+            rows,
+            cols,
+            data: self.data,
+        }
+    }
+
+    // Keeps the top-left min(rows) x min(cols) block and fills everything new with `value`
+    pub fn resize(&mut self, rows: usize, cols: usize, value: T) {
+        if cols == self.cols {
+            self.data.resize(rows * cols, value);
+            self.rows = rows;
+            return;
+        }
+
+        let data = AVec::from_iter(
+            CACHELINE_ALIGN,
+            (0..rows * cols).map(|k| {
+                let (i, j) = (k / cols, k % cols);
+                if i < self.rows && j < self.cols {
+                    self.data[i * self.cols + j].clone()
+                } else {
+                    value.clone()
+                }
+            }),
+        );
+        *self = Matrix { rows, cols, data };
+    }
+
+    // Inserts `row` so that it becomes row `index`, shifting the rows below it down
+    pub fn insert_row(&mut self, index: usize, row: &[T]) {
+        assert!(index <= self.rows, "Row index out of bounds");
+        assert_eq!(
+            row.len(),
+            self.cols,
+            "Matrices must have the same number of columns"
+        );
+
+        let start = index * self.cols;
+        let data = AVec::from_iter(
+            CACHELINE_ALIGN,
+            self.data[..start]
+                .iter()
+                .chain(row)
+                .chain(&self.data[start..])
+                .cloned(),
+        );
+        self.data = data;
+        self.rows += 1;
+    }
+
+    // Inserts `col` so that it becomes column `index`, shifting the columns right of it
+    pub fn insert_col(&mut self, index: usize, col: &[T]) {
+        assert!(index <= self.cols, "Column index out of bounds");
+        assert_eq!(
+            col.len(),
+            self.rows,
+            "Matrices must have the same number of rows"
+        );
+
+        let cols = self.cols;
+        let data = AVec::from_iter(
+            CACHELINE_ALIGN,
+            (0..self.rows).flat_map(|i| {
+                let row = &self.data[i * cols..(i + 1) * cols];
+                row[..index]
+                    .iter()
+                    .chain(std::iter::once(&col[i]))
+                    .chain(&row[index..])
+                    .cloned()
+            }),
+        );
+        self.data = data;
+        self.cols += 1;
+    }
+
+    pub fn remove_row(&mut self, index: usize) -> Vec<T> {
+        assert!(index < self.rows, "Row index out of bounds");
+
+        let cols = self.cols;
+        let removed = self.data[index * cols..(index + 1) * cols].to_vec();
+        let data = AVec::from_iter(
+            CACHELINE_ALIGN,
+            self.data[..index * cols]
+                .iter()
+                .chain(&self.data[(index + 1) * cols..])
+                .cloned(),
+        );
+        self.data = data;
+        self.rows -= 1;
+        removed
+    }
+
+    pub fn remove_col(&mut self, index: usize) -> Vec<T> {
+        assert!(index < self.cols, "Column index out of bounds");
+
+        let cols = self.cols;
+        let removed = (0..self.rows)
+            .map(|i| self.data[i * cols + index].clone())
+            .collect();
+        let data = AVec::from_iter(
+            CACHELINE_ALIGN,
+            self.data
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| k % cols != index)
+                .map(|(_, x)| x.clone()),
+        );
+        self.data = data;
+        self.cols -= 1;
+        removed
+    }
+
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        assert!(a < self.rows && b < self.rows, "Row index out of bounds");
+        if a == b {
+            return;
+        }
+
+        let cols = self.cols;
+        let (low, high) = (a.min(b), a.max(b));
+        let (head, tail) = self.data.split_at_mut(high * cols);
+        head[low * cols..(low + 1) * cols].swap_with_slice(&mut tail[..cols]);
+    }
+
+    pub fn swap_cols(&mut self, a: usize, b: usize) {
+        assert!(a < self.cols && b < self.cols, "Column index out of bounds");
+        for row in self.data.chunks_exact_mut(self.cols) {
+            row.swap(a, b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reshape_keeps_storage() {
+        let m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let ptr = m.data.as_ptr();
+        let r = m.reshape(3, 2);
+        assert_eq!(r, Matrix::new([[1, 2], [3, 4], [5, 6]]));
+        assert_eq!(r.data.as_ptr(), ptr);
+    }
+
+    #[test]
+    #[should_panic(expected = "Reshape must preserve the number of elements")]
+    fn test_reshape_wrong_size() {
+        let _ = Matrix::new([[1, 2, 3]]).reshape(2, 2);
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut m = Matrix::new([[1, 2], [3, 4]]);
+        m.resize(3, 3, 0);
+        assert_eq!(m, Matrix::new([[1, 2, 0], [3, 4, 0], [0, 0, 0]]));
+        m.resize(1, 2, 9);
+        assert_eq!(m, Matrix::new([[1, 2]]));
+        m.resize(2, 2, 9);
+        assert_eq!(m, Matrix::new([[1, 2], [9, 9]]));
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut m = Matrix::new([[1, 2], [3, 4]]);
+        m.insert_row(1, &[5, 6]);
+        assert_eq!(m, Matrix::new([[1, 2], [5, 6], [3, 4]]));
+        m.insert_col(2, &[7, 8, 9]);
+        assert_eq!(m, Matrix::new([[1, 2, 7], [5, 6, 8], [3, 4, 9]]));
+
+        assert_eq!(m.remove_col(0), vec![1, 5, 3]);
+        assert_eq!(m.remove_row(2), vec![4, 9]);
+        assert_eq!(m, Matrix::new([[2, 7], [6, 8]]));
+    }
+
+    #[test]
+    fn test_swaps() {
+        let mut m = Matrix::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        m.swap_rows(2, 0);
+        assert_eq!(m, Matrix::new([[7, 8, 9], [4, 5, 6], [1, 2, 3]]));
+        m.swap_cols(0, 1);
+        assert_eq!(m, Matrix::new([[8, 7, 9], [5, 4, 6], [2, 1, 3]]));
+    }
+}
@@ -0,0 +1,241 @@
+use super::{
+    mat::Matrix,
+    scalar::{adjoint, from_complex, to_complex, Scalar},
+    schur::{complex_schur, reorder_schur},
+    sylvester::{check_shape, check_square},
+};
+use crate::error::LinalgError;
+use num::complex::Complex;
+use num_traits::{Float, One, Zero};
+
+impl<T: Scalar> Matrix<T> {
+    // Stabilizing solution of the continuous algebraic Riccati equation
+    //   A^H X + X A - X B R^-1 B^H X + Q = 0
+    // from the stable invariant subspace of the Hamiltonian [[A, -B R^-1 B^H], [-Q, -A^H]]
+    pub fn solve_care(
+        a: &Matrix<T>,
+        b: &Matrix<T>,
+        q: &Matrix<T>,
+        r: &Matrix<T>,
+    ) -> Result<Matrix<T>, LinalgError> {
+        let n = a.rows;
+        let g = riccati_gain(a, b, q, r)?;
+        let a = to_complex(a);
+        let q = to_complex(q);
+
+        let hamiltonian = Matrix::from_fn(2 * n, 2 * n, |i, j| match (i < n, j < n) {
+            (true, true) => a[(i, j)],
+            (true, false) => -g[(i, j - n)],
+            (false, true) => -q[(i - n, j)],
+            (false, false) => -a[(j - n, i - n)].conj(),
+        });
+        stable_subspace_solution(hamiltonian, |z| z.re < T::Real::zero())
+    }
+
+    // Stabilizing solution of the discrete algebraic Riccati equation
+    //   A^H X A - X - A^H X B (R + B^H X B)^-1 B^H X A + Q = 0
+    // from the stable deflating subspace of the symplectic pencil M - zL with
+    //   M = [[A, 0], [-Q, I]], L = [[I, G], [0, A^H]] and G = B R^-1 B^H.
+    // The Cayley transform (M + L)^-1 (M - L) has the same subspace as an invariant subspace and
+    // maps |z| < 1 to the left half plane. Unlike L^-1 M it never inverts A, so singular A is fine;
+    // M + L is only singular when -1 is an eigenvalue, and then there is no stabilizing solution.
+    pub fn solve_dare(
+        a: &Matrix<T>,
+        b: &Matrix<T>,
+        q: &Matrix<T>,
+        r: &Matrix<T>,
+    ) -> Result<Matrix<T>, LinalgError> {
+        let n = a.rows;
+        let g = riccati_gain(a, b, q, r)?;
+        let a = to_complex(a);
+        let q = to_complex(q);
+        let one = Complex::<T::Real>::one();
+        let eye = |i: usize, j: usize| if i == j { one } else { Complex::zero() };
+
+        let m_plus_l = Matrix::from_fn(2 * n, 2 * n, |i, j| match (i < n, j < n) {
+            (true, true) => a[(i, j)] + eye(i, j),
+            (true, false) => g[(i, j - n)],
+            (false, true) => -q[(i - n, j)],
+            (false, false) => eye(i, j) + a[(j - n, i - n)].conj(),
+        });
+        let m_minus_l = Matrix::from_fn(2 * n, 2 * n, |i, j| match (i < n, j < n) {
+            (true, true) => a[(i, j)] - eye(i, j),
+            (true, false) => -g[(i, j - n)],
+            (false, true) => -q[(i - n, j)],
+            (false, false) => eye(i, j) - a[(j - n, i - n)].conj(),
+        });
+
+        let cayley = match m_plus_l.lu() {
+            Ok(lu) => lu.solve(&m_minus_l),
+            Err(LinalgError::Singular { .. }) => return Err(LinalgError::NoStabilizingSolution),
+            Err(e) => return Err(e),
+        };
+        stable_subspace_solution(cayley, |z| z.re < T::Real::zero())
+    }
+}
+
+// Checks the shapes and returns G = B R^-1 B^H
+fn riccati_gain<T: Scalar>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    q: &Matrix<T>,
+    r: &Matrix<T>,
+) -> Result<Matrix<Complex<T::Real>>, LinalgError> {
+    check_square(a)?;
+    let n = a.rows;
+    check_shape(b, (n, b.cols))?;
+    check_shape(q, (n, n))?;
+    check_shape(r, (b.cols, b.cols))?;
+
+    let b = to_complex(b);
+    let r_inv_bh = to_complex(r).lu()?.solve(&adjoint(&b));
+    Ok(&b * &r_inv_bh)
+}
+
+// X = U21 U11^-1 where [U11; U21] spans the invariant subspace for the selected eigenvalues
+fn stable_subspace_solution<T, F>(
+    m: Matrix<Complex<T::Real>>,
+    stable: F,
+) -> Result<Matrix<T>, LinalgError>
+where
+    T: Scalar,
+    F: Fn(Complex<T::Real>) -> bool,
+{
+    let n = m.rows / 2;
+    let (mut t, mut u) = complex_schur(m)?;
+    if reorder_schur(&mut t, &mut u, stable) != n {
+        return Err(LinalgError::NoStabilizingSolution);
+    }
+
+    let u11 = u.view(0..n, 0..n).to_matrix();
+    let u21 = u.view(n..2 * n, 0..n).to_matrix();
+    // X U11 = U21, solved as U11^H X^H = U21^H. A singular U11 means the subspace is not the graph
+    // of any X, which happens when an unstable mode cannot be reached from B.
+    let u11_h = match adjoint(&u11).lu() {
+        Ok(lu) if lu.rcond() >= T::Real::epsilon() => lu,
+        Ok(_) | Err(LinalgError::Singular { .. }) => {
+            return Err(LinalgError::NoStabilizingSolution)
+        }
+        Err(e) => return Err(e),
+    };
+    let x = adjoint(&u11_h.solve(&adjoint(&u21)));
+
+    // The exact solution is Hermitian, drop the rounding that breaks that
+    let half = Complex::new(
+        T::Real::one() / (T::Real::one() + T::Real::one()),
+        T::Real::zero(),
+    );
+    let x = Matrix::from_fn(n, n, |i, j| (x[(i, j)] + x[(j, i)].conj()) * half);
+    Ok(from_complex(&x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num::c64;
+
+    #[test]
+    fn test_scalar_riccati_equations() {
+        let one = Matrix::new([[1.0]]);
+        // 2x - x^2 + 1 = 0
+        let care = Matrix::solve_care(&one, &one, &one, &one).unwrap();
+        assert!((care[(0, 0)] - (1.0 + 2.0f64.sqrt())).abs() < 1e-12);
+        // x^2 = x + 1
+        let dare = Matrix::solve_dare(&one, &one, &one, &one).unwrap();
+        assert!((dare[(0, 0)] - (1.0 + 5.0f64.sqrt()) / 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_care_residual() {
+        let a = Matrix::new([[0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [-1.0, 2.0, 0.5]]);
+        let b = Matrix::new([[0.0], [0.0], [1.0]]);
+        let q = Matrix::<f64>::identity(3, 3);
+        let r = Matrix::new([[0.5]]);
+
+        let x = Matrix::solve_care(&a, &b, &q, &r).unwrap();
+        let at = a.transpose();
+        let xb = &x * &b;
+        let residual = &(&(&(&at * &x) + &(&x * &a)) - &((&xb * &xb.transpose()) * 2.0)) + &q;
+        assert!(residual.norm_max() < 1e-9, "{}", residual.norm_max());
+
+        // The closed loop A - B R^-1 B^T X is stable
+        let closed = &a - &((&b * &xb.transpose()) * 2.0);
+        let (t, _) = complex_schur(to_complex(&closed)).unwrap();
+        assert!((0..3).all(|i| t[(i, i)].re < 0.0));
+    }
+
+    fn dare_residual(
+        a: &Matrix<c64>,
+        b: &Matrix<c64>,
+        q: &Matrix<c64>,
+        r: &Matrix<c64>,
+        x: &Matrix<c64>,
+    ) -> Matrix<c64> {
+        let ah = adjoint(a);
+        let bh = adjoint(b);
+        let ah_x_b = &(&ah * x) * b;
+        let gain = (r + &(&(&bh * x) * b)).lu().unwrap();
+        let correction = &ah_x_b * &gain.solve(&adjoint(&ah_x_b));
+        &(&(&(&(&ah * x) * a) - x) - &correction) + q
+    }
+
+    #[test]
+    fn test_dare_residual_complex() {
+        let a = Matrix::new([
+            [c64::new(0.9, 0.1), c64::new(0.3, 0.0)],
+            [c64::new(0.0, -0.2), c64::new(1.1, 0.0)],
+        ]);
+        let b = Matrix::new([[c64::new(0.0, 0.0)], [c64::new(1.0, 0.5)]]);
+        let q = Matrix::<c64>::identity(2, 2);
+        let r = Matrix::new([[c64::new(1.0, 0.0)]]);
+
+        let x = Matrix::solve_dare(&a, &b, &q, &r).unwrap();
+        let residual = dare_residual(&a, &b, &q, &r, &x);
+        assert!(residual.norm_max() < 1e-10, "{}", residual.norm_max());
+    }
+
+    #[test]
+    fn test_dare_singular_a() {
+        // With A = 0 the equation reduces to X = Q
+        let zero = Matrix::new([[0.0]]);
+        let one = Matrix::new([[1.0f64]]);
+        let x = Matrix::solve_dare(&zero, &one, &one, &one).unwrap();
+        assert!((x[(0, 0)] - 1.0).abs() < 1e-12);
+
+        // A one-step delay in front of an integrator, A is nilpotent
+        let a = Matrix::new([[1.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0, 0.0]]);
+        let b = Matrix::new([[0.0], [0.0], [1.0]]);
+        let q = Matrix::<f64>::identity(3, 3);
+        let r = Matrix::new([[1.0]]);
+        let x = Matrix::solve_dare(&a, &b, &q, &r).unwrap();
+        let [a, b, q, r, x] = [&a, &b, &q, &r, &x].map(to_complex);
+        let residual = dare_residual(&a, &b, &q, &r, &x);
+        assert!(residual.norm_max() < 1e-10, "{}", residual.norm_max());
+    }
+
+    #[test]
+    fn test_dare_without_stabilizing_solution() {
+        // The unstable mode is not reachable from B
+        let a = Matrix::new([[2.0, 0.0], [0.0, 0.5]]);
+        let b = Matrix::new([[0.0], [1.0]]);
+        let q = Matrix::<f64>::identity(2, 2);
+        let r = Matrix::new([[1.0]]);
+        assert_eq!(
+            Matrix::solve_dare(&a, &b, &q, &r).unwrap_err(),
+            LinalgError::NoStabilizingSolution
+        );
+    }
+
+    #[test]
+    fn test_riccati_shape_errors() {
+        let a = Matrix::<f64>::identity(2, 2);
+        let b = Matrix::new([[1.0], [0.0]]);
+        assert_eq!(
+            Matrix::solve_care(&a, &b, &a, &a).unwrap_err(),
+            LinalgError::DimensionMismatch {
+                expected: (1, 1),
+                found: (2, 2)
+            }
+        );
+    }
+}
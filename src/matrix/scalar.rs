@@ -0,0 +1,75 @@
+use super::mat::Matrix;
+use num::complex::{self, Complex};
+use num_traits::{Float, FloatConst};
+
+// The four element types the dense solvers support: f32, f64 and their complex counterparts.
+// Algorithms that need complex arithmetic even for real input (Schur forms, eigenvalues) work on
+// `Complex<Self::Real>` and convert at the boundary.
+pub trait Scalar:
+    complex::ComplexFloat<Real: Float + FloatConst + Default + Send + Sync>
+    + Default
+    + Send
+    + Sync
+    + 'static
+{
+    fn to_complex(self) -> Complex<Self::Real>;
+
+    // Real types keep only the real part
+    fn from_complex(z: Complex<Self::Real>) -> Self;
+}
+
+impl Scalar for f32 {
+    fn to_complex(self) -> Complex<f32> {
+        Complex::new(self, 0.0)
+    }
+
+    fn from_complex(z: Complex<f32>) -> Self {
+        z.re
+    }
+}
+
+impl Scalar for f64 {
+    fn to_complex(self) -> Complex<f64> {
+        Complex::new(self, 0.0)
+    }
+
+    fn from_complex(z: Complex<f64>) -> Self {
+        z.re
+    }
+}
+
+impl Scalar for Complex<f32> {
+    fn to_complex(self) -> Complex<f32> {
+        self
+    }
+
+    fn from_complex(z: Complex<f32>) -> Self {
+        z
+    }
+}
+
+impl Scalar for Complex<f64> {
+    fn to_complex(self) -> Complex<f64> {
+        self
+    }
+
+    fn from_complex(z: Complex<f64>) -> Self {
+        z
+    }
+}
+
+pub(crate) fn to_complex<T: Scalar>(m: &Matrix<T>) -> Matrix<Complex<T::Real>> {
+    m.map(|&x| x.to_complex())
+}
+
+pub(crate) fn from_complex<T: Scalar>(m: &Matrix<Complex<T::Real>>) -> Matrix<T> {
+    m.map(|&z| T::from_complex(z))
+}
+
+// Conjugate transpose, for any complex-valued element type
+pub(crate) fn adjoint<T>(m: &Matrix<T>) -> Matrix<T>
+where
+    T: complex::ComplexFloat + Default,
+{
+    Matrix::from_fn(m.cols, m.rows, |i, j| m[(j, i)].conj())
+}
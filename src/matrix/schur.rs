@@ -0,0 +1,296 @@
+use super::mat::Matrix;
+use crate::error::LinalgError;
+use num::complex::Complex;
+use num_traits::{Float, FloatConst, One, Zero};
+
+const MAX_ITERATIONS_PER_EIGENVALUE: usize = 30;
+
+type ComplexMatrix<R> = Matrix<Complex<R>>;
+
+// Complex Schur form A = U T U^H with T upper triangular and U unitary. Reduces to Hessenberg form
+// with Householder reflectors, then runs implicitly shifted QR with Wilkinson shifts.
+pub(crate) fn complex_schur<R>(
+    mut t: Matrix<Complex<R>>,
+) -> Result<(ComplexMatrix<R>, ComplexMatrix<R>), LinalgError>
+where
+    R: Float + FloatConst + Default,
+{
+    let n = t.rows;
+    let mut u = Matrix::identity(n, n);
+    hessenberg(&mut t, &mut u);
+
+    let eps = R::epsilon();
+    let mut hi = n.saturating_sub(1);
+    let mut iterations = 0;
+    let mut total = 0;
+
+    while hi > 0 {
+        // Split off the bottom unreduced block lo..=hi at a negligible subdiagonal
+        let mut lo = hi;
+        while lo > 0 {
+            let sub = t[(lo, lo - 1)].norm();
+            let scale = t[(lo - 1, lo - 1)].norm() + t[(lo, lo)].norm();
+            if sub <= eps * scale || sub < R::min_positive_value() {
+                t[(lo, lo - 1)] = Complex::zero();
+                break;
+            }
+            lo -= 1;
+        }
+
+        if lo == hi {
+            hi -= 1;
+            iterations = 0;
+            continue;
+        }
+
+        iterations += 1;
+        total += 1;
+        if total > MAX_ITERATIONS_PER_EIGENVALUE * n {
+            return Err(LinalgError::NotConverged { iterations: total });
+        }
+
+        let shift = if iterations % 10 == 0 {
+            // Exceptional shift to break out of cycles
+            let bump = t[(hi, hi - 1)].norm() * R::from(0.75).unwrap();
+            t[(hi, hi)] + Complex::new(bump, R::zero())
+        } else {
+            wilkinson_shift(
+                t[(hi - 1, hi - 1)],
+                t[(hi - 1, hi)],
+                t[(hi, hi - 1)],
+                t[(hi, hi)],
+            )
+        };
+
+        // Chase the bulge down the active block
+        for k in lo..hi {
+            let (x, y) = if k == lo {
+                (t[(lo, lo)] - shift, t[(lo + 1, lo)])
+            } else {
+                (t[(k, k - 1)], t[(k + 1, k - 1)])
+            };
+            let (c, s) = givens(x, y);
+
+            rotate_rows(&mut t, k, if k > lo { k - 1 } else { lo }, c, s);
+            rotate_cols(&mut t, k, (k + 2).min(hi) + 1, c, s);
+            rotate_cols(&mut u, k, n, c, s);
+            if k > lo {
+                t[(k + 1, k - 1)] = Complex::zero();
+            }
+        }
+    }
+
+    for i in 1..n {
+        for j in 0..i {
+            t[(i, j)] = Complex::zero();
+        }
+    }
+    Ok((t, u))
+}
+
+// Moves the eigenvalues for which `select` holds to the top of T, updating U to match. Returns
+// how many were selected.
+pub(crate) fn reorder_schur<R, F>(
+    t: &mut Matrix<Complex<R>>,
+    u: &mut Matrix<Complex<R>>,
+    select: F,
+) -> usize
+where
+    R: Float + FloatConst,
+    F: Fn(Complex<R>) -> bool,
+{
+    let n = t.rows;
+    let mut selected = 0;
+    for k in 0..n {
+        if !select(t[(k, k)]) {
+            continue;
+        }
+        for j in (selected..k).rev() {
+            swap_eigenvalues(t, u, j);
+        }
+        selected += 1;
+    }
+    selected
+}
+
+// Exchanges the adjacent diagonal entries j and j + 1 of T by a unitary similarity (LAPACK ztrexc)
+fn swap_eigenvalues<R>(t: &mut Matrix<Complex<R>>, u: &mut Matrix<Complex<R>>, j: usize)
+where
+    R: Float + FloatConst,
+{
+    let n = t.rows;
+    let (t11, t22) = (t[(j, j)], t[(j + 1, j + 1)]);
+    let (c, s) = givens(t[(j, j + 1)], t22 - t11);
+
+    rotate_rows(t, j, j + 2, c, s);
+    rotate_cols(t, j, j, c, s);
+    t[(j, j)] = t22;
+    t[(j + 1, j + 1)] = t11;
+    rotate_cols(u, j, n, c, s);
+}
+
+// Householder reduction to upper Hessenberg form, accumulating the reflectors into U
+fn hessenberg<R>(h: &mut Matrix<Complex<R>>, u: &mut Matrix<Complex<R>>)
+where
+    R: Float + FloatConst,
+{
+    let n = h.rows;
+    let two = R::one() + R::one();
+
+    for k in 0..n.saturating_sub(2) {
+        let mut v: Vec<Complex<R>> = (k + 1..n).map(|i| h[(i, k)]).collect();
+        let tail = v[1..].iter().fold(R::zero(), |s, x| s + x.norm_sqr());
+        if tail == R::zero() {
+            continue;
+        }
+
+        let norm = (tail + v[0].norm_sqr()).sqrt();
+        let phase = if v[0].norm() == R::zero() {
+            Complex::one()
+        } else {
+            v[0] / v[0].norm()
+        };
+        v[0] = v[0] + phase * norm;
+        let v_norm = v.iter().fold(R::zero(), |s, x| s + x.norm_sqr()).sqrt();
+        v.iter_mut().for_each(|x| *x = *x / v_norm);
+
+        // H = (I - 2vv^H) H (I - 2vv^H), U = U (I - 2vv^H)
+        for j in k..n {
+            let s = (0..v.len()).fold(Complex::<R>::zero(), |s, i| {
+                s + v[i].conj() * h[(k + 1 + i, j)]
+            });
+            for (i, &vi) in v.iter().enumerate() {
+                h[(k + 1 + i, j)] = h[(k + 1 + i, j)] - vi * s * two;
+            }
+        }
+        for m in [&mut *h, &mut *u] {
+            for i in 0..n {
+                let s =
+                    (0..v.len()).fold(Complex::<R>::zero(), |s, j| s + m[(i, k + 1 + j)] * v[j]);
+                for (j, &vj) in v.iter().enumerate() {
+                    m[(i, k + 1 + j)] = m[(i, k + 1 + j)] - s * vj.conj() * two;
+                }
+            }
+        }
+        for i in k + 2..n {
+            h[(i, k)] = Complex::zero();
+        }
+    }
+}
+
+// Eigenvalue of [[a, b], [c, d]] closer to d
+fn wilkinson_shift<R: Float>(
+    a: Complex<R>,
+    b: Complex<R>,
+    c: Complex<R>,
+    d: Complex<R>,
+) -> Complex<R> {
+    let two = R::one() + R::one();
+    let half = (a - d) / two;
+    let root = (half * half + b * c).sqrt();
+    let mean = (a + d) / two;
+    let (mu1, mu2) = (mean + root, mean - root);
+    if (mu1 - d).norm() <= (mu2 - d).norm() {
+        mu1
+    } else {
+        mu2
+    }
+}
+
+// c real and s complex with [c, s; -conj(s), c] [f; g] = [r; 0]
+pub(crate) fn givens<R: Float>(f: Complex<R>, g: Complex<R>) -> (R, Complex<R>) {
+    if g == Complex::zero() {
+        return (R::one(), Complex::zero());
+    }
+    if f == Complex::zero() {
+        return (R::zero(), g.conj() / g.norm());
+    }
+    let (f_abs, g_abs) = (f.norm(), g.norm());
+    let norm = f_abs.hypot(g_abs);
+    (f_abs / norm, (f / f_abs) * g.conj() / norm)
+}
+
+// Rows k and k + 1 of M from column `from` on become G applied to them
+fn rotate_rows<R: Float>(m: &mut Matrix<Complex<R>>, k: usize, from: usize, c: R, s: Complex<R>) {
+    for j in from..m.cols {
+        let (x, y) = (m[(k, j)], m[(k + 1, j)]);
+        m[(k, j)] = x * c + s * y;
+        m[(k + 1, j)] = y * c - s.conj() * x;
+    }
+}
+
+// Columns k and k + 1 of M in rows 0..rows get multiplied by G^H from the right
+fn rotate_cols<R: Float>(m: &mut Matrix<Complex<R>>, k: usize, rows: usize, c: R, s: Complex<R>) {
+    for i in 0..rows {
+        let (x, y) = (m[(i, k)], m[(i, k + 1)]);
+        m[(i, k)] = x * c + y * s.conj();
+        m[(i, k + 1)] = y * c - x * s;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num::c64;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn check_schur(a: &Matrix<c64>) {
+        let n = a.rows();
+        let (t, u) = complex_schur(a.clone()).unwrap();
+        let uh = Matrix::from_fn(n, n, |i, j| u[(j, i)].conj());
+
+        let product = &(&u * &t) * &uh;
+        for (x, y) in product.iter().zip(a.iter()) {
+            assert!((x - y).norm() < 1e-10, "{x} != {y}");
+        }
+        let gram = &uh * &u;
+        for ((i, j), x) in gram.indexed_iter() {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            assert!((x - expected).norm() < 1e-12);
+        }
+        for ((i, j), x) in t.indexed_iter() {
+            if i > j {
+                assert_eq!(*x, c64::zero());
+            }
+        }
+    }
+
+    #[test]
+    fn test_schur_rotation_has_complex_eigenvalues() {
+        let a = Matrix::new([[0.0, -1.0], [1.0, 0.0]]).map(|&x| c64::new(x, 0.0));
+        check_schur(&a);
+        let (t, _) = complex_schur(a).unwrap();
+        let mut eigenvalues = [t[(0, 0)], t[(1, 1)]];
+        eigenvalues.sort_by(|x, y| x.im.partial_cmp(&y.im).unwrap());
+        assert!((eigenvalues[0] - c64::new(0.0, -1.0)).norm() < 1e-12);
+        assert!((eigenvalues[1] - c64::new(0.0, 1.0)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_schur_random_matrices() {
+        let mut rng = StdRng::seed_from_u64(21);
+        for n in [1, 3, 8, 20] {
+            check_schur(&Matrix::<c64>::random_normal(n, n, &mut rng));
+        }
+        // Repeated eigenvalues and a nontrivial Jordan block
+        check_schur(
+            &Matrix::new([[2.0, 1.0, 0.0], [0.0, 2.0, 1.0], [0.0, 0.0, 2.0]])
+                .map(|&x| c64::new(x, 0.0)),
+        );
+    }
+
+    #[test]
+    fn test_reorder_schur() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let a = Matrix::<c64>::random_normal(10, 10, &mut rng);
+        let (mut t, mut u) = complex_schur(a.clone()).unwrap();
+        let stable = reorder_schur(&mut t, &mut u, |z| z.re < 0.0);
+
+        assert!((0..10).all(|i| (t[(i, i)].re < 0.0) == (i < stable)));
+        let uh = Matrix::from_fn(10, 10, |i, j| u[(j, i)].conj());
+        let product = &(&u * &t) * &uh;
+        for (x, y) in product.iter().zip(a.iter()) {
+            assert!((x - y).norm() < 1e-10);
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use super::{mat::Matrix, mat_view::MatrixView};
+use crate::error::LinalgError;
+use aligned_vec::{AVec, CACHELINE_ALIGN};
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Add, Mul, Sub},
+};
+
+const FIELDS: &[&str] = &["rows", "cols", "data"];
+
+// Written as `{rows, cols, data}` with `data` in row-major order
+impl<T> Serialize for Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Matrix", FIELDS.len())?;
+        state.serialize_field("rows", &self.rows)?;
+        state.serialize_field("cols", &self.cols)?;
+        state.serialize_field("data", self.data.as_slice())?;
+        state.end()
+    }
+}
+
+// Same layout as a matrix, so a serialized view reads back as an owned `Matrix`
+impl<'a, T> Serialize for MatrixView<'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Matrix", FIELDS.len())?;
+        state.serialize_field("rows", &self.rows)?;
+        state.serialize_field("cols", &self.cols)?;
+        state.serialize_field("data", &RowMajor(self))?;
+        state.end()
+    }
+}
+
+// The elements of a possibly strided view as one flat sequence
+struct RowMajor<'v, 'a, T>(&'v MatrixView<'a, T>)
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>;
+
+impl<'v, 'a, T> Serialize for RowMajor<'v, 'a, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let view = self.0;
+        serializer.collect_seq((0..view.rows).flat_map(|i| view.row_slice(i)))
+    }
+}
+
+// Reads `{rows, cols, data}`, checking that `data` holds exactly rows * cols elements. Human
+// readable formats such as JSON may instead give the rows as nested arrays, `[[1, 2], [3, 4]]`.
+impl<'de, T> Deserialize<'de> for Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(MatrixVisitor {
+                nested: true,
+                marker: PhantomData,
+            })
+        } else {
+            // Compact formats like bincode are not self-describing, a sequence here is the
+            // struct written as a tuple
+            deserializer.deserialize_struct(
+                "Matrix",
+                FIELDS,
+                MatrixVisitor {
+                    nested: false,
+                    marker: PhantomData,
+                },
+            )
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Rows,
+    Cols,
+    Data,
+}
+
+struct MatrixVisitor<T> {
+    // Whether a sequence is a list of rows rather than `(rows, cols, data)`
+    nested: bool,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T> Visitor<'de> for MatrixVisitor<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Deserialize<'de>,
+{
+    type Value = Matrix<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.nested {
+            f.write_str("a matrix as {rows, cols, data} or as an array of rows")
+        } else {
+            f.write_str("a matrix as {rows, cols, data}")
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        if !self.nested {
+            let rows = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let cols = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            let data = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+            return from_parts(rows, cols, data);
+        }
+
+        let mut data = AVec::new(CACHELINE_ALIGN);
+        let mut rows = 0;
+        let mut cols = None;
+        while let Some(row) = seq.next_element::<Vec<T>>()? {
+            let expected = *cols.get_or_insert(row.len());
+            if row.len() != expected {
+                return Err(de::Error::custom(LinalgError::RaggedInput {
+                    index: rows,
+                    expected,
+                    found: row.len(),
+                }));
+            }
+            data.extend_from_slice(&row);
+            rows += 1;
+        }
+
+        Ok(Matrix {
+            rows,
+            cols: cols.unwrap_or(0),
+            data,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut rows = None;
+        let mut cols = None;
+        let mut data = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Rows if rows.is_some() => return Err(de::Error::duplicate_field("rows")),
+                Field::Cols if cols.is_some() => return Err(de::Error::duplicate_field("cols")),
+                Field::Data if data.is_some() => return Err(de::Error::duplicate_field("data")),
+                Field::Rows => rows = Some(map.next_value()?),
+                Field::Cols => cols = Some(map.next_value()?),
+                Field::Data => data = Some(map.next_value()?),
+            }
+        }
+
+        from_parts(
+            rows.ok_or_else(|| de::Error::missing_field("rows"))?,
+            cols.ok_or_else(|| de::Error::missing_field("cols"))?,
+            data.ok_or_else(|| de::Error::missing_field("data"))?,
+        )
+    }
+}
+
+// The checks `from_vec` asserts, reported as a deserialization error instead of a panic
+fn from_parts<T, E>(rows: usize, cols: usize, data: Vec<T>) -> Result<Matrix<T>, E>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    E: de::Error,
+{
+    if rows.checked_mul(cols) != Some(data.len()) {
+        return Err(E::custom(format_args!(
+            "data has {} elements, expected rows * cols = {rows} * {cols}",
+            data.len()
+        )));
+    }
+    Ok(Matrix {
+        rows,
+        cols,
+        data: AVec::from_iter(CACHELINE_ALIGN, data),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+
+    #[test]
+    fn test_json_round_trip() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(
+            json,
+            r#"{"rows":2,"cols":3,"data":[1.0,2.0,3.0,4.0,5.0,6.0]}"#
+        );
+        assert_eq!(serde_json::from_str::<Matrix<f64>>(&json).unwrap(), m);
+    }
+
+    #[test]
+    fn test_json_nested_rows() {
+        let m: Matrix<i32> = serde_json::from_str("[[1, 2], [3, 4], [5, 6]]").unwrap();
+        assert_eq!(m, Matrix::new([[1, 2], [3, 4], [5, 6]]));
+
+        let empty: Matrix<i32> = serde_json::from_str("[]").unwrap();
+        assert_eq!((empty.rows(), empty.cols()), (0, 0));
+
+        let err = serde_json::from_str::<Matrix<i32>>("[[1, 2], [3]]").unwrap_err();
+        assert!(
+            err.to_string().starts_with("Input 1 has 1 elements"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_length_is_checked() {
+        let err = serde_json::from_str::<Matrix<f64>>(r#"{"rows":2,"cols":2,"data":[1,2,3]}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("data has 3 elements"), "{err}");
+
+        let err = serde_json::from_str::<Matrix<f64>>(r#"{"rows":2,"data":[1,2]}"#).unwrap_err();
+        assert!(err.to_string().contains("missing field `cols`"), "{err}");
+    }
+
+    #[test]
+    fn test_complex_round_trip() {
+        let m = Matrix::new([[c64::new(1.0, -1.0), c64::new(0.0, 2.5)]]);
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(json, r#"{"rows":1,"cols":2,"data":[[1.0,-1.0],[0.0,2.5]]}"#);
+        assert_eq!(serde_json::from_str::<Matrix<c64>>(&json).unwrap(), m);
+    }
+
+    #[test]
+    fn test_view_serializes_as_matrix() {
+        let m = Matrix::from_fn(4, 4, |i, j| (4 * i + j) as f32);
+        let view = m.view(1..3, 1..4);
+        let json = serde_json::to_string(&view).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Matrix<f32>>(&json).unwrap(),
+            view.to_matrix()
+        );
+    }
+
+    #[test]
+    fn test_message_pack_round_trip() {
+        let m = Matrix::new([[c64::new(1.0, 2.0)], [c64::new(3.0, 4.0)]]);
+        let compact = rmp_serde::to_vec(&m).unwrap();
+        assert_eq!(rmp_serde::from_slice::<Matrix<c64>>(&compact).unwrap(), m);
+        let named = rmp_serde::to_vec_named(&m).unwrap();
+        assert_eq!(rmp_serde::from_slice::<Matrix<c64>>(&named).unwrap(), m);
+
+        let short = rmp_serde::to_vec(&(3usize, 1usize, vec![c64::new(1.0, 0.0)])).unwrap();
+        assert!(rmp_serde::from_slice::<Matrix<c64>>(&short).is_err());
+    }
+}
@@ -1,12 +1,15 @@
 use num::complex::{Complex32 as c32, Complex64 as c64};
 #[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
+use std::ops::Add;
 
 pub trait SimdOps: Sized {
     type Vector;
 
     const LANE_SIZE: usize;
     const PREFETCH_DISTANCE: usize;
+    // Byte alignment required by `load_aligned` and `store_aligned`
+    const ALIGNMENT: usize = 32;
 
     #[cfg(target_arch = "x86_64")]
     unsafe fn load(ptr: *const Self) -> Self::Vector;
@@ -14,12 +17,68 @@ pub trait SimdOps: Sized {
     #[cfg(target_arch = "x86_64")]
     unsafe fn store(ptr: *mut Self, vec: Self::Vector);
 
+    // `ptr` must be aligned to `ALIGNMENT` bytes
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn load_aligned(ptr: *const Self) -> Self::Vector {
+        Self::load(ptr)
+    }
+
+    // `ptr` must be aligned to `ALIGNMENT` bytes
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn store_aligned(ptr: *mut Self, vec: Self::Vector) {
+        Self::store(ptr, vec)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn is_aligned(ptr: *const Self) -> bool {
+        (ptr as usize).is_multiple_of(Self::ALIGNMENT)
+    }
+
     #[cfg(target_arch = "x86_64")]
     unsafe fn add(a: Self::Vector, b: Self::Vector) -> Self::Vector;
 
     #[cfg(target_arch = "x86_64")]
     unsafe fn sub(a: Self::Vector, b: Self::Vector) -> Self::Vector;
 
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn zeroed() -> Self::Vector
+    where
+        Self: Copy + Default,
+    {
+        // No vector holds more than 8 lanes
+        Self::load([Self::default(); 8].as_ptr())
+    }
+
+    // Horizontal sum of the LANE_SIZE lanes of `vec`
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn reduce_add(vec: Self::Vector) -> Self
+    where
+        Self: Copy + Default + Add<Output = Self>,
+    {
+        let mut lanes = [Self::default(); 8];
+        Self::store(lanes.as_mut_ptr(), vec);
+        lanes[..Self::LANE_SIZE]
+            .iter()
+            .fold(Self::default(), |acc, &x| acc + x)
+    }
+
+    // Transposes a LANE_SIZE x LANE_SIZE tile, strides are given in elements.
+    // SimdOps is only implemented for plain-old-data types, so the bitwise copy is sound.
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn transpose_tile(
+        src: *const Self,
+        src_stride: usize,
+        dst: *mut Self,
+        dst_stride: usize,
+    ) {
+        for i in 0..Self::LANE_SIZE {
+            for j in 0..Self::LANE_SIZE {
+                dst.add(j * dst_stride + i)
+                    .write(src.add(i * src_stride + j).read());
+            }
+        }
+    }
+
     #[cfg(target_arch = "x86_64")]
     unsafe fn prefetch(ptr: *const Self) {
         // _MM_HINT_T0: Prefetch data into all levels of the cache hierarchy
@@ -30,6 +89,89 @@ pub trait SimdOps: Sized {
     fn has_simd_support() -> bool;
 }
 
+// 8x8 register transpose: interleave pairs of rows, then 64-bit pairs, then 128-bit lanes
+#[cfg(target_arch = "x86_64")]
+unsafe fn transpose_8x8_ps(src: *const f32, src_stride: usize, dst: *mut f32, dst_stride: usize) {
+    let r0 = _mm256_loadu_ps(src);
+    let r1 = _mm256_loadu_ps(src.add(src_stride));
+    let r2 = _mm256_loadu_ps(src.add(2 * src_stride));
+    let r3 = _mm256_loadu_ps(src.add(3 * src_stride));
+    let r4 = _mm256_loadu_ps(src.add(4 * src_stride));
+    let r5 = _mm256_loadu_ps(src.add(5 * src_stride));
+    let r6 = _mm256_loadu_ps(src.add(6 * src_stride));
+    let r7 = _mm256_loadu_ps(src.add(7 * src_stride));
+
+    let t0 = _mm256_unpacklo_ps(r0, r1);
+    let t1 = _mm256_unpackhi_ps(r0, r1);
+    let t2 = _mm256_unpacklo_ps(r2, r3);
+    let t3 = _mm256_unpackhi_ps(r2, r3);
+    let t4 = _mm256_unpacklo_ps(r4, r5);
+    let t5 = _mm256_unpackhi_ps(r4, r5);
+    let t6 = _mm256_unpacklo_ps(r6, r7);
+    let t7 = _mm256_unpackhi_ps(r6, r7);
+
+    let s0 = _mm256_shuffle_ps::<0x44>(t0, t2);
+    let s1 = _mm256_shuffle_ps::<0xEE>(t0, t2);
+    let s2 = _mm256_shuffle_ps::<0x44>(t1, t3);
+    let s3 = _mm256_shuffle_ps::<0xEE>(t1, t3);
+    let s4 = _mm256_shuffle_ps::<0x44>(t4, t6);
+    let s5 = _mm256_shuffle_ps::<0xEE>(t4, t6);
+    let s6 = _mm256_shuffle_ps::<0x44>(t5, t7);
+    let s7 = _mm256_shuffle_ps::<0xEE>(t5, t7);
+
+    _mm256_storeu_ps(dst, _mm256_permute2f128_ps::<0x20>(s0, s4));
+    _mm256_storeu_ps(dst.add(dst_stride), _mm256_permute2f128_ps::<0x20>(s1, s5));
+    _mm256_storeu_ps(
+        dst.add(2 * dst_stride),
+        _mm256_permute2f128_ps::<0x20>(s2, s6),
+    );
+    _mm256_storeu_ps(
+        dst.add(3 * dst_stride),
+        _mm256_permute2f128_ps::<0x20>(s3, s7),
+    );
+    _mm256_storeu_ps(
+        dst.add(4 * dst_stride),
+        _mm256_permute2f128_ps::<0x31>(s0, s4),
+    );
+    _mm256_storeu_ps(
+        dst.add(5 * dst_stride),
+        _mm256_permute2f128_ps::<0x31>(s1, s5),
+    );
+    _mm256_storeu_ps(
+        dst.add(6 * dst_stride),
+        _mm256_permute2f128_ps::<0x31>(s2, s6),
+    );
+    _mm256_storeu_ps(
+        dst.add(7 * dst_stride),
+        _mm256_permute2f128_ps::<0x31>(s3, s7),
+    );
+}
+
+// 4x4 register transpose: interleave pairs of rows, then swap 128-bit lanes
+#[cfg(target_arch = "x86_64")]
+unsafe fn transpose_4x4_pd(src: *const f64, src_stride: usize, dst: *mut f64, dst_stride: usize) {
+    let r0 = _mm256_loadu_pd(src);
+    let r1 = _mm256_loadu_pd(src.add(src_stride));
+    let r2 = _mm256_loadu_pd(src.add(2 * src_stride));
+    let r3 = _mm256_loadu_pd(src.add(3 * src_stride));
+
+    let t0 = _mm256_unpacklo_pd(r0, r1);
+    let t1 = _mm256_unpackhi_pd(r0, r1);
+    let t2 = _mm256_unpacklo_pd(r2, r3);
+    let t3 = _mm256_unpackhi_pd(r2, r3);
+
+    _mm256_storeu_pd(dst, _mm256_permute2f128_pd::<0x20>(t0, t2));
+    _mm256_storeu_pd(dst.add(dst_stride), _mm256_permute2f128_pd::<0x20>(t1, t3));
+    _mm256_storeu_pd(
+        dst.add(2 * dst_stride),
+        _mm256_permute2f128_pd::<0x31>(t0, t2),
+    );
+    _mm256_storeu_pd(
+        dst.add(3 * dst_stride),
+        _mm256_permute2f128_pd::<0x31>(t1, t3),
+    );
+}
+
 // x86_64 implementations (AVX2)
 #[cfg(target_arch = "x86_64")]
 impl SimdOps for f32 {
@@ -49,6 +191,23 @@ impl SimdOps for f32 {
         _mm256_storeu_ps(ptr, vec)
     }
 
+    unsafe fn load_aligned(ptr: *const Self) -> Self::Vector {
+        _mm256_load_ps(ptr)
+    }
+
+    unsafe fn store_aligned(ptr: *mut Self, vec: Self::Vector) {
+        _mm256_store_ps(ptr, vec)
+    }
+
+    unsafe fn transpose_tile(
+        src: *const Self,
+        src_stride: usize,
+        dst: *mut Self,
+        dst_stride: usize,
+    ) {
+        transpose_8x8_ps(src, src_stride, dst, dst_stride)
+    }
+
     unsafe fn add(a: Self::Vector, b: Self::Vector) -> Self::Vector {
         _mm256_add_ps(a, b)
     }
@@ -77,6 +236,23 @@ impl SimdOps for f64 {
         _mm256_storeu_pd(ptr, vec)
     }
 
+    unsafe fn load_aligned(ptr: *const Self) -> Self::Vector {
+        _mm256_load_pd(ptr)
+    }
+
+    unsafe fn store_aligned(ptr: *mut Self, vec: Self::Vector) {
+        _mm256_store_pd(ptr, vec)
+    }
+
+    unsafe fn transpose_tile(
+        src: *const Self,
+        src_stride: usize,
+        dst: *mut Self,
+        dst_stride: usize,
+    ) {
+        transpose_4x4_pd(src, src_stride, dst, dst_stride)
+    }
+
     unsafe fn add(a: Self::Vector, b: Self::Vector) -> Self::Vector {
         _mm256_add_pd(a, b)
     }
@@ -104,6 +280,23 @@ impl SimdOps for u32 {
         _mm256_storeu_si256(ptr as *mut __m256i, vec)
     }
 
+    unsafe fn load_aligned(ptr: *const Self) -> Self::Vector {
+        _mm256_load_si256(ptr as *const __m256i)
+    }
+
+    unsafe fn store_aligned(ptr: *mut Self, vec: Self::Vector) {
+        _mm256_store_si256(ptr as *mut __m256i, vec)
+    }
+
+    unsafe fn transpose_tile(
+        src: *const Self,
+        src_stride: usize,
+        dst: *mut Self,
+        dst_stride: usize,
+    ) {
+        transpose_8x8_ps(src as *const f32, src_stride, dst as *mut f32, dst_stride)
+    }
+
     unsafe fn add(a: Self::Vector, b: Self::Vector) -> Self::Vector {
         _mm256_add_epi32(a, b)
     }
@@ -131,6 +324,23 @@ impl SimdOps for u64 {
         _mm256_storeu_si256(ptr as *mut __m256i, vec)
     }
 
+    unsafe fn load_aligned(ptr: *const Self) -> Self::Vector {
+        _mm256_load_si256(ptr as *const __m256i)
+    }
+
+    unsafe fn store_aligned(ptr: *mut Self, vec: Self::Vector) {
+        _mm256_store_si256(ptr as *mut __m256i, vec)
+    }
+
+    unsafe fn transpose_tile(
+        src: *const Self,
+        src_stride: usize,
+        dst: *mut Self,
+        dst_stride: usize,
+    ) {
+        transpose_4x4_pd(src as *const f64, src_stride, dst as *mut f64, dst_stride)
+    }
+
     unsafe fn add(a: Self::Vector, b: Self::Vector) -> Self::Vector {
         _mm256_add_epi64(a, b)
     }
@@ -158,6 +368,23 @@ impl SimdOps for i32 {
         _mm256_storeu_si256(ptr as *mut __m256i, vec)
     }
 
+    unsafe fn load_aligned(ptr: *const Self) -> Self::Vector {
+        _mm256_load_si256(ptr as *const __m256i)
+    }
+
+    unsafe fn store_aligned(ptr: *mut Self, vec: Self::Vector) {
+        _mm256_store_si256(ptr as *mut __m256i, vec)
+    }
+
+    unsafe fn transpose_tile(
+        src: *const Self,
+        src_stride: usize,
+        dst: *mut Self,
+        dst_stride: usize,
+    ) {
+        transpose_8x8_ps(src as *const f32, src_stride, dst as *mut f32, dst_stride)
+    }
+
     unsafe fn add(a: Self::Vector, b: Self::Vector) -> Self::Vector {
         _mm256_add_epi32(a, b)
     }
@@ -185,6 +412,23 @@ impl SimdOps for i64 {
         _mm256_storeu_si256(ptr as *mut __m256i, vec)
     }
 
+    unsafe fn load_aligned(ptr: *const Self) -> Self::Vector {
+        _mm256_load_si256(ptr as *const __m256i)
+    }
+
+    unsafe fn store_aligned(ptr: *mut Self, vec: Self::Vector) {
+        _mm256_store_si256(ptr as *mut __m256i, vec)
+    }
+
+    unsafe fn transpose_tile(
+        src: *const Self,
+        src_stride: usize,
+        dst: *mut Self,
+        dst_stride: usize,
+    ) {
+        transpose_4x4_pd(src as *const f64, src_stride, dst as *mut f64, dst_stride)
+    }
+
     unsafe fn add(a: Self::Vector, b: Self::Vector) -> Self::Vector {
         _mm256_add_epi64(a, b)
     }
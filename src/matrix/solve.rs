@@ -0,0 +1,103 @@
+use super::mat::Matrix;
+use crate::error::LinalgError;
+use num::complex;
+use num_traits::Float;
+
+// Solution of A X = B together with an estimate of how far it can be trusted
+#[derive(Debug, Clone)]
+pub struct Solution<T>
+where
+    T: complex::ComplexFloat,
+{
+    pub x: Matrix<T>,
+    // Estimated reciprocal 1-norm condition number of A; about -log10(rcond) digits of X are lost
+    pub rcond: T::Real,
+}
+
+impl<T> Solution<T>
+where
+    T: complex::ComplexFloat,
+{
+    // A is singular to working precision, same test as LAPACK's expert drivers
+    pub fn is_ill_conditioned(&self) -> bool {
+        self.rcond.is_nan() || self.rcond < T::Real::epsilon()
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: complex::ComplexFloat + 'static,
+{
+    // General square A, through LU with partial pivoting
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Solution<T>, LinalgError> {
+        self.check_rhs(b)?;
+        let lu = self.lu()?;
+        Ok(Solution {
+            x: lu.solve(b),
+            rcond: lu.rcond(),
+        })
+    }
+
+    // Hermitian positive definite A, through Cholesky. About half the work of `solve`.
+    pub fn solve_positive_definite(&self, b: &Matrix<T>) -> Result<Solution<T>, LinalgError> {
+        self.check_rhs(b)?;
+        let cholesky = self.cholesky()?;
+        Ok(Solution {
+            x: cholesky.solve(b),
+            rcond: cholesky.rcond(),
+        })
+    }
+
+    // B needs one row per row of A, the factorizations assert it
+    fn check_rhs(&self, b: &Matrix<T>) -> Result<(), LinalgError> {
+        if b.rows == self.rows {
+            Ok(())
+        } else {
+            Err(LinalgError::DimensionMismatch {
+                expected: (self.rows, b.cols),
+                found: (b.rows, b.cols),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_reports_conditioning() {
+        let a = Matrix::new([[4.0, 1.0], [1.0, 3.0]]);
+        let b = Matrix::new([[1.0], [2.0]]);
+
+        let general = a.solve(&b).unwrap();
+        let spd = a.solve_positive_definite(&b).unwrap();
+        for s in [&general, &spd] {
+            assert!((s.x[(0, 0)] - 1.0 / 11.0).abs() < 1e-12);
+            assert!((s.x[(1, 0)] - 7.0 / 11.0).abs() < 1e-12);
+            assert!((s.rcond - 11.0 / 25.0).abs() < 1e-12);
+            assert!(!s.is_ill_conditioned());
+        }
+    }
+
+    #[test]
+    fn test_solve_rejects_wrong_sized_rhs() {
+        let a = Matrix::new([[4.0, 1.0], [1.0, 3.0]]);
+        let b = Matrix::new([[1.0], [2.0], [3.0]]);
+        let expected = LinalgError::DimensionMismatch {
+            expected: (2, 1),
+            found: (3, 1),
+        };
+
+        assert_eq!(a.solve(&b).unwrap_err(), expected);
+        assert_eq!(a.solve_positive_definite(&b).unwrap_err(), expected);
+    }
+
+    #[test]
+    fn test_solve_flags_near_singular_system() {
+        let a = Matrix::new([[1.0, 1.0], [1.0, 1.0 + 2.0 * f64::EPSILON]]);
+        let b = Matrix::new([[2.0], [2.0]]);
+        let solution = a.solve(&b).unwrap();
+        assert!(solution.is_ill_conditioned());
+    }
+}
@@ -1,4 +1,5 @@
 use super::{mat::Matrix, simd::SimdOps};
+use aligned_vec::{avec, AVec, CACHELINE_ALIGN};
 use rayon::prelude::*;
 use std::ops::{Add, Mul, Sub, SubAssign};
 
@@ -37,14 +38,15 @@ fn sub_scalar<T>(m1: &Matrix<T>, m2: &Matrix<T>) -> Matrix<T>
 where
     T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
 {
-    let data: Vec<T> = m1
-        .data
-        .iter()
-        .zip(&m2.data)
-        .map(|(a, b)| a.clone() - b.clone())
-        .collect();
-
-    Matrix::from_vec(m1.rows, m1.cols, data)
+    let data = AVec::from_iter(
+        CACHELINE_ALIGN,
+        m1.data
+            .iter()
+            .zip(m2.data.iter())
+            .map(|(a, b)| a.clone() - b.clone()),
+    );
+
+    Matrix::from_aligned(m1.rows, m1.cols, data)
 }
 
 #[cfg(target_arch = "x86_64")]
@@ -60,8 +62,7 @@ where
         + Sync,
 {
     let total_elements = m1.rows * m1.cols;
-    let mut result = Vec::with_capacity(total_elements);
-    result.set_len(total_elements);
+    let mut result = avec![T::default(); total_elements];
 
     result
         .par_chunks_mut(T::LANE_SIZE * 128)
@@ -69,6 +70,10 @@ where
         .zip(m2.data.par_chunks(T::LANE_SIZE * 128))
         .for_each(|((r, a), b)| {
             let chunks = r.len() / T::LANE_SIZE;
+            // Matrix storage is cache-line aligned and chunk sizes are a multiple of the
+            // lane width, so this only fails for storage not allocated by `Matrix`
+            let aligned =
+                T::is_aligned(a.as_ptr()) && T::is_aligned(b.as_ptr()) && T::is_aligned(r.as_ptr());
 
             for i in 0..chunks {
                 let offset = i * T::LANE_SIZE;
@@ -86,19 +91,26 @@ where
                 }
 
                 // Regular SIMD loading, subtraction, and storing
-                let m1_vec = T::load(a[offset..].as_ptr());
-                let m2_vec = T::load(b[offset..].as_ptr());
-                let sum = <T as SimdOps>::sub(m1_vec, m2_vec);
-                T::store(r[offset..].as_mut_ptr(), sum);
+                if aligned {
+                    let m1_vec = T::load_aligned(a[offset..].as_ptr());
+                    let m2_vec = T::load_aligned(b[offset..].as_ptr());
+                    let sum = <T as SimdOps>::sub(m1_vec, m2_vec);
+                    T::store_aligned(r[offset..].as_mut_ptr(), sum);
+                } else {
+                    let m1_vec = T::load(a[offset..].as_ptr());
+                    let m2_vec = T::load(b[offset..].as_ptr());
+                    let sum = <T as SimdOps>::sub(m1_vec, m2_vec);
+                    T::store(r[offset..].as_mut_ptr(), sum);
+                }
             }
 
             let remaining_start = chunks * T::LANE_SIZE;
             for i in remaining_start..r.len() {
-                r[i] = a[i].clone() + b[i].clone();
+                r[i] = a[i].clone() - b[i].clone();
             }
         });
 
-    Matrix::from_vec(m1.rows, m1.cols, result)
+    Matrix::from_aligned(m1.rows, m1.cols, result)
 }
 
 impl<T> Sub for Matrix<T>
@@ -133,7 +145,7 @@ where
     type Output = Matrix<T>;
 
     fn sub(self, other: &Matrix<T>) -> Self::Output {
-        sub_matrix_impl(&self, &other)
+        sub_matrix_impl(self, other)
     }
 }
 
@@ -169,7 +181,7 @@ where
 {
     m1.data
         .iter_mut()
-        .zip(&m2.data)
+        .zip(m2.data.iter())
         .for_each(|(a, b)| *a -= b.clone());
 }
 
@@ -298,4 +310,15 @@ mod tests {
             assert_eq!(m3[(row, col)], c64::new(-1.0, 0.0));
         }
     }
+
+    #[test]
+    fn test_sub_large_matrix_tail() {
+        // Not a multiple of any lane width, so the last chunk ends in a scalar tail
+        let size = 513;
+        let m1 = Matrix::from_vec(size, size, vec![3.0f64; size * size]);
+        let m2 = Matrix::from_vec(size, size, vec![1.0f64; size * size]);
+        let m3 = &m1 - &m2;
+        assert!(m3.data.iter().all(|&x| x == 2.0));
+        assert_eq!(m3[(size - 1, size - 1)], 2.0);
+    }
 }
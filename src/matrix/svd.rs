@@ -0,0 +1,184 @@
+use super::{mat::Matrix, mat_view::MatrixView};
+use num::complex;
+use num_traits::{Float, NumCast, One, Zero};
+
+const MAX_SWEEPS: usize = 60;
+
+impl<T> Matrix<T>
+where
+    T: complex::ComplexFloat,
+{
+    // Singular values in descending order, min(rows, cols) of them
+    pub fn singular_values(&self) -> Vec<T::Real> {
+        singular_values(&self.data, self.rows, self.cols, self.cols)
+    }
+}
+
+impl<'a, T> MatrixView<'a, T>
+where
+    T: complex::ComplexFloat,
+{
+    pub fn singular_values(&self) -> Vec<T::Real> {
+        singular_values(self.data, self.rows, self.cols, self.stride)
+    }
+}
+
+// One-sided (Hestenes) Jacobi: rotate pairs of columns until all are mutually orthogonal, the
+// singular values are then the column norms. Costs O(min(m, n)^2 max(m, n)) per sweep, so it is
+// meant for small to medium matrices.
+pub(crate) fn singular_values<T>(
+    data: &[T],
+    rows: usize,
+    cols: usize,
+    stride: usize,
+) -> Vec<T::Real>
+where
+    T: complex::ComplexFloat,
+{
+    let zero = T::Real::zero();
+    let one = T::Real::one();
+    let two = one + one;
+
+    // Work on whichever of A and A^H has fewer columns, they share their nonzero singular values
+    let mut columns: Vec<Vec<T>> = if cols <= rows {
+        (0..cols)
+            .map(|j| (0..rows).map(|i| data[i * stride + j]).collect())
+            .collect()
+    } else {
+        (0..rows)
+            .map(|i| (0..cols).map(|j| data[i * stride + j].conj()).collect())
+            .collect()
+    };
+
+    // Scale entries into [-1, 1] so the squared column norms can neither overflow nor underflow
+    let scale = columns
+        .iter()
+        .flatten()
+        .fold(zero, |m, x| Float::max(m, x.abs()));
+    if scale == zero || !scale.is_finite() {
+        return vec![scale; columns.len()];
+    }
+    let inv_scale = from_real::<T>(one / scale);
+    columns
+        .iter_mut()
+        .flatten()
+        .for_each(|x| *x = *x * inv_scale);
+
+    let n = columns.len();
+    let eps = T::Real::epsilon();
+
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let (left, right) = columns.split_at_mut(q);
+                let (a_p, a_q) = (&mut left[p], &mut right[0]);
+
+                let alpha = a_p.iter().fold(zero, |s, x| s + x.abs() * x.abs());
+                let beta = a_q.iter().fold(zero, |s, x| s + x.abs() * x.abs());
+                let gamma = a_p
+                    .iter()
+                    .zip(a_q.iter())
+                    .fold(T::zero(), |s, (&x, &y)| s + x.conj() * y);
+
+                let g = gamma.abs();
+                if g == zero || g <= eps * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                // Rotating a_q by the phase of gamma makes the 2x2 Gram matrix real symmetric
+                let phase = (gamma * from_real::<T>(one / g)).conj();
+                let zeta = (beta - alpha) / (two * g);
+                let t = zeta.signum() / (zeta.abs() + (one + zeta * zeta).sqrt());
+                let c = one / (one + t * t).sqrt();
+                let s = c * t;
+                let (c, s) = (from_real::<T>(c), from_real::<T>(s));
+
+                for (x, y) in a_p.iter_mut().zip(a_q.iter_mut()) {
+                    let (u, v) = (*x, *y * phase);
+                    *x = u * c - v * s;
+                    *y = u * s + v * c;
+                }
+            }
+        }
+
+        if !rotated {
+            break;
+        }
+    }
+
+    let mut values: Vec<T::Real> = columns
+        .iter()
+        .map(|column| {
+            column
+                .iter()
+                .fold(zero, |s, x| s + x.abs() * x.abs())
+                .sqrt()
+                * scale
+        })
+        .collect();
+    values.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    values
+}
+
+// Lifts a real scalar into T, the imaginary part of a complex T is zero
+pub(crate) fn from_real<T: complex::ComplexFloat>(x: T::Real) -> T {
+    <T as NumCast>::from(x).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex64 as c64;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-10 * e.abs().max(1.0), "{a} != {e}");
+        }
+    }
+
+    #[test]
+    fn test_singular_values_square() {
+        let m = Matrix::new([[3.0, 0.0], [4.0, 5.0]]);
+        let expected = [45.0f64.sqrt(), 5.0f64.sqrt()];
+        assert_close(&m.singular_values(), &expected);
+    }
+
+    #[test]
+    fn test_singular_values_wide_and_tall() {
+        let wide = Matrix::new([[1.0, 0.0, 0.0], [0.0, 2.0, 0.0]]);
+        assert_close(&wide.singular_values(), &[2.0, 1.0]);
+        assert_close(&wide.transpose().singular_values(), &[2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_singular_values_complex() {
+        let m = Matrix::new([
+            [c64::new(0.0, 3.0), c64::new(0.0, 0.0)],
+            [c64::new(0.0, 0.0), c64::new(4.0, 0.0)],
+        ]);
+        assert_close(&m.singular_values(), &[4.0, 3.0]);
+
+        let h = Matrix::new([
+            [c64::new(1.0, 1.0), c64::new(2.0, -1.0)],
+            [c64::new(0.0, 2.0), c64::new(1.0, 0.0)],
+        ]);
+        let sv = h.singular_values();
+        // The squared singular values sum to the squared Frobenius norm and multiply to |det|^2
+        let fro2: f64 = h.data.iter().map(|z| z.norm_sqr()).sum();
+        let det = h[(0, 0)] * h[(1, 1)] - h[(0, 1)] * h[(1, 0)];
+        assert!((sv[0] * sv[0] + sv[1] * sv[1] - fro2).abs() < 1e-10);
+        assert!((sv[0] * sv[1] - det.norm()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_singular_values_rank_deficient() {
+        let m = Matrix::new([[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]);
+        let sv = m.singular_values();
+        assert!((sv[0] - 70.0f64.sqrt()).abs() < 1e-10);
+        assert!(sv[1].abs() < 1e-10);
+    }
+}
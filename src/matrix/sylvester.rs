@@ -0,0 +1,203 @@
+use super::{
+    mat::Matrix,
+    scalar::{adjoint, from_complex, to_complex, Scalar},
+    schur::complex_schur,
+};
+use crate::error::LinalgError;
+use num::complex::Complex;
+use num_traits::{Float, One, Zero};
+
+impl<T: Scalar> Matrix<T> {
+    // Solves A X + X B = C by Bartels-Stewart: both A and B go to complex Schur form, which
+    // leaves a triangular system that is solved one column at a time. Errors with `Singular`
+    // when A and -B share an eigenvalue.
+    pub fn solve_sylvester(
+        a: &Matrix<T>,
+        b: &Matrix<T>,
+        c: &Matrix<T>,
+    ) -> Result<Matrix<T>, LinalgError> {
+        check_square(a)?;
+        check_square(b)?;
+        check_shape(c, (a.rows, b.rows))?;
+
+        let (ta, ua) = complex_schur(to_complex(a))?;
+        let (tb, ub) = complex_schur(to_complex(b))?;
+        let f = &(&adjoint(&ua) * &to_complex(c)) * &ub;
+        let y = triangular_sylvester(&ta, &tb, &f)?;
+        Ok(from_complex(&(&(&ua * &y) * &adjoint(&ub))))
+    }
+
+    // Solves the continuous Lyapunov equation A X + X A^H = Q. The controllability Gramian of
+    // (A, B) is `solve_continuous_lyapunov(A, -B B^H)`.
+    pub fn solve_continuous_lyapunov(
+        a: &Matrix<T>,
+        q: &Matrix<T>,
+    ) -> Result<Matrix<T>, LinalgError> {
+        Self::solve_sylvester(a, &adjoint(a), q)
+    }
+
+    // Solves the discrete Lyapunov (Stein) equation A X A^H - X + Q = 0. The controllability
+    // Gramian of (A, B) is `solve_discrete_lyapunov(A, B B^H)`.
+    pub fn solve_discrete_lyapunov(a: &Matrix<T>, q: &Matrix<T>) -> Result<Matrix<T>, LinalgError> {
+        check_square(a)?;
+        check_shape(q, (a.rows, a.rows))?;
+
+        let (t, u) = complex_schur(to_complex(a))?;
+        let f = &(&adjoint(&u) * &to_complex(q)) * &u;
+        let y = triangular_stein(&t, &f)?;
+        Ok(from_complex(&(&(&u * &y) * &adjoint(&u))))
+    }
+}
+
+pub(crate) fn check_square<T: Scalar>(m: &Matrix<T>) -> Result<(), LinalgError> {
+    if m.rows != m.cols {
+        return Err(LinalgError::NotSquare {
+            rows: m.rows,
+            cols: m.cols,
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn check_shape<T: Scalar>(
+    m: &Matrix<T>,
+    expected: (usize, usize),
+) -> Result<(), LinalgError> {
+    if (m.rows, m.cols) != expected {
+        return Err(LinalgError::DimensionMismatch {
+            expected,
+            found: (m.rows, m.cols),
+        });
+    }
+    Ok(())
+}
+
+// T Y + Y S = F with T and S upper triangular. Column k only depends on columns before it.
+fn triangular_sylvester<R: Float>(
+    t: &Matrix<Complex<R>>,
+    s: &Matrix<Complex<R>>,
+    f: &Matrix<Complex<R>>,
+) -> Result<Matrix<Complex<R>>, LinalgError> {
+    let (m, n) = (t.rows, s.rows);
+    let mut y = f.clone();
+
+    for k in 0..n {
+        for i in 0..m {
+            let known = (0..k).fold(Complex::zero(), |acc, j| acc + s[(j, k)] * y[(i, j)]);
+            y[(i, k)] = y[(i, k)] - known;
+        }
+        for i in (0..m).rev() {
+            let denominator = t[(i, i)] + s[(k, k)];
+            if denominator == Complex::zero() {
+                return Err(LinalgError::Singular { pivot: i });
+            }
+            let known = (i + 1..m).fold(Complex::zero(), |acc, l| acc + t[(i, l)] * y[(l, k)]);
+            y[(i, k)] = (y[(i, k)] - known) / denominator;
+        }
+    }
+    Ok(y)
+}
+
+// T Y T^H - Y + F = 0 with T upper triangular. Column j needs the columns after it:
+// (conj(t_jj) T - I) y_j = -f_j - T sum_{l > j} conj(t_jl) y_l
+fn triangular_stein<R: Float>(
+    t: &Matrix<Complex<R>>,
+    f: &Matrix<Complex<R>>,
+) -> Result<Matrix<Complex<R>>, LinalgError> {
+    let n = t.rows;
+    let mut y = f.map(|_| Complex::zero());
+
+    for j in (0..n).rev() {
+        let w: Vec<Complex<R>> = (0..n)
+            .map(|i| (j + 1..n).fold(Complex::zero(), |acc, l| acc + t[(j, l)].conj() * y[(i, l)]))
+            .collect();
+        let mut rhs: Vec<Complex<R>> = (0..n)
+            .map(|i| {
+                let tw = (i..n).fold(Complex::zero(), |acc, l| acc + t[(i, l)] * w[l]);
+                -f[(i, j)] - tw
+            })
+            .collect();
+
+        let scale = t[(j, j)].conj();
+        for i in (0..n).rev() {
+            let denominator = scale * t[(i, i)] - Complex::one();
+            if denominator == Complex::zero() {
+                return Err(LinalgError::Singular { pivot: i });
+            }
+            let known = (i + 1..n).fold(Complex::zero(), |acc, l| acc + scale * t[(i, l)] * rhs[l]);
+            rhs[i] = (rhs[i] - known) / denominator;
+        }
+        for (i, &x) in rhs.iter().enumerate() {
+            y[(i, j)] = x;
+        }
+    }
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num::c64;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn assert_small(m: &Matrix<f64>, tolerance: f64) {
+        assert!(m.norm_max() < tolerance, "residual {}", m.norm_max());
+    }
+
+    #[test]
+    fn test_sylvester_residual() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let a = Matrix::<f64>::random_normal(6, 6, &mut rng);
+        let b = Matrix::<f64>::random_normal(4, 4, &mut rng);
+        let c = Matrix::<f64>::random_normal(6, 4, &mut rng);
+
+        let x = Matrix::solve_sylvester(&a, &b, &c).unwrap();
+        assert_small(&(&(&(&a * &x) + &(&x * &b)) - &c), 1e-10);
+    }
+
+    #[test]
+    fn test_sylvester_complex() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let a = Matrix::<c64>::random_normal(4, 4, &mut rng);
+        let b = Matrix::<c64>::random_normal(3, 3, &mut rng);
+        let c = Matrix::<c64>::random_normal(4, 3, &mut rng);
+
+        let x = Matrix::solve_sylvester(&a, &b, &c).unwrap();
+        let residual = &(&(&a * &x) + &(&x * &b)) - &c;
+        assert!(residual.norm_max() < 1e-10);
+    }
+
+    #[test]
+    fn test_sylvester_errors() {
+        let a = Matrix::new([[1.0, 0.0], [0.0, 2.0]]);
+        let b = Matrix::new([[-1.0]]);
+        assert_eq!(
+            Matrix::solve_sylvester(&a, &b, &Matrix::new([[1.0], [1.0]])).unwrap_err(),
+            LinalgError::Singular { pivot: 0 }
+        );
+        assert_eq!(
+            Matrix::solve_sylvester(&a, &b, &Matrix::new([[1.0, 1.0]])).unwrap_err(),
+            LinalgError::DimensionMismatch {
+                expected: (2, 1),
+                found: (1, 2)
+            }
+        );
+    }
+
+    #[test]
+    fn test_controllability_gramians() {
+        // Stable continuous system
+        let a = Matrix::new([[-1.0, 2.0, 0.0], [0.0, -3.0, 1.0], [0.5, 0.0, -2.0]]);
+        let b = Matrix::new([[1.0], [0.0], [2.0]]);
+        let bbt = &b * &b.transpose();
+        let w = Matrix::solve_continuous_lyapunov(&a, &-&bbt).unwrap();
+        assert_small(&(&(&(&a * &w) + &(&w * &a.transpose())) + &bbt), 1e-12);
+        assert_small(&(&w - &w.transpose()), 1e-12);
+
+        // Schur-stable discrete system
+        let ad = a.clone() * 0.2;
+        let wd = Matrix::solve_discrete_lyapunov(&ad, &bbt).unwrap();
+        let residual = &(&(&(&ad * &wd) * &ad.transpose()) - &wd) + &bbt;
+        assert_small(&residual, 1e-12);
+    }
+}
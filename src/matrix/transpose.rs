@@ -1,23 +1,251 @@
-use super::mat::Matrix;
-use std::ops::{Add, Mul, Sub};
+use super::{mat::Matrix, simd::SimdOps};
+use aligned_vec::avec;
+use num::complex::{Complex, Complex32 as c32, Complex64 as c64};
+use rayon::prelude::*;
+use std::ops::{Add, Mul, Neg, Range, Sub};
+
+const BLOCK_SIZE: usize = 64; // Tile edge, a multiple of every SIMD lane size
+const PARALLEL_THRESHOLD: usize = 256 * 256; // Minimum elements for rayon to be worth it
+
+// Copies the rows x cols tile of `src` at (row_range, col_range) into `dst`, transposed
+type TileKernel<T> = fn(&[T], usize, &mut [T], usize, Range<usize>, Range<usize>, usize);
+
+// Element types `transpose` can move. By default the scalar tiles run on one thread, so any
+// element type can opt in with an empty impl; the primitive numeric types below use SIMD tiles
+// where they have them and split large matrices across threads.
+pub trait TransposeElement: Clone + Sized {
+    // Writes the cols x rows transpose of the rows x cols `src` into `dst`
+    fn transpose_into(src: &[Self], rows: usize, cols: usize, dst: &mut [Self]) {
+        for (panel, dst) in dst.chunks_mut(BLOCK_SIZE * rows).enumerate() {
+            transpose_panel(
+                src,
+                rows,
+                cols,
+                dst,
+                panel * BLOCK_SIZE,
+                transpose_tile_scalar,
+            );
+        }
+    }
+}
+
+macro_rules! impl_transpose_element {
+    ($kernel:ident: $($t:ty),*) => {
+        $(
+            impl TransposeElement for $t {
+                fn transpose_into(src: &[Self], rows: usize, cols: usize, dst: &mut [Self]) {
+                    transpose_parallel(src, rows, cols, dst, $kernel::<$t>);
+                }
+            }
+        )*
+    };
+}
+
+impl_transpose_element!(transpose_tile_simd: f32, f64, u32, u64, i32, i64, c32, c64);
+impl_transpose_element!(
+    transpose_tile_scalar: i8, i16, i128, isize, u8, u16, u128, usize,
+    Complex<i8>, Complex<i16>, Complex<i32>, Complex<i64>
+);
 
 impl<T> Matrix<T>
 where
-    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default + TransposeElement,
 {
     pub fn transpose(&self) -> Matrix<T> {
-        let mut transposed_data = Vec::with_capacity(self.rows * self.cols);
+        let (rows, cols) = (self.rows, self.cols);
+        let mut transposed_data = avec![T::default(); rows * cols];
+        if rows > 0 && cols > 0 {
+            T::transpose_into(&self.data, rows, cols, &mut transposed_data);
+        }
+        Matrix::from_aligned(cols, rows, transposed_data)
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    // Moves the elements within the existing storage, without allocating
+    pub fn transpose_in_place(&mut self) {
+        if self.rows == self.cols {
+            transpose_square_in_place(&mut self.data, self.rows);
+        } else {
+            transpose_cycles_in_place(&mut self.data, self.rows, self.cols);
+            std::mem::swap(&mut self.rows, &mut self.cols);
+        }
+    }
+}
 
-        for col in 0..self.cols {
-            for row in 0..self.rows {
-                transposed_data.push(self.data[row * self.cols + col].clone());
+impl<T> Matrix<Complex<T>>
+where
+    T: Clone + num_traits::Num + Neg<Output = T>,
+    Complex<T>: Default + TransposeElement,
+{
+    pub fn adjoint(&self) -> Matrix<Complex<T>> {
+        let mut adjoint = self.transpose();
+        adjoint.data.iter_mut().for_each(|z| *z = z.conj());
+        adjoint
+    }
+}
+
+// Each panel holds BLOCK_SIZE rows of the result, i.e. BLOCK_SIZE columns of the source
+fn transpose_parallel<T: Clone + Send + Sync>(
+    src: &[T],
+    rows: usize,
+    cols: usize,
+    dst: &mut [T],
+    kernel: TileKernel<T>,
+) {
+    let panel = |(panel, dst): (usize, &mut [T])| {
+        transpose_panel(src, rows, cols, dst, panel * BLOCK_SIZE, kernel)
+    };
+    if rows * cols >= PARALLEL_THRESHOLD {
+        dst.par_chunks_mut(BLOCK_SIZE * rows)
+            .enumerate()
+            .for_each(panel);
+    } else {
+        dst.chunks_mut(BLOCK_SIZE * rows)
+            .enumerate()
+            .for_each(panel);
+    }
+}
+
+// Writes source columns col_start..col_start + dst.len() / rows into `dst`, tile by tile
+fn transpose_panel<T>(
+    src: &[T],
+    rows: usize,
+    cols: usize,
+    dst: &mut [T],
+    col_start: usize,
+    kernel: TileKernel<T>,
+) {
+    let col_end = col_start + dst.len() / rows;
+
+    for row_start in (0..rows).step_by(BLOCK_SIZE) {
+        let row_end = (row_start + BLOCK_SIZE).min(rows);
+        kernel(
+            src,
+            cols,
+            dst,
+            rows,
+            row_start..row_end,
+            col_start..col_end,
+            col_start,
+        );
+    }
+}
+
+fn transpose_tile_simd<T>(
+    src: &[T],
+    cols: usize,
+    dst: &mut [T],
+    rows: usize,
+    row_range: Range<usize>,
+    col_range: Range<usize>,
+    col_offset: usize,
+) where
+    T: Clone + SimdOps,
+{
+    let lane = T::LANE_SIZE;
+    let mut simd_rows = row_range.start;
+    let mut simd_cols = col_range.start;
+
+    #[cfg(target_arch = "x86_64")]
+    if T::has_simd_support() {
+        let full_rows = (row_range.end - row_range.start) / lane * lane;
+        let full_cols = (col_range.end - col_range.start) / lane * lane;
+
+        for i in (row_range.start..row_range.start + full_rows).step_by(lane) {
+            for j in (col_range.start..col_range.start + full_cols).step_by(lane) {
+                // Both lane x lane tiles lie entirely within their slices
+                unsafe {
+                    T::transpose_tile(
+                        src[i * cols + j..].as_ptr(),
+                        cols,
+                        dst[(j - col_offset) * rows + i..].as_mut_ptr(),
+                        rows,
+                    );
+                }
             }
         }
 
-        Matrix {
-            rows: self.cols,
-            cols: self.rows,
-            data: transposed_data,
+        simd_rows = row_range.start + full_rows;
+        simd_cols = col_range.start + full_cols;
+    }
+
+    // Scalar edges: the right strip of every row, then the bottom rows
+    let (right, bottom) = (simd_cols..col_range.end, simd_rows..row_range.end);
+    transpose_tile_scalar(
+        src,
+        cols,
+        dst,
+        rows,
+        row_range.start..simd_rows,
+        right,
+        col_offset,
+    );
+    transpose_tile_scalar(src, cols, dst, rows, bottom, col_range, col_offset);
+}
+
+fn transpose_tile_scalar<T: Clone>(
+    src: &[T],
+    cols: usize,
+    dst: &mut [T],
+    rows: usize,
+    row_range: Range<usize>,
+    col_range: Range<usize>,
+    col_offset: usize,
+) {
+    for i in row_range {
+        for j in col_range.clone() {
+            dst[(j - col_offset) * rows + i] = src[i * cols + j].clone();
+        }
+    }
+}
+
+fn transpose_square_in_place<T>(data: &mut [T], n: usize) {
+    for block_row in (0..n).step_by(BLOCK_SIZE) {
+        for block_col in (block_row..n).step_by(BLOCK_SIZE) {
+            for i in block_row..(block_row + BLOCK_SIZE).min(n) {
+                let j_start = if block_row == block_col {
+                    i + 1
+                } else {
+                    block_col
+                };
+                for j in j_start..(block_col + BLOCK_SIZE).min(n) {
+                    data.swap(i * n + j, j * n + i);
+                }
+            }
+        }
+    }
+}
+
+// Element k of a rows x cols matrix moves to (k * rows) mod (n - 1), the first and last stay put.
+// Each permutation cycle is followed once, swapping its elements through the cycle start.
+fn transpose_cycles_in_place<T>(data: &mut [T], rows: usize, cols: usize) {
+    let n = rows * cols;
+    if n < 3 {
+        return;
+    }
+
+    let modulus = (n - 1) as u128;
+    let destination = |k: usize| ((k as u128 * rows as u128) % modulus) as usize;
+
+    for start in 1..n - 1 {
+        // Only the smallest index of a cycle leads it, which takes a walk round the cycle to
+        // check but no memory
+        let mut next = destination(start);
+        while next > start {
+            next = destination(next);
+        }
+        if next < start {
+            continue;
+        }
+
+        let mut next = destination(start);
+        while next != start {
+            data.swap(start, next);
+            next = destination(next);
         }
     }
 }
@@ -25,6 +253,18 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num::complex::Complex64 as c64;
+    use std::num::Wrapping;
+
+    fn naive_transpose<T: Clone>(data: &[T], rows: usize, cols: usize) -> Vec<T> {
+        let mut result = Vec::with_capacity(rows * cols);
+        for col in 0..cols {
+            for row in 0..rows {
+                result.push(data[row * cols + col].clone());
+            }
+        }
+        result
+    }
 
     #[test]
     fn test_transpose() {
@@ -33,7 +273,7 @@ mod tests {
 
         assert_eq!(transposed.rows, 2);
         assert_eq!(transposed.cols, 3);
-        assert_eq!(transposed.data, vec![1, 3, 5, 2, 4, 6]);
+        assert_eq!(transposed.data.as_slice(), vec![1, 3, 5, 2, 4, 6]);
     }
 
     #[test]
@@ -44,7 +284,7 @@ mod tests {
 
         assert_eq!(transposed.rows, 2);
         assert_eq!(transposed.cols, 2);
-        assert_eq!(transposed.data, vec![1, 3, 2, 4]);
+        assert_eq!(transposed.data.as_slice(), vec![1, 3, 2, 4]);
     }
 
     #[test]
@@ -55,6 +295,111 @@ mod tests {
 
         assert_eq!(transposed.rows, 3);
         assert_eq!(transposed.cols, 1);
-        assert_eq!(transposed.data, vec![1, 2, 3]);
+        assert_eq!(transposed.data.as_slice(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_transpose_large_f32() {
+        let (rows, cols) = (301, 517);
+        let data: Vec<f32> = (0..rows * cols).map(|i| i as f32).collect();
+        let matrix = Matrix::from_vec(rows, cols, data.clone());
+
+        let transposed = matrix.transpose();
+
+        assert_eq!(transposed.rows, cols);
+        assert_eq!(transposed.cols, rows);
+        assert_eq!(
+            transposed.data.as_slice(),
+            naive_transpose(&data, rows, cols)
+        );
+    }
+
+    #[test]
+    fn test_transpose_large_i64() {
+        let (rows, cols) = (130, 67);
+        let data: Vec<i64> = (0..(rows * cols) as i64).collect();
+        let matrix = Matrix::from_vec(rows, cols, data.clone());
+
+        let transposed = matrix.transpose();
+
+        assert_eq!(
+            transposed.data.as_slice(),
+            naive_transpose(&data, rows, cols)
+        );
+    }
+
+    #[test]
+    fn test_transpose_large_without_simd() {
+        // Neither element type has a SIMD kernel, both go through the scalar tiles
+        let (rows, cols) = (300, 250);
+        let data: Vec<i16> = (0..rows * cols).map(|i| i as i16).collect();
+        let matrix = Matrix::from_vec(rows, cols, data.clone());
+        assert_eq!(
+            matrix.transpose().data.as_slice(),
+            naive_transpose(&data, rows, cols)
+        );
+
+        let data: Vec<Complex<i32>> = (0..70 * 90).map(|i| Complex::new(i, -i)).collect();
+        let matrix = Matrix::from_vec(70, 90, data.clone());
+        assert_eq!(
+            matrix.transpose().data.as_slice(),
+            naive_transpose(&data, 70, 90)
+        );
+    }
+
+    // Other element types opt in and get the scalar tiles on one thread
+    impl TransposeElement for Wrapping<u8> {}
+
+    #[test]
+    fn test_transpose_opted_in_type() {
+        let (rows, cols) = (90, 70);
+        let data: Vec<Wrapping<u8>> = (0..rows * cols).map(|i| Wrapping(i as u8)).collect();
+        let matrix = Matrix::from_vec(rows, cols, data.clone());
+        assert_eq!(
+            matrix.transpose().data.as_slice(),
+            naive_transpose(&data, rows, cols)
+        );
+    }
+
+    #[test]
+    fn test_transpose_in_place_square() {
+        let n = 70;
+        let data: Vec<u32> = (0..(n * n) as u32).collect();
+        let mut matrix = Matrix::from_vec(n, n, data.clone());
+
+        matrix.transpose_in_place();
+
+        assert_eq!(matrix.data.as_slice(), naive_transpose(&data, n, n));
+    }
+
+    #[test]
+    fn test_transpose_in_place_rectangular() {
+        for (rows, cols) in [(13, 29), (2, 3), (1, 5), (64, 3), (100, 37)] {
+            let data: Vec<f64> = (0..rows * cols).map(|i| i as f64).collect();
+            let mut matrix = Matrix::from_vec(rows, cols, data.clone());
+
+            matrix.transpose_in_place();
+
+            assert_eq!(matrix.rows, cols);
+            assert_eq!(matrix.cols, rows);
+            assert_eq!(matrix.data.as_slice(), naive_transpose(&data, rows, cols));
+        }
+    }
+
+    #[test]
+    fn test_adjoint() {
+        let matrix: Matrix<c64> = Matrix::new([
+            [c64::new(1.0, 1.0), c64::new(2.0, -2.0), c64::new(0.0, 3.0)],
+            [c64::new(4.0, 0.0), c64::new(5.0, 5.0), c64::new(6.0, -1.0)],
+        ]);
+
+        let adjoint = matrix.adjoint();
+
+        assert_eq!(adjoint.rows, 3);
+        assert_eq!(adjoint.cols, 2);
+        assert_eq!(adjoint[(0, 0)], c64::new(1.0, -1.0));
+        assert_eq!(adjoint[(1, 0)], c64::new(2.0, 2.0));
+        assert_eq!(adjoint[(2, 0)], c64::new(0.0, -3.0));
+        assert_eq!(adjoint[(2, 1)], c64::new(6.0, 1.0));
     }
 }
@@ -0,0 +1,694 @@
+use crate::{
+    error::LinalgError,
+    matrix::{cholesky::Cholesky, lu::Lu, mat::Matrix, mat_view::MatrixView, out_of_core},
+};
+use pyo3::{
+    buffer::PyBuffer,
+    create_exception,
+    exceptions::{PyBufferError, PyIndexError, PyValueError},
+    ffi,
+    prelude::*,
+    pyclass::CompareOp,
+};
+use std::{
+    borrow::Cow,
+    ffi::{c_char, c_int, c_void},
+    ptr, slice,
+};
+
+// Python bindings for `Matrix<f64>`, built as the `linalg` extension module with maturin (see
+// pyproject.toml). A C-contiguous buffer of doubles, such as a NumPy array, is not copied: the
+// matrix holds the buffer and the operators, transpose and singular values read it in place
+// through a `MatrixView`. Strided or Fortran-ordered buffers are gathered into a row-major copy,
+// and so are borrowed buffers handed to solve, lu and cholesky, which factor a copy of their input
+// anyway. A borrowed array should not be written to while a kernel reads it. Matrices export
+// their own storage through the buffer protocol too, so `numpy.asarray(m)` does not copy. The
+// kernels run with the GIL released, and a panic in one is raised as pyo3's PanicException.
+
+create_exception!(linalg, LinAlgError, PyValueError);
+
+impl From<LinalgError> for PyErr {
+    fn from(err: LinalgError) -> Self {
+        LinAlgError::new_err(err.to_string())
+    }
+}
+
+// Bytes of tiles the view kernels work through at a time, 128x128 doubles per operand
+const TILE_BUDGET: usize = 3 * 128 * 128 * 8;
+
+enum Storage {
+    Owned(Matrix<f64>),
+    // A C-contiguous buffer read in place, held until the matrix is dropped
+    Buffer {
+        buffer: PyBuffer<f64>,
+        rows: usize,
+        cols: usize,
+    },
+}
+
+impl Storage {
+    fn view(&self) -> MatrixView<'_, f64> {
+        match self {
+            Storage::Owned(m) => m.view(0..m.rows, 0..m.cols),
+            Storage::Buffer { buffer, rows, cols } => {
+                let len = rows * cols;
+                let data = if len == 0 {
+                    &[]
+                } else {
+                    // SAFETY: `PyBuffer::get` checked the format and alignment, the buffer is
+                    // C-contiguous with `len` elements, and it stays acquired while `self` lives
+                    unsafe { slice::from_raw_parts(buffer.buf_ptr() as *const f64, len) }
+                };
+                MatrixView::new(*rows, *cols, data)
+            }
+        }
+    }
+
+    fn owned(&self) -> Option<&Matrix<f64>> {
+        match self {
+            Storage::Owned(m) => Some(m),
+            Storage::Buffer { .. } => None,
+        }
+    }
+
+    // For the kernels that only take a `Matrix`
+    fn matrix(&self) -> Cow<'_, Matrix<f64>> {
+        match self {
+            Storage::Owned(m) => Cow::Borrowed(m),
+            Storage::Buffer { .. } => Cow::Owned(self.view().to_matrix()),
+        }
+    }
+}
+
+#[pyclass(name = "Matrix", module = "linalg", frozen)]
+pub struct PyMatrix {
+    storage: Storage,
+}
+
+// A `Matrix` operand, or anything `Matrix(...)` accepts
+enum Operand<'py> {
+    Borrowed(PyRef<'py, PyMatrix>),
+    Converted(Storage),
+}
+
+impl Operand<'_> {
+    fn storage(&self) -> &Storage {
+        match self {
+            Operand::Borrowed(m) => &m.storage,
+            Operand::Converted(s) => s,
+        }
+    }
+}
+
+impl<'py> FromPyObject<'_, 'py> for Operand<'py> {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, 'py, PyAny>) -> PyResult<Self> {
+        match obj.cast::<PyMatrix>() {
+            Ok(m) => Ok(Operand::Borrowed(m.borrow())),
+            Err(_) => to_storage(&obj).map(Operand::Converted),
+        }
+    }
+}
+
+// 2-D buffers keep their shape and 1-D ones become a column; nested sequences are taken as rows
+fn to_storage(obj: &Bound<'_, PyAny>) -> PyResult<Storage> {
+    let Ok(buffer) = PyBuffer::<f64>::get(obj) else {
+        let rows: Vec<Vec<f64>> = obj.extract()?;
+        return Ok(Storage::Owned(Matrix::from_rows(&rows)?));
+    };
+    let (rows, cols) = match *buffer.shape() {
+        [rows, cols] => (rows, cols),
+        [rows] => (rows, 1),
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Expected a 1-D or 2-D buffer, got {} dimensions",
+                buffer.dimensions()
+            )))
+        }
+    };
+    if buffer.is_c_contiguous() {
+        Ok(Storage::Buffer { buffer, rows, cols })
+    } else {
+        // Strided or Fortran-ordered, gathered into row-major order
+        let data = buffer.to_vec(obj.py())?;
+        Ok(Storage::Owned(Matrix::from_vec(rows, cols, data)))
+    }
+}
+
+fn same_shape(a: &MatrixView<f64>, b: &MatrixView<f64>) -> Result<(), LinalgError> {
+    if (a.rows, a.cols) == (b.rows, b.cols) {
+        Ok(())
+    } else {
+        Err(LinalgError::DimensionMismatch {
+            expected: (a.rows, a.cols),
+            found: (b.rows, b.cols),
+        })
+    }
+}
+
+fn chained(a: &MatrixView<f64>, b: &MatrixView<f64>) -> Result<(), LinalgError> {
+    if a.cols == b.rows {
+        Ok(())
+    } else {
+        Err(LinalgError::DimensionMismatch {
+            expected: (a.cols, b.cols),
+            found: (b.rows, b.cols),
+        })
+    }
+}
+
+// Right-hand sides of a system with `n` equations
+fn rows_match(n: usize, b: &Matrix<f64>) -> Result<(), LinalgError> {
+    if b.rows == n {
+        Ok(())
+    } else {
+        Err(LinalgError::DimensionMismatch {
+            expected: (n, b.cols),
+            found: (b.rows, b.cols),
+        })
+    }
+}
+
+// Owned matrices take the SIMD kernel `simd`, borrowed buffers apply `f` row by row
+fn map(
+    a: &Storage,
+    simd: impl Fn(&Matrix<f64>) -> Matrix<f64>,
+    f: impl Fn(f64) -> f64,
+) -> Matrix<f64> {
+    if let Some(a) = a.owned() {
+        return simd(a);
+    }
+    let a = a.view();
+    Matrix::from_row_iter(
+        a.rows,
+        a.cols,
+        (0..a.rows).flat_map(|i| a.row_slice(i).iter().map(|&x| f(x))),
+    )
+}
+
+fn zip_with(
+    a: &Storage,
+    b: &Storage,
+    simd: impl Fn(&Matrix<f64>, &Matrix<f64>) -> Matrix<f64>,
+    f: impl Fn(f64, f64) -> f64,
+) -> Matrix<f64> {
+    if let (Some(a), Some(b)) = (a.owned(), b.owned()) {
+        return simd(a, b);
+    }
+    let (a, b) = (a.view(), b.view());
+    Matrix::from_row_iter(
+        a.rows,
+        a.cols,
+        (0..a.rows).flat_map(|i| {
+            let (x, y) = (a.row_slice(i), b.row_slice(i));
+            x.iter().zip(y).map(|(&x, &y)| f(x, y))
+        }),
+    )
+}
+
+fn product(a: &Storage, b: &Storage) -> Matrix<f64> {
+    if let (Some(a), Some(b)) = (a.owned(), b.owned()) {
+        return a * b;
+    }
+    let (a, b) = (a.view(), b.view());
+    let mut c = Matrix::zeros(a.rows, b.cols);
+    out_of_core::multiply(&a, &b, &mut c.view_mut(0..a.rows, 0..b.cols), TILE_BUDGET);
+    c
+}
+
+fn transpose(a: &Storage) -> Matrix<f64> {
+    if let Some(a) = a.owned() {
+        return a.transpose();
+    }
+    let a = a.view();
+    let mut t = Matrix::zeros(a.cols, a.rows);
+    out_of_core::transpose(&a, &mut t.view_mut(0..a.cols, 0..a.rows), TILE_BUDGET);
+    t
+}
+
+impl From<Matrix<f64>> for PyMatrix {
+    fn from(inner: Matrix<f64>) -> Self {
+        PyMatrix {
+            storage: Storage::Owned(inner),
+        }
+    }
+}
+
+#[pymethods]
+impl PyMatrix {
+    #[new]
+    fn new(data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyMatrix {
+            storage: to_storage(data)?,
+        })
+    }
+
+    #[staticmethod]
+    fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix::zeros(rows, cols).into()
+    }
+
+    #[staticmethod]
+    fn identity(n: usize) -> Self {
+        Matrix::identity(n, n).into()
+    }
+
+    // NumPy hands mixed operations such as `array + m` back to `__radd__` and friends instead of
+    // treating the matrix as an object array, and prefers Matrix results where it still looks
+    #[classattr]
+    fn __array_ufunc__(py: Python<'_>) -> Py<PyAny> {
+        py.None()
+    }
+
+    #[classattr]
+    fn __array_priority__() -> f64 {
+        1000.0
+    }
+
+    #[getter]
+    fn shape(&self) -> (usize, usize) {
+        let m = self.storage.view();
+        (m.rows, m.cols)
+    }
+
+    fn __getitem__(&self, index: (usize, usize)) -> PyResult<f64> {
+        let m = self.storage.view();
+        let (i, j) = index;
+        if i >= m.rows || j >= m.cols {
+            return Err(PyIndexError::new_err(format!(
+                "Index ({i}, {j}) out of range for a {}x{} matrix",
+                m.rows, m.cols
+            )));
+        }
+        Ok(m[(i, j)])
+    }
+
+    fn tolist(&self) -> Vec<Vec<f64>> {
+        let m = self.storage.view();
+        (0..m.rows).map(|i| m.row_slice(i).to_vec()).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Matrix({:?})", self.tolist())
+    }
+
+    fn __richcmp__(&self, other: &Bound<'_, PyAny>, op: CompareOp) -> PyResult<Py<PyAny>> {
+        let py = other.py();
+        let Ok(other) = other.extract::<Operand>() else {
+            return Ok(py.NotImplemented());
+        };
+        let equal = self.storage.view() == other.storage().view();
+        match op {
+            CompareOp::Eq => Ok(equal.into_pyobject(py)?.to_owned().into_any().unbind()),
+            CompareOp::Ne => Ok((!equal).into_pyobject(py)?.to_owned().into_any().unbind()),
+            _ => Ok(py.NotImplemented()),
+        }
+    }
+
+    fn __add__(&self, py: Python<'_>, other: Operand) -> PyResult<Self> {
+        let (a, b) = (&self.storage, other.storage());
+        same_shape(&a.view(), &b.view())?;
+        Ok(py
+            .detach(|| zip_with(a, b, |a, b| a + b, |x, y| x + y))
+            .into())
+    }
+
+    fn __radd__(&self, py: Python<'_>, other: Operand) -> PyResult<Self> {
+        let (a, b) = (other.storage(), &self.storage);
+        same_shape(&a.view(), &b.view())?;
+        Ok(py
+            .detach(|| zip_with(a, b, |a, b| a + b, |x, y| x + y))
+            .into())
+    }
+
+    fn __sub__(&self, py: Python<'_>, other: Operand) -> PyResult<Self> {
+        let (a, b) = (&self.storage, other.storage());
+        same_shape(&a.view(), &b.view())?;
+        Ok(py
+            .detach(|| zip_with(a, b, |a, b| a - b, |x, y| x - y))
+            .into())
+    }
+
+    fn __rsub__(&self, py: Python<'_>, other: Operand) -> PyResult<Self> {
+        let (a, b) = (other.storage(), &self.storage);
+        same_shape(&a.view(), &b.view())?;
+        Ok(py
+            .detach(|| zip_with(a, b, |a, b| a - b, |x, y| x - y))
+            .into())
+    }
+
+    // As in Rust, `*` is the matrix product between matrices and scales by a number
+    fn __mul__(&self, py: Python<'_>, other: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(x) = other.extract::<f64>() {
+            return Ok(self.scale(py, x));
+        }
+        self.__matmul__(py, other.extract()?)
+    }
+
+    fn __rmul__(&self, py: Python<'_>, other: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(x) = other.extract::<f64>() {
+            return Ok(self.scale(py, x));
+        }
+        self.__rmatmul__(py, other.extract()?)
+    }
+
+    fn __matmul__(&self, py: Python<'_>, other: Operand) -> PyResult<Self> {
+        let (a, b) = (&self.storage, other.storage());
+        chained(&a.view(), &b.view())?;
+        Ok(py.detach(|| product(a, b)).into())
+    }
+
+    fn __rmatmul__(&self, py: Python<'_>, other: Operand) -> PyResult<Self> {
+        let (a, b) = (other.storage(), &self.storage);
+        chained(&a.view(), &b.view())?;
+        Ok(py.detach(|| product(a, b)).into())
+    }
+
+    fn __neg__(&self) -> Self {
+        map(&self.storage, |a| -a, |x| -x).into()
+    }
+
+    #[getter(T)]
+    fn transpose(&self, py: Python<'_>) -> Self {
+        py.detach(|| transpose(&self.storage)).into()
+    }
+
+    // General square A, through LU; raises LinAlgError when A is singular
+    fn solve(&self, py: Python<'_>, b: Operand) -> PyResult<Self> {
+        let (a, b) = (&self.storage, b.storage());
+        Ok(py.detach(|| a.matrix().solve(&b.matrix()))?.x.into())
+    }
+
+    fn lu(&self, py: Python<'_>) -> PyResult<PyLu> {
+        Ok(PyLu(py.detach(|| self.storage.matrix().lu())?))
+    }
+
+    fn cholesky(&self, py: Python<'_>) -> PyResult<PyCholesky> {
+        Ok(PyCholesky(py.detach(|| self.storage.matrix().cholesky())?))
+    }
+
+    fn singular_values(&self, py: Python<'_>) -> Vec<f64> {
+        py.detach(|| self.storage.view().singular_values())
+    }
+
+    // Exports the storage as a read-only, row-major 2-D buffer of doubles. The matrix is
+    // immutable from Python and the view keeps it alive, so the memory stays valid until it is
+    // released.
+    unsafe fn __getbuffer__(
+        slf: Bound<'_, Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+        let requested = |flag| flags & flag == flag;
+        if requested(ffi::PyBUF_WRITABLE) {
+            return Err(PyBufferError::new_err("Matrix is read-only"));
+        }
+        let m = slf.get().storage.view();
+        // Row-major storage is only Fortran-contiguous as a single row or column
+        if requested(ffi::PyBUF_F_CONTIGUOUS) && m.rows > 1 && m.cols > 1 {
+            return Err(PyBufferError::new_err("Matrix is not Fortran-contiguous"));
+        }
+        let item = std::mem::size_of::<f64>() as isize;
+        let (rows, cols, stride) = (m.rows as isize, m.cols as isize, m.stride as isize);
+        let buf = m.data.as_ptr() as *mut c_void;
+        // Shape then strides, freed in `__releasebuffer__`
+        let layout: *mut [isize; 4] = Box::into_raw(Box::new([rows, cols, stride * item, item]));
+
+        // SAFETY: `view` is a valid, writable Py_buffer provided by the interpreter
+        unsafe {
+            (*view).obj = slf.into_any().into_ptr();
+            (*view).buf = buf;
+            (*view).len = rows * cols * item;
+            (*view).readonly = 1;
+            (*view).itemsize = item;
+            (*view).format = if requested(ffi::PyBUF_FORMAT) {
+                c"d".as_ptr() as *mut c_char
+            } else {
+                ptr::null_mut()
+            };
+            (*view).ndim = 2;
+            (*view).shape = if requested(ffi::PyBUF_ND) {
+                layout.cast()
+            } else {
+                ptr::null_mut()
+            };
+            (*view).strides = if requested(ffi::PyBUF_STRIDES) {
+                layout.cast::<isize>().add(2)
+            } else {
+                ptr::null_mut()
+            };
+            (*view).suboffsets = ptr::null_mut();
+            (*view).internal = layout.cast();
+        }
+        Ok(())
+    }
+
+    unsafe fn __releasebuffer__(&self, view: *mut ffi::Py_buffer) {
+        // SAFETY: `internal` holds the layout boxed by `__getbuffer__`
+        unsafe { drop(Box::from_raw((*view).internal.cast::<[isize; 4]>())) };
+    }
+}
+
+impl PyMatrix {
+    fn scale(&self, py: Python<'_>, x: f64) -> Self {
+        py.detach(|| map(&self.storage, |a| a.clone() * x, |y| y * x))
+            .into()
+    }
+}
+
+#[pyclass(name = "Lu", module = "linalg", frozen)]
+pub struct PyLu(Lu<f64>);
+
+#[pymethods]
+impl PyLu {
+    #[getter]
+    fn l(&self) -> PyMatrix {
+        self.0.l().into()
+    }
+
+    #[getter]
+    fn u(&self) -> PyMatrix {
+        self.0.u().into()
+    }
+
+    // Row i of P A is row `permutation[i]` of A
+    #[getter]
+    fn permutation(&self) -> Vec<usize> {
+        self.0.permutation().indices().to_vec()
+    }
+
+    fn det(&self) -> f64 {
+        self.0.det()
+    }
+
+    fn rcond(&self) -> f64 {
+        self.0.rcond()
+    }
+
+    fn solve(&self, py: Python<'_>, b: Operand) -> PyResult<PyMatrix> {
+        let b = b.storage().matrix();
+        rows_match(self.0.permutation().len(), &b)?;
+        Ok(py.detach(|| self.0.solve(&b)).into())
+    }
+}
+
+#[pyclass(name = "Cholesky", module = "linalg", frozen)]
+pub struct PyCholesky(Cholesky<f64>);
+
+#[pymethods]
+impl PyCholesky {
+    #[getter]
+    fn l(&self) -> PyMatrix {
+        self.0.l().clone().into()
+    }
+
+    fn rcond(&self) -> f64 {
+        self.0.rcond()
+    }
+
+    fn solve(&self, py: Python<'_>, b: Operand) -> PyResult<PyMatrix> {
+        let b = b.storage().matrix();
+        rows_match(self.0.l().rows, &b)?;
+        Ok(py.detach(|| self.0.solve(&b)).into())
+    }
+}
+
+#[pymodule]
+fn linalg(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyMatrix>()?;
+    m.add_class::<PyLu>()?;
+    m.add_class::<PyCholesky>()?;
+    m.add("LinAlgError", m.py().get_type::<LinAlgError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+    use std::ffi::CStr;
+
+    // Runs Python `code` with this module importable as `linalg`
+    fn run(code: &CStr) {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "linalg").unwrap();
+            linalg(&module).unwrap();
+            py.import("sys")
+                .and_then(|sys| sys.getattr("modules"))
+                .and_then(|modules| modules.set_item("linalg", module))
+                .unwrap();
+            let globals = PyDict::new(py);
+            if let Err(err) = py.run(code, Some(&globals), None) {
+                panic!("{err}");
+            }
+        });
+    }
+
+    #[test]
+    fn test_operators() {
+        run(c"
+import array
+from linalg import Matrix, LinAlgError
+
+a = Matrix([[1.0, 2.0], [3.0, 4.0]])
+b = Matrix(memoryview(array.array('d', [5, 6, 7, 8])).cast('B').cast('d', (2, 2)))
+assert (a + b).tolist() == [[6, 8], [10, 12]]
+assert (b - a).tolist() == [[4, 4], [4, 4]]
+assert (a * b).tolist() == [[19, 22], [43, 50]]
+assert a @ b == a * b
+assert (2 * a).tolist() == [[2, 4], [6, 8]] and a * 2 == 2 * a
+assert [[1, 1], [1, 1]] + a == a + [[1, 1], [1, 1]]
+assert (-a).tolist() == [[-1, -2], [-3, -4]]
+assert a.T.tolist() == [[1, 3], [2, 4]]
+assert a.shape == (2, 2) and a[1, 0] == 3.0
+
+try:
+    a + Matrix.zeros(3, 2)
+    assert False
+except LinAlgError as err:
+    assert str(err) == 'Expected a 2x2 matrix, got 3x2'
+");
+    }
+
+    #[test]
+    fn test_buffer_protocol() {
+        run(c"
+import array
+from linalg import Matrix
+
+m = Matrix([[1, 2, 3], [4, 5, 6]])
+view = memoryview(m)
+assert view.shape == (2, 3) and view.strides == (24, 8)
+assert view.format == 'd' and view.readonly
+assert view.tolist() == m.tolist()
+assert Matrix(view) == m
+
+column = Matrix(array.array('d', [1, 2, 3]))
+assert column.shape == (3, 1)
+strided = memoryview(array.array('d', range(12))).cast('B').cast('d', (3, 4))[::2]
+assert Matrix(strided).tolist() == [[0, 1, 2, 3], [8, 9, 10, 11]]
+
+# Contiguous buffers are read in place, strided ones copied
+data = array.array('d', range(6))
+borrowed = Matrix(memoryview(data).cast('B').cast('d', (2, 3)))
+copied = Matrix(memoryview(data).cast('B').cast('d', (3, 2))[::2])
+data[0] = 10
+assert borrowed[0, 0] == 10 and copied[0, 0] == 0
+assert Matrix.__array_ufunc__ is None and Matrix.__array_priority__ > 0
+");
+
+        // Exported views point at the matrix's own storage
+        Python::attach(|py| {
+            let m = Bound::new(py, PyMatrix::from(Matrix::new([[1.0, 2.0]]))).unwrap();
+            let buffer = PyBuffer::<f64>::get(m.as_any()).unwrap();
+            assert_eq!(
+                buffer.buf_ptr() as *const f64,
+                m.get().storage.view().data.as_ptr()
+            );
+        });
+
+        // Fortran order is only offered for vector shapes
+        Python::attach(|py| {
+            let flags = ffi::PyBUF_F_CONTIGUOUS | ffi::PyBUF_FORMAT;
+            for (m, exported) in [
+                (Matrix::new([[1.0, 2.0], [3.0, 4.0]]), false),
+                (Matrix::new([[1.0, 2.0]]), true),
+            ] {
+                let m = Bound::new(py, PyMatrix::from(m)).unwrap();
+                let mut view = std::mem::MaybeUninit::<ffi::Py_buffer>::uninit();
+                // SAFETY: `view` is released below whenever the export succeeds
+                let status =
+                    unsafe { ffi::PyObject_GetBuffer(m.as_ptr(), view.as_mut_ptr(), flags) };
+                if exported {
+                    assert_eq!(status, 0);
+                    unsafe { ffi::PyBuffer_Release(view.as_mut_ptr()) };
+                } else {
+                    assert_eq!(status, -1);
+                    assert!(PyErr::take(py).unwrap().is_instance_of::<PyBufferError>(py));
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_borrowed_buffers() {
+        run(c"
+import array
+from linalg import Matrix
+
+def borrowed(rows):
+    cols = len(rows[0])
+    data = array.array('d', [x for row in rows for x in row])
+    return Matrix(memoryview(data).cast('B').cast('d', (len(rows), cols)))
+
+rows = [[1.0, 2.0, 3.0], [4.0, 5.0, 7.0], [2.0, 0.0, 1.0]]
+a, b = Matrix(rows), borrowed(rows)
+assert b == a and b.tolist() == rows and b.shape == (3, 3)
+assert b + b == a + a and b - a == Matrix.zeros(3, 3) and a - b == b - a
+assert b @ b == a @ a and b @ a == a @ a and 2 * b == 2 * a and -b == -a
+assert b.T == a.T and b.singular_values() == a.singular_values()
+assert b.solve(b) == a.solve(a) and b.lu().det() == a.lu().det()
+
+empty = Matrix(memoryview(Matrix.zeros(0, 3)))
+assert empty.shape == (0, 3) and empty.T.shape == (3, 0) and (empty @ empty.T).shape == (0, 0)
+");
+    }
+
+    #[test]
+    fn test_decompositions() {
+        run(c"
+from linalg import Matrix, LinAlgError
+
+def close(x, y):
+    return all(abs(p - q) < 1e-10 for r, s in zip(x.tolist(), y.tolist()) for p, q in zip(r, s))
+
+a = Matrix([[4, 12, -16], [12, 37, -43], [-16, -43, 98]])
+b = Matrix([[1], [2], [3]])
+
+lu = a.lu()
+rows = a.tolist()
+assert close(lu.l @ lu.u, Matrix([rows[i] for i in lu.permutation]))
+assert abs(lu.det() - 36) < 1e-9
+assert close(a @ lu.solve(b), b)
+
+cholesky = a.cholesky()
+assert cholesky.l.tolist() == [[2, 0, 0], [6, 1, 0], [-8, 5, 3]]
+assert close(cholesky.solve(b), a.solve(b))
+assert 0 < cholesky.rcond() <= 1
+
+values = a.singular_values()
+assert len(values) == 3 and values == sorted(values, reverse=True)
+
+try:
+    Matrix([[1, 2], [2, 4]]).solve([[1], [2]])
+    assert False
+except LinAlgError as err:
+    assert 'singular' in str(err)
+");
+    }
+}
@@ -1275,6 +1275,31 @@ pub fn bench_1024x1024_matrix_muls(c: &mut Criterion) {
     group.finish();
 }
 
+pub fn bench_2048x2048_matrix_transposes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("2048x2048 Matrix Transpose");
+    let mut rng = rand::thread_rng();
+
+    // f32
+    {
+        let m: Matrix<f32> = Matrix::random_uniform(2048, 2048, -100.0, 100.0, &mut rng);
+        group.bench_function("f32", |b| b.iter(|| black_box(&m).transpose()));
+    }
+
+    // f64
+    {
+        let m: Matrix<f64> = Matrix::random_uniform(2048, 2048, -100.0, 100.0, &mut rng);
+        group.bench_function("f64", |b| b.iter(|| black_box(&m).transpose()));
+    }
+
+    // c64
+    {
+        let m: Matrix<c64> = Matrix::random_normal(2048, 2048, &mut rng);
+        group.bench_function("c64", |b| b.iter(|| black_box(&m).transpose()));
+    }
+
+    group.finish();
+}
+
 criterion_group! {
     name = small_matrix_addition;
     config = Criterion::default().measurement_time(Duration::from_secs(10));
@@ -1316,9 +1341,17 @@ criterion_group! {
         bench_1024x1024_matrix_muls
 }
 
+criterion_group! {
+    name = large_matrix_transpose;
+    config = Criterion::default().measurement_time(Duration::from_secs(30));
+    targets =
+        bench_2048x2048_matrix_transposes
+}
+
 criterion_main!(
     small_matrix_addition,
     large_matrix_addition,
+    large_matrix_transpose,
     small_matrix_subtraction,
     small_matrix_multiplication,
     // large_matrix_multiplication
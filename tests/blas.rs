@@ -0,0 +1,97 @@
+#![cfg(feature = "blas")]
+
+// Checks the BLAS and LAPACK backed paths against the native kernels they replace
+
+use linalg::matrix::mat::Matrix;
+use linalg::num::{c32, c64};
+use num::{complex::ComplexFloat, Float};
+
+fn assert_close<T: ComplexFloat>(actual: &Matrix<T>, expected: &Matrix<T>, rows: usize, cols: usize)
+where
+    T::Real: Into<f64>,
+{
+    let tol = T::Real::epsilon().into().sqrt();
+    for i in 0..rows {
+        for j in 0..cols {
+            let (a, e) = (actual[(i, j)], expected[(i, j)]);
+            let scale = e.abs().into().max(1.0);
+            assert!((a - e).abs().into() / scale < tol, "mismatch at ({i}, {j})");
+        }
+    }
+}
+
+macro_rules! backend_tests {
+    ($name:ident, $t:ty, $scalar:expr) => {
+        mod $name {
+            use super::*;
+
+            const SCALAR: fn(f64, f64) -> $t = $scalar;
+
+            fn value(i: usize, j: usize) -> $t {
+                SCALAR(
+                    ((i * 7 + j * 3) % 11) as f64 - 5.0,
+                    ((i + 2 * j) % 5) as f64,
+                )
+            }
+
+            fn shifted(a: &Matrix<$t>, n: usize, shift: f64) -> Matrix<$t> {
+                Matrix::from_fn(n, n, |i, j| {
+                    a[(i, j)] + SCALAR(if i == j { shift } else { 0.0 }, 0.0)
+                })
+            }
+
+            #[test]
+            fn test_multiply() {
+                let a = Matrix::from_fn(13, 9, value);
+                let b = Matrix::from_fn(9, 21, |i, j| value(j, i + 1));
+                assert_close(&(&a * &b), &a.multiply_native(&b), 13, 21);
+            }
+
+            #[test]
+            fn test_lu() {
+                // Diagonally dominant, so the pivots are well away from zero
+                let a = shifted(&Matrix::from_fn(12, 12, value), 12, 40.0);
+                let b = Matrix::from_fn(12, 3, value);
+                let blas = a.lu().unwrap();
+                let native = a.lu_native().unwrap();
+                assert_close(&blas.solve(&b), &native.solve(&b), 12, 3);
+                assert_close(&(&a * &blas.solve(&b)), &b, 12, 3);
+            }
+
+            #[test]
+            fn test_cholesky() {
+                // G G^H plus a shift is Hermitian positive definite
+                let g = Matrix::from_fn(10, 10, value);
+                let gram = Matrix::from_fn(10, 10, |i, j| {
+                    (0..10).fold(SCALAR(0.0, 0.0), |s, k| s + g[(i, k)] * g[(j, k)].conj())
+                });
+                let a = shifted(&gram, 10, 10.0);
+                let b = Matrix::from_fn(10, 2, value);
+                let blas = a.cholesky().unwrap();
+                let native = a.cholesky_native().unwrap();
+                assert_close(blas.l(), native.l(), 10, 10);
+                assert_close(&blas.solve(&b), &native.solve(&b), 10, 2);
+            }
+        }
+    };
+}
+
+backend_tests!(f32_backend, f32, |re, _| re as f32);
+backend_tests!(f64_backend, f64, |re, _| re);
+backend_tests!(c32_backend, c32, |re, im| c32::new(re as f32, im as f32));
+backend_tests!(c64_backend, c64, |re, im| c64::new(re, im));
+
+#[test]
+fn test_errors_match_native() {
+    let singular = Matrix::new([[1.0, 2.0], [2.0, 4.0]]);
+    assert_eq!(
+        singular.lu().unwrap_err(),
+        singular.lu_native().unwrap_err()
+    );
+
+    let indefinite = Matrix::new([[1.0, 2.0], [2.0, 1.0]]);
+    assert_eq!(
+        indefinite.cholesky().unwrap_err(),
+        indefinite.cholesky_native().unwrap_err()
+    );
+}
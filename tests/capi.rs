@@ -0,0 +1,101 @@
+// Keeps include/linalg.h in step with src/capi.rs and runs a C program against the library
+
+use std::{env, path::Path, process::Command};
+
+fn generate_header(root: &Path) -> String {
+    let config = cbindgen::Config::from_file(root.join("cbindgen.toml")).unwrap();
+    let mut header = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(root.join("src/capi.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut header);
+    String::from_utf8(header).unwrap()
+}
+
+#[test]
+fn test_header_is_current() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let path = root.join("include/linalg.h");
+    let generated = generate_header(root);
+    if env::var_os("LINALG_UPDATE_HEADER").is_some() {
+        std::fs::write(&path, generated).unwrap();
+        return;
+    }
+    let committed = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        committed == generated,
+        "include/linalg.h is out of date, run `LINALG_UPDATE_HEADER=1 cargo test --test capi`"
+    );
+}
+
+// Builds the shared library with `profile`, then compiles and runs the C test against it
+#[cfg(unix)]
+fn run_c_program(profile: &str) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let out = Path::new(env!("CARGO_TARGET_TMPDIR")).join("capi");
+
+    // The test harness only builds the rlib, so build the shared library into a separate target
+    // directory, which also keeps clear of the lock held by the outer cargo
+    let status = Command::new(env!("CARGO"))
+        .args([
+            "build",
+            "--lib",
+            "--quiet",
+            "--profile",
+            profile,
+            "--manifest-path",
+        ])
+        .arg(root.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(out.join("target"))
+        .status()
+        .unwrap();
+    assert!(status.success(), "building the shared library failed");
+
+    let lib_dir = out.join("target").join(match profile {
+        "dev" => "debug",
+        _ => profile,
+    });
+    let exe = out.join(format!("capi_test_{profile}"));
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(cc)
+        .args(["-std=c99", "-Wall", "-Werror", "-o"])
+        .arg(&exe)
+        .arg(root.join("tests/capi/capi_test.c"))
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .args(["-llinalg", "-lm"])
+        .status()
+        .expect("a C compiler is needed, set CC to choose one");
+    assert!(status.success(), "compiling the C test failed");
+
+    // Cargo points the loader at its own target directory, which may hold an older build
+    let output = Command::new(&exe)
+        .env_remove("LD_LIBRARY_PATH")
+        .env_remove("DYLD_LIBRARY_PATH")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "C test failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_c_program() {
+    run_c_program("dev");
+}
+
+// The shipped library is a release build, with its own profile settings (LTO, unwinding)
+#[cfg(unix)]
+#[test]
+fn test_c_program_release() {
+    run_c_program("release");
+}
@@ -0,0 +1,128 @@
+/* Exercises the C interface through the generated header, built and run by tests/capi.rs */
+#include <math.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "linalg.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                                  \
+    do {                                                                             \
+        if (!(cond)) {                                                               \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #cond); \
+            failures++;                                                              \
+        }                                                                            \
+    } while (0)
+
+static int close_to(const double *actual, const double *expected, size_t n, double tol) {
+    for (size_t i = 0; i < n; i++) {
+        if (fabs(actual[i] - expected[i]) > tol) {
+            return 0;
+        }
+    }
+    return 1;
+}
+
+static void test_f64(void) {
+    const double a_data[] = {4.0, 1.0, 2.0, 3.0};
+    const double b_data[] = {1.0, 2.0, 3.0, 4.0};
+    LinalgMatrixF64 *a = NULL, *b = NULL, *m = NULL;
+    CHECK(linalg_f64_new(a_data, 2, 2, &a) == LINALG_STATUS_OK);
+    CHECK(linalg_f64_new(b_data, 2, 2, &b) == LINALG_STATUS_OK);
+    CHECK(linalg_f64_rows(a) == 2 && linalg_f64_cols(a) == 2);
+
+    const double sum[] = {5.0, 3.0, 5.0, 7.0};
+    CHECK(linalg_f64_add(a, b, &m) == LINALG_STATUS_OK);
+    CHECK(close_to(linalg_f64_data(m), sum, 4, 0.0));
+    linalg_f64_free(m);
+
+    const double difference[] = {3.0, -1.0, -1.0, -1.0};
+    CHECK(linalg_f64_sub(a, b, &m) == LINALG_STATUS_OK);
+    CHECK(close_to(linalg_f64_data(m), difference, 4, 0.0));
+    linalg_f64_free(m);
+
+    const double product[] = {7.0, 12.0, 11.0, 16.0};
+    CHECK(linalg_f64_mul(a, b, &m) == LINALG_STATUS_OK);
+    CHECK(close_to(linalg_f64_data(m), product, 4, 1e-12));
+    linalg_f64_free(m);
+
+    const double transposed[] = {4.0, 2.0, 1.0, 3.0};
+    CHECK(linalg_f64_transpose(a, &m) == LINALG_STATUS_OK);
+    CHECK(close_to(linalg_f64_data(m), transposed, 4, 0.0));
+    linalg_f64_free(m);
+
+    /* a x = b has x = a^-1 b */
+    const double x[] = {0.0, 0.2, 1.0, 1.2};
+    double rcond = 0.0;
+    CHECK(linalg_f64_solve(a, b, &m, &rcond) == LINALG_STATUS_OK);
+    CHECK(close_to(linalg_f64_data(m), x, 4, 1e-12));
+    CHECK(rcond > 0.1 && rcond <= 1.0);
+    linalg_f64_free(m);
+
+    const double singular_data[] = {1.0, 2.0, 2.0, 4.0};
+    LinalgMatrixF64 *singular = NULL;
+    CHECK(linalg_f64_new(singular_data, 2, 2, &singular) == LINALG_STATUS_OK);
+    m = NULL;
+    CHECK(linalg_f64_solve(singular, b, &m, NULL) == LINALG_STATUS_SINGULAR);
+    CHECK(m == NULL);
+    CHECK(strstr(linalg_last_error(), "singular") != NULL);
+    linalg_f64_free(singular);
+
+    LinalgMatrixF64 *row = NULL;
+    CHECK(linalg_f64_new(a_data, 1, 4, &row) == LINALG_STATUS_OK);
+    CHECK(linalg_f64_add(a, row, &m) == LINALG_STATUS_DIMENSION_MISMATCH);
+    CHECK(linalg_f64_mul(row, a, &m) == LINALG_STATUS_DIMENSION_MISMATCH);
+    CHECK(linalg_f64_solve(row, b, &m, NULL) == LINALG_STATUS_DIMENSION_MISMATCH);
+    CHECK(linalg_f64_add(a, NULL, &m) == LINALG_STATUS_NULL_POINTER);
+    CHECK(strcmp(linalg_status_message(LINALG_STATUS_NULL_POINTER), "Null pointer argument") == 0);
+    CHECK(strcmp(linalg_status_message(-1), "Unknown status") == 0);
+    linalg_f64_free(row);
+
+    linalg_f64_free(a);
+    linalg_f64_free(b);
+    linalg_f64_free(NULL);
+}
+
+static void test_f32(void) {
+    const float a_data[] = {2.0f, 0.0f, 1.0f, 0.0f, 3.0f, -1.0f};
+    LinalgMatrixF32 *a = NULL, *t = NULL, *p = NULL;
+    CHECK(linalg_f32_new(a_data, 2, 3, &a) == LINALG_STATUS_OK);
+    CHECK(linalg_f32_transpose(a, &t) == LINALG_STATUS_OK);
+    CHECK(linalg_f32_rows(t) == 3 && linalg_f32_cols(t) == 2);
+
+    /* a a^T */
+    CHECK(linalg_f32_mul(a, t, &p) == LINALG_STATUS_OK);
+    const float *data = linalg_f32_data(p);
+    CHECK(data[0] == 5.0f && data[1] == -1.0f && data[2] == -1.0f && data[3] == 10.0f);
+
+    const float rhs_data[] = {4.0f, 9.0f};
+    LinalgMatrixF32 *rhs = NULL, *x = NULL;
+    float rcond = 0.0f;
+    CHECK(linalg_f32_new(rhs_data, 2, 1, &rhs) == LINALG_STATUS_OK);
+    CHECK(linalg_f32_solve(p, rhs, &x, &rcond) == LINALG_STATUS_OK);
+    data = linalg_f32_data(x);
+    CHECK(fabsf(data[0] - 1.0f) < 1e-5f && fabsf(data[1] - 1.0f) < 1e-5f);
+    CHECK(linalg_f32_solve(a, rhs, &x, NULL) == LINALG_STATUS_NOT_SQUARE);
+
+    LinalgMatrixF32 *empty = NULL;
+    CHECK(linalg_f32_new(NULL, 0, 3, &empty) == LINALG_STATUS_OK);
+    CHECK(linalg_f32_rows(empty) == 0 && linalg_f32_cols(empty) == 3);
+
+    linalg_f32_free(empty);
+    linalg_f32_free(x);
+    linalg_f32_free(rhs);
+    linalg_f32_free(p);
+    linalg_f32_free(t);
+    linalg_f32_free(a);
+}
+
+int main(void) {
+    test_f64();
+    test_f32();
+    if (failures > 0) {
+        fprintf(stderr, "%d checks failed\n", failures);
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,75 @@
+# Writes the MAT-file fixtures the way MATLAB lays them out: small-format names, doubles stored
+# in the narrowest integer type that holds them, and variables in zlib-compressed elements.
+import struct
+import zlib
+
+
+def element(e, ty, data, small=True):
+    if small and len(data) <= 4:
+        return struct.pack(e + "I", (len(data) << 16) | ty) + data.ljust(4, b"\0")
+    pad = -len(data) % 8
+    return struct.pack(e + "II", ty, len(data)) + data + b"\0" * pad
+
+
+def array(e, cls, dims, name, parts, flags=0, nnz=0, small=True):
+    body = element(e, 6, struct.pack(e + "II", cls | flags, nnz))
+    body += element(e, 5, struct.pack(e + "%di" % len(dims), *dims))
+    body += element(e, 1, name.encode(), small)
+    for ty, fmt, values in parts:
+        body += element(e, ty, struct.pack(e + "%d%s" % (len(values), fmt), *values))
+    return struct.pack(e + "II", 14, len(body)) + body
+
+
+def compressed(e, matrix):
+    data = zlib.compress(matrix)
+    return struct.pack(e + "II", 15, len(data)) + data
+
+
+def header(e):
+    text = b"MATLAB 5.0 MAT-file, Platform: GLNXA64, Created on: Mon Jan  6 10:00:00 2025"
+    indicator = b"IM" if e == "<" else b"MI"
+    return text.ljust(116, b" ") + b"\0" * 8 + struct.pack(e + "H", 0x0100) + indicator
+
+
+def little_endian():
+    e = "<"
+    out = header(e)
+    # a = [1 2 3; 4 5 6], saved as doubles stored in uint8
+    out += compressed(e, array(e, 6, [2, 3], "a", [(2, "B", [1, 4, 2, 5, 3, 6])]))
+    # b = [0.5 -1.25], uncompressed
+    out += array(e, 6, [1, 2], "b", [(9, "d", [0.5, -1.25])])
+    # z = single([1+2i; -3i])
+    out += compressed(
+        e, array(e, 7, [2, 1], "z", [(7, "f", [1, 0]), (7, "f", [2, -3])], flags=0x0800)
+    )
+    # k = int16([-2 300])
+    out += array(e, 10, [1, 2], "k", [(3, "h", [-2, 300])])
+    # s = sparse([1 3 2], [1 1 3], [1.5 2 -4], 3, 3)
+    out += compressed(
+        e,
+        array(
+            e,
+            5,
+            [3, 3],
+            "s",
+            [(5, "i", [0, 2, 1]), (5, "i", [0, 2, 2, 3]), (9, "d", [1.5, 2, -4])],
+            nnz=3,
+        ),
+    )
+    # c = {}, an empty cell the reader skips
+    out += array(e, 1, [0, 0], "c", [])
+    return out
+
+
+def big_endian():
+    e = ">"
+    out = header(e)
+    # m = [1.5 2; -3 4]
+    out += array(e, 6, [2, 2], "m", [(9, "d", [1.5, -3, 2, 4])], small=False)
+    return out
+
+
+with open("matlab_le.mat", "wb") as f:
+    f.write(little_endian())
+with open("matlab_be.mat", "wb") as f:
+    f.write(big_endian())